@@ -68,6 +68,7 @@ mod convert {
     use crate::core::api::tv_maze::series_information::SeriesMainInformation;
     use crate::core::api::tv_maze::show_lookup::{show_lookup, Id};
     use crate::core::api::tv_maze::ApiError as TvMazeApiError;
+    use crate::core::caching::fetch_limiter::FETCH_LIMITER;
     use crate::core::caching::series_information::cache_series_information;
     use crate::core::database::Series;
 
@@ -157,7 +158,7 @@ mod convert {
             .cloned()
             .map(|show| {
                 let progress_sender = progress_sender.clone();
-                tokio::spawn(async move {
+                FETCH_LIMITER.spawn(async move {
                     let res = trakt_show_to_troxide(&show).await;
                     if let Err(err) = progress_sender.send(ProgressData::Progressing).await {
                         tracing::warn!("failed to send import progress as: {}", err);
@@ -167,7 +168,7 @@ mod convert {
             })
             .collect();
 
-        for (show, handle) in trakt_shows.into_iter().zip(handles.into_iter()) {
+        for (show, handle) in trakt_shows.into_iter().zip(handles) {
             if let Some(id_and_series) = handle.await.expect("failed to join all the handles")? {
                 ids_and_series.push(id_and_series);
             } else {
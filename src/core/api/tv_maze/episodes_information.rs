@@ -1,6 +1,7 @@
-use chrono::{DateTime, Datelike, Duration, Local, Timelike, Utc};
+use chrono::{DateTime, Duration, Local, Utc};
 
 use super::{series_information::SeriesMainInformation, *};
+use crate::core::date_time_display::DateTimeDisplay;
 
 const EPISODE_INFORMATION_ADDRESS: &str =
     "https://api.tvmaze.com/shows/SERIES-ID/episodebynumber?season=SEASON&number=EPISODE";
@@ -112,34 +113,20 @@ impl EpisodeReleaseTime {
         let local_time = Utc::now().with_timezone(&Local);
         self.release_time > local_time
     }
+
+    pub fn local_date_time(&self) -> DateTime<Local> {
+        self.release_time
+    }
 }
 
 impl std::fmt::Display for EpisodeReleaseTime {
+    /// Displays the release time in the time zone and formats of the locale settings
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        /// appends zero the minute digit if it's below 10 for better display
-        fn append_zero(num: u32) -> String {
-            if num < 10 {
-                format!("0{num}")
-            } else {
-                format!("{num}")
-            }
-        }
-
-        let (is_pm, hour) = self.release_time.hour12();
-        let pm_am = if is_pm { "p.m." } else { "a.m." };
-
-        let minute = append_zero(self.release_time.minute());
-
-        let str = format!(
-            "{} {} {}:{} {}",
-            self.release_time.date_naive(),
-            self.release_time.weekday(),
-            hour,
-            minute,
-            pm_am
-        );
-
-        write!(f, "{}", str)
+        write!(
+            f,
+            "{}",
+            DateTimeDisplay::from_settings().date_time(&self.release_time)
+        )
     }
 }
 
@@ -152,18 +139,14 @@ pub async fn get_episode_information(
     let url = url.replace("SEASON", &season.to_string());
     let url = url.replace("EPISODE", &episode.to_string());
 
-    let prettified_json = get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)?;
+    let prettified_json = get_pretty_json_from_url(url).await?;
 
     deserialize_json(&prettified_json)
 }
 
 pub async fn get_episode_list(series_id: u32) -> Result<(Vec<Episode>, String), ApiError> {
     let url = EPISODE_LIST_ADDRESS.replace("SERIES-ID", &series_id.to_string());
-    let prettified_json = get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)?;
+    let prettified_json = get_pretty_json_from_url(url).await?;
 
     Ok((deserialize_json(&prettified_json)?, prettified_json))
 }
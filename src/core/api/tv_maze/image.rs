@@ -3,75 +3,130 @@ use std::io::Write;
 use bytes::Bytes;
 use tracing::error;
 
+const POSTER_THUMB_WIDTH: u32 = 210;
+const POSTER_THUMB_HEIGHT: u32 = 295;
 const POSTER_WIDTH: u32 = 480;
 const POSTER_HEIGHT: u32 = 853;
 const BACKGROUND_WIDTH: u32 = 1280;
 const BACKGROUND_HEIGHT: u32 = 720;
+const CAST_AVATAR_WIDTH: u32 = 160;
+const CAST_AVATAR_HEIGHT: u32 = 240;
+const EPISODE_STILL_WIDTH: u32 = 256;
+const EPISODE_STILL_HEIGHT: u32 = 144;
 
+/// The resolution of the image url being loaded together with how the image is going to be displayed
+#[derive(Clone, Copy)]
 pub enum ImageResolution {
     Original(ImageKind),
-    Medium,
+    Medium(ImageKind),
+}
+
+impl ImageResolution {
+    pub fn image_kind(&self) -> ImageKind {
+        match self {
+            ImageResolution::Original(image_kind) | ImageResolution::Medium(image_kind) => {
+                *image_kind
+            }
+        }
+    }
 }
 
+/// The different ways images are displayed in the GUI, each having it's own maximum size
 #[derive(Clone, Copy)]
 pub enum ImageKind {
+    /// Posters displayed in collections of series i.e. Discover and My Shows tabs
+    PosterThumb,
+    /// The main poster in the series page
     Poster,
     Background,
+    CastAvatar,
+    /// The landscape stills of episodes in the watchlist and season lists
+    EpisodeStill,
 }
 
-/// Loads the image from the provided url
-///
-/// Since Original images from TvMaze may have extremely high resolution up to 4k which can cause `wgpu` to crash,
-/// this function will thumbnail the original image to the size that is good enough to be displayed in the GUI.
-pub async fn load_image(image_url: String, image_resolution: ImageResolution) -> Option<Bytes> {
-    loop {
-        match reqwest::get(&image_url).await {
-            Ok(response) => {
-                if let Ok(bytes) = response.bytes().await {
-                    let image = image::load_from_memory(&bytes)
-                        .map_err(|err| error!("failed to load image from the api: {}", err))
-                        .ok()?;
-
-                    break match image_resolution {
-                        ImageResolution::Original(image_kind) => {
-                            if should_lower_resolution(&image, image_kind) {
-                                lower_image_resolution(image, image_kind)
-                            } else {
-                                Some(bytes)
-                            }
-                        }
-                        ImageResolution::Medium => Some(bytes),
-                    };
-                }
-            }
-            Err(ref err) => {
-                if err.is_request() {
-                    super::random_async_sleep().await;
-                } else {
-                    break None;
-                }
-            }
+impl ImageKind {
+    /// The name used to differentiate cached images of the same url scaled for different `ImageKind`s
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ImageKind::PosterThumb => "poster-thumb",
+            ImageKind::Poster => "poster",
+            ImageKind::Background => "background",
+            ImageKind::CastAvatar => "cast-avatar",
+            ImageKind::EpisodeStill => "episode-still",
+        }
+    }
+
+    /// The maximum width and height of the `ImageKind`
+    fn max_dimensions(&self) -> (u32, u32) {
+        match self {
+            ImageKind::PosterThumb => (POSTER_THUMB_WIDTH, POSTER_THUMB_HEIGHT),
+            ImageKind::Poster => (POSTER_WIDTH, POSTER_HEIGHT),
+            ImageKind::Background => (BACKGROUND_WIDTH, BACKGROUND_HEIGHT),
+            ImageKind::CastAvatar => (CAST_AVATAR_WIDTH, CAST_AVATAR_HEIGHT),
+            ImageKind::EpisodeStill => (EPISODE_STILL_WIDTH, EPISODE_STILL_HEIGHT),
         }
     }
 }
 
-fn should_lower_resolution(image: &image::DynamicImage, image_kind: ImageKind) -> bool {
-    match image_kind {
-        ImageKind::Poster => image.height() > POSTER_HEIGHT || image.width() > POSTER_WIDTH,
-        ImageKind::Background => {
-            image.height() > BACKGROUND_HEIGHT || image.width() > BACKGROUND_WIDTH
+/// Loads the image from the provided url as it is
+pub async fn load_image(image_url: String) -> Option<Bytes> {
+    let response = match super::get_response(&image_url).await {
+        Ok(response) => response,
+        // Not worth reporting as it's expected for uncached images
+        Err(super::ApiError::Offline) => return None,
+        Err(err) => {
+            error!("failed to load image: {}", err);
+            return None;
         }
+    };
+
+    response
+        .bytes()
+        .await
+        .map_err(|err| error!("failed to load image bytes: {}", err))
+        .ok()
+}
+
+/// Scales the image down to the size of the given `ImageKind`
+///
+/// Since Original images from TvMaze may have extremely high resolution up to 4k which can cause `wgpu` to crash,
+/// and decoding big images for small posters is a waste of memory, images are thumbnailed to the size that is good
+/// enough to be displayed in the GUI.
+///
+/// Returns `None` when the image is already small enough or when it can't be decoded.
+pub fn scale_image(image_bytes: &[u8], image_kind: ImageKind) -> Option<Bytes> {
+    // Reading only the image header first so that images that are small enough are never decoded
+    let (width, height) = image::ImageReader::new(std::io::Cursor::new(image_bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .map_err(|err| error!("failed to read image dimensions: {}", err))
+        .ok()?;
+
+    if !should_lower_resolution(width, height, image_kind) {
+        return None;
     }
+
+    let image = image::load_from_memory(image_bytes)
+        .map_err(|err| error!("failed to load image: {}", err))
+        .ok()?;
+
+    lower_image_resolution(image, image_kind)
+}
+
+fn should_lower_resolution(width: u32, height: u32, image_kind: ImageKind) -> bool {
+    let (max_width, max_height) = image_kind.max_dimensions();
+    height > max_height || width > max_width
 }
 
 fn lower_image_resolution(
     image: image::DynamicImage,
     image_kind: ImageKind,
 ) -> Option<bytes::Bytes> {
-    let img = match image_kind {
-        ImageKind::Poster => image.thumbnail(POSTER_WIDTH, POSTER_WIDTH),
-        ImageKind::Background => image.thumbnail(BACKGROUND_WIDTH, BACKGROUND_HEIGHT),
-    };
+    let (max_width, max_height) = image_kind.max_dimensions();
+
+    // Jpeg has no alpha channel, so the image is converted to rgb before encoding
+    let img = image::DynamicImage::ImageRgb8(image.thumbnail(max_width, max_height).to_rgb8());
 
     let mut writer = std::io::BufWriter::new(vec![]);
 
@@ -89,3 +144,45 @@ fn lower_image_resolution(
 
     Some(bytes::Bytes::copy_from_slice(writer.get_ref()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded_image(width: u32, height: u32) -> Vec<u8> {
+        let image = image::DynamicImage::new_rgb8(width, height);
+        let mut image_bytes = std::io::Cursor::new(vec![]);
+        image
+            .write_to(&mut image_bytes, image::ImageFormat::Png)
+            .unwrap();
+        image_bytes.into_inner()
+    }
+
+    fn dimensions(image_bytes: &[u8]) -> (u32, u32) {
+        let image = image::load_from_memory(image_bytes).unwrap();
+        (image.width(), image.height())
+    }
+
+    #[test]
+    fn should_lower_resolution_test() {
+        assert!(!should_lower_resolution(210, 295, ImageKind::PosterThumb));
+        assert!(should_lower_resolution(211, 295, ImageKind::PosterThumb));
+        assert!(should_lower_resolution(210, 296, ImageKind::PosterThumb));
+        assert!(!should_lower_resolution(250, 140, ImageKind::EpisodeStill));
+        assert!(should_lower_resolution(250, 140, ImageKind::CastAvatar));
+    }
+
+    #[test]
+    fn scale_image_test() {
+        // Scaling keeps the aspect ratio
+        let poster = scale_image(&encoded_image(680, 1000), ImageKind::PosterThumb).unwrap();
+        assert_eq!(dimensions(&poster), (201, 295));
+
+        let still = scale_image(&encoded_image(3840, 2160), ImageKind::EpisodeStill).unwrap();
+        assert_eq!(dimensions(&still), (256, 144));
+
+        // Small enough images are used as they are
+        assert!(scale_image(&encoded_image(250, 140), ImageKind::EpisodeStill).is_none());
+        assert!(scale_image(b"not an image", ImageKind::Poster).is_none());
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::core::offline::OFFLINE;
+
 pub mod episodes_information;
 pub mod image;
 pub mod people;
@@ -12,10 +14,15 @@ pub mod show_lookup;
 pub mod tv_schedule;
 pub mod updates;
 
+#[cfg(test)]
+pub mod test_fixtures;
+
 #[derive(Debug, Error)]
 pub enum ApiError {
     #[error("network error during request")]
     Network(reqwest::Error),
+    #[error("not available while offline")]
+    Offline,
     #[error("tvmaze api error when deserializing json: unexpected '{0}'")]
     Deserialization(String, serde_json::Error),
     #[error("errored json from tvmaze: name: '{0}', message: '{1}'")]
@@ -77,28 +84,110 @@ pub fn deserialize_json<'a, T: serde::Deserialize<'a>>(
 }
 
 /// Requests text response from the provided url
-async fn get_pretty_json_from_url(url: String) -> Result<String, reqwest::Error> {
-    let response = loop {
-        match reqwest::get(&url).await {
-            Ok(response) => break response,
-            Err(err) => {
-                if err.is_request() {
-                    random_async_sleep().await;
-                } else {
-                    return Err(err);
-                }
-            }
-        }
-    };
+async fn get_pretty_json_from_url(url: String) -> Result<String, ApiError> {
+    let response = get_response(&url).await?;
 
-    let text = response.text().await?;
+    let text = response.text().await.map_err(ApiError::Network)?;
 
     Ok(json::stringify_pretty(json::parse(&text).unwrap(), 1))
 }
 
+/// How many times a failed request is retried before giving up
+const MAX_RETRIES: usize = 5;
+
+/// Performs a get request to the provided url, retrying a few times on request errors
+///
+/// Fails right away with `ApiError::Offline` when the program is offline, see the `offline` module.
+async fn get_response(url: &str) -> Result<reqwest::Response, ApiError> {
+    retry_request(
+        || async move {
+            if OFFLINE.should_skip_requests() {
+                return Err(ApiError::Offline);
+            }
+
+            let result = reqwest::get(url).await;
+            OFFLINE.report_request_result(&result);
+            result.map_err(ApiError::Network)
+        },
+        |err| matches!(err, ApiError::Network(err) if err.is_request() && !err.is_connect()),
+    )
+    .await
+}
+
+/// Performs the request, retrying it at most `MAX_RETRIES` times when it fails with an error
+/// that should be retried
+async fn retry_request<T, F, Fut>(
+    mut request: F,
+    should_retry: impl Fn(&ApiError) -> bool,
+) -> Result<T, ApiError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, ApiError>>,
+{
+    let mut retries = 0;
+    loop {
+        match request().await {
+            Err(err) if retries < MAX_RETRIES && should_retry(&err) => {
+                retries += 1;
+                random_async_sleep().await;
+            }
+            result => break result,
+        }
+    }
+}
+
 /// Sleeps the current thread asynchronously between 0-0.2 seconds choosing a random
 /// value in between.
 async fn random_async_sleep() {
     let random_val = rand::random::<u64>() / 100_000_000_000_000_000;
     tokio::time::sleep(std::time::Duration::from_millis(random_val)).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn retry_request_test() {
+        let attempts = AtomicUsize::new(0);
+        let res: Result<(), ApiError> = retry_request(
+            || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(ApiError::Offline)
+            },
+            |_| true,
+        )
+        .await;
+        assert!(matches!(res, Err(ApiError::Offline)));
+        assert_eq!(attempts.load(Ordering::SeqCst), MAX_RETRIES + 1);
+
+        // Errors that should not be retried fail right away
+        let attempts = AtomicUsize::new(0);
+        let res: Result<(), ApiError> = retry_request(
+            || async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(ApiError::Offline)
+            },
+            |_| false,
+        )
+        .await;
+        assert!(res.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let attempts = AtomicUsize::new(0);
+        let res = retry_request(
+            || async {
+                if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    Err(ApiError::Offline)
+                } else {
+                    Ok("response")
+                }
+            },
+            |_| true,
+        )
+        .await;
+        assert_eq!(res.unwrap(), "response");
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+}
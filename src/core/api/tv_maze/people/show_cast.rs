@@ -21,7 +21,5 @@ const SHOW_CAST_ADDRESS: &str = "https://api.tvmaze.com/shows/ID/cast";
 pub async fn get_show_cast(series_id: u32) -> Result<String, ApiError> {
     let url = SHOW_CAST_ADDRESS.replace("ID", &series_id.to_string());
 
-    get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)
+    get_pretty_json_from_url(url).await
 }
@@ -16,7 +16,5 @@ const SHOW_CREW_ADDRESS: &str = "https://api.tvmaze.com/shows/ID/crew";
 pub async fn get_show_crew(series_id: u32) -> Result<String, ApiError> {
     let url = SHOW_CREW_ADDRESS.replace("ID", &series_id.to_string());
 
-    get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)
+    get_pretty_json_from_url(url).await
 }
@@ -344,9 +344,7 @@ pub struct ExternalIds {
 }
 
 pub async fn get_series_main_info_with_url(url: String) -> Result<String, ApiError> {
-    get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)
+    get_pretty_json_from_url(url).await
 }
 
 pub async fn get_series_main_info_with_id(series_id: u32) -> Result<String, ApiError> {
@@ -357,9 +355,7 @@ pub async fn get_series_info_and_episode_list(
     series_id: u32,
 ) -> Result<SeriesMainInformation, ApiError> {
     let url = SERIES_INFO_AND_EPISODE_LIST.replace("ID", &series_id.to_string());
-    let pretty_json = get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)?;
+    let pretty_json = get_pretty_json_from_url(url).await?;
 
     deserialize_json(&pretty_json)
 }
@@ -14,9 +14,7 @@ pub struct SeriesSearchResult {
 pub async fn search_series(series_name: String) -> Result<Vec<SeriesSearchResult>, ApiError> {
     let url = format!("{}{}", SERIES_SEARCH_ADDRESS, series_name);
 
-    let prettified_json = get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)?;
+    let prettified_json = get_pretty_json_from_url(url).await?;
 
     deserialize_json(&prettified_json)
 }
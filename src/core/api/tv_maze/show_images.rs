@@ -62,9 +62,7 @@ const IMAGES_ADDRESS: &str = "https://api.tvmaze.com/shows/ID/images";
 pub async fn get_show_images(series_id: u32) -> Result<String, ApiError> {
     let url = IMAGES_ADDRESS.replace("ID", &series_id.to_string());
 
-    get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)
+    get_pretty_json_from_url(url).await
 }
 
 // /// Loads the most recent image banner from the provided series id
@@ -18,9 +18,7 @@ pub async fn show_lookup(show_id: Id) -> Result<Option<SeriesMainInformation>, A
         Id::Tvdb(tvdb_id) => format!("{}{}{}", SHOW_LOOKUP_ADDRESS, "thetvdb=", tvdb_id),
     };
 
-    let pretty_json_str = super::get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)?;
+    let pretty_json_str = super::get_pretty_json_from_url(url).await?;
 
     // handling the case when the show is not found
     if serde_json::from_str::<Option<()>>(&pretty_json_str).is_ok() {
@@ -0,0 +1,43 @@
+//! Minimal TvMaze data for tests, the fields the tests care about are set on the returned values
+
+use super::episodes_information::Episode;
+use super::series_information::SeriesMainInformation;
+
+/// A running series named after it's id
+pub fn series_info(id: u32) -> SeriesMainInformation {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "name": format!("Series {}", id),
+        "type": "Scripted",
+        "language": "English",
+        "genres": [],
+        "status": "Running",
+        "averageRuntime": 60,
+        "premiered": null,
+        "ended": null,
+        "rating": {"average": null},
+        "network": null,
+        "webChannel": null,
+        "summary": null,
+        "image": null,
+    }))
+    .unwrap()
+}
+
+/// An hour long episode without an air date or an image
+pub fn episode(season: u32, number: Option<u32>) -> Episode {
+    serde_json::from_value(serde_json::json!({
+        "name": "Episode",
+        "season": season,
+        "number": number,
+        "runtime": 60,
+        "airdate": null,
+        "airtime": "",
+        "airstamp": null,
+        "rating": {"average": null},
+        "image": null,
+        "summary": null,
+        "_links": {"show": {"href": "https://api.tvmaze.com/shows/1"}},
+    }))
+    .unwrap()
+}
@@ -18,9 +18,7 @@ const FULL_SCHEDULE: &str = "https://api.tvmaze.com/schedule/full";
 pub async fn get_episodes_with_date(date: Option<&str>) -> Result<Vec<Episode>, ApiError> {
     let url = SCHEDULE_ON_DATE_ADDRESS.replace("DATE", date.unwrap_or_default());
 
-    let prettified_json = get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)?;
+    let prettified_json = get_pretty_json_from_url(url).await?;
 
     deserialize_json::<Vec<Episode>>(&prettified_json).map(|mut episodes| {
         // deduplicating episodes that come from the same show
@@ -33,9 +31,7 @@ pub async fn get_episodes_with_date(date: Option<&str>) -> Result<Vec<Episode>,
 pub async fn get_episodes_with_country(country_iso: &str) -> Result<Vec<Episode>, ApiError> {
     let url = SCHEDULE_WITH_COUNTRY.replace("COUNTRY", country_iso);
 
-    let prettified_json = get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)?;
+    let prettified_json = get_pretty_json_from_url(url).await?;
 
     deserialize_json::<Vec<Episode>>(&prettified_json).map(|mut episodes| {
         // deduplicating episodes that come from the same show
@@ -49,7 +45,5 @@ pub async fn get_episodes_with_country(country_iso: &str) -> Result<Vec<Episode>
 /// Full schedule is a list of all future episodes known to TVmaze, regardless of their country.
 /// Returns the episodes in form of json string
 pub async fn get_full_schedule() -> Result<String, ApiError> {
-    get_pretty_json_from_url(FULL_SCHEDULE.to_string())
-        .await
-        .map_err(ApiError::Network)
+    get_pretty_json_from_url(FULL_SCHEDULE.to_string()).await
 }
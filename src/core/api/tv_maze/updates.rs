@@ -25,9 +25,7 @@ pub async fn get_shows_updates_index(
 
     tracing::info!("fetching shows updates");
 
-    let prettified_json = get_pretty_json_from_url(url)
-        .await
-        .map_err(ApiError::Network)?;
+    let prettified_json = get_pretty_json_from_url(url).await?;
 
     deserialize_json(&prettified_json)
 }
@@ -24,7 +24,27 @@
 //! be required in different places. A good example is the show cast, the same actor can appear
 //! in different shows and tvmaze reuses the same image for that actor. Another example is when
 //! searching for series, results may include a series that is already tracked and whose image
-//! is already cached.
+//! is already cached. Images are stored as they were downloaded, named after the hash of their url,
+//! with scaled down variants for the sizes the GUI displays them at, named by appending the variant
+//! name to the hash i.e. `<hash>-poster-thumb`. The size of this directory is limited by the
+//! `image_cache` module which evicts the least recently used images.
+//!
+//! ## Writing and reading cache
+//! All cache files are written to a temporary file first and then renamed to their actual path,
+//! this way a crash or a full disk never leaves a truncated file behind. Json cache files that
+//! still fail to deserialize are considered corrupt and are removed so that they are fetched again.
+//!
+//! ## Series changes
+//! When updating the cache, the replaced cache of tracked series is compared with the new one by the
+//! `series_changes` module so that changes like a series ending or a new season can be notified.
+//!
+//! ## Offline availability
+//! Series are normally cached lazily as their data gets displayed. The `offline_availability`
+//! module caches everything a series page needs upfront so that it can be used offline.
+//!
+//! ## In-memory cache
+//! Parsed `main-info` and `episode-list` files are also kept in memory by the `memory_cache`
+//! module so that switching tabs does not read and parse them from disk all over again.
 
 use bytes::Bytes;
 use std::io::{self, ErrorKind};
@@ -35,12 +55,19 @@ use super::api::tv_maze::{series_information::SeriesMainInformation, ApiError};
 use super::paths;
 use crate::core::api::tv_maze::{self, deserialize_json};
 use lazy_static::lazy_static;
+use serde::de::DeserializeOwned;
 use tokio::fs;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub mod cache_updating;
 pub mod episode_list;
+pub mod fetch_limiter;
+pub mod image_cache;
+pub mod memory_cache;
+pub mod offline_availability;
 pub mod people;
+pub mod seasons_list;
+pub mod series_changes;
 pub mod series_info_and_episode_list;
 pub mod series_information;
 pub mod series_list;
@@ -54,6 +81,7 @@ const SERIES_MAIN_INFORMATION_FILENAME: &str = "main-info";
 const SERIES_CAST_FILENAME: &str = "show-cast";
 const SERIES_CREW_FILENAME: &str = "show-crew";
 const SERIES_IMAGE_LIST_FILENAME: &str = "image-list";
+const SERIES_SEASON_LIST_FILENAME: &str = "season-list";
 
 lazy_static! {
     pub static ref CACHER: Cacher = Cacher::init();
@@ -70,6 +98,7 @@ pub enum CacheFilePath {
     SeriesShowCast(u32),
     SeriesShowCrew(u32),
     SeriesImageList(u32),
+    SeriesSeasonList(u32),
 }
 
 pub struct Cacher {
@@ -132,6 +161,11 @@ impl Cacher {
                 cache_folder.push(SERIES_IMAGE_LIST_FILENAME);
                 cache_folder
             }
+            CacheFilePath::SeriesSeasonList(series_id) => {
+                let mut cache_folder = self.get_series_cache_folder_path(series_id);
+                cache_folder.push(SERIES_SEASON_LIST_FILENAME);
+                cache_folder
+            }
         }
     }
 
@@ -144,43 +178,190 @@ impl Cacher {
 }
 
 /// Loads the image from the provided url
-pub async fn load_image(image_url: String, image_type: ImageResolution) -> Option<Bytes> {
-    // Hashing the image url as a file name as the forward slashes in web urls
-    // mimic paths
-    use sha2::{Digest, Sha256};
+///
+/// The downloaded image is cached once as the original, and every `ImageKind` it's displayed as gets
+/// it's own variant scaled down from the cached original, as the downloaded images can be as big as 4k.
+/// Images that are already small enough for the `ImageKind` are used as they are without a variant.
+pub async fn load_image(image_url: String, image_resolution: ImageResolution) -> Option<Bytes> {
+    let image_kind = image_resolution.image_kind();
 
-    let mut hasher = Sha256::new();
-    hasher.update(&image_url);
-    let image_hash = format!("{:x}", hasher.finalize());
+    let variant_path = get_image_variant_path(&image_url, image_kind);
+    if let Some(image_bytes) = read_cached_image(&variant_path).await.ok()? {
+        return Some(image_bytes);
+    }
 
-    let mut image_path = CACHER.get_cache_folder_path(CacheFolderType::Images);
-    image_path.push(&image_hash);
+    let original_bytes = load_original_image(image_url).await?;
 
-    match fs::read(&image_path).await {
-        Ok(image_bytes) => Some(Bytes::from(image_bytes)),
-        Err(err) => {
-            if err.kind() == ErrorKind::NotFound {
-                info!("falling back online for image with link {}", image_url);
-                if let Some(image_bytes) = tv_maze::image::load_image(image_url, image_type).await {
-                    write_cache(&image_bytes, &image_path).await;
-                    Some(image_bytes)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
+    let scaling_bytes = original_bytes.clone();
+    let scaled_bytes = tokio::task::spawn_blocking(move || {
+        tv_maze::image::scale_image(&scaling_bytes, image_kind)
+    })
+    .await
+    .expect("failed to join image scaling handle");
+
+    match scaled_bytes {
+        Some(scaled_bytes) => {
+            cache_image(&scaled_bytes, &variant_path).await;
+            Some(scaled_bytes)
+        }
+        None => Some(original_bytes),
+    }
+}
+
+/// The url and resolution episode stills are loaded with
+///
+/// Shared by the GUI and making series available offline so that both end up with the same cached image.
+pub fn episode_still_source(image: &tv_maze::Image) -> (String, ImageResolution) {
+    (
+        image.medium_image_url.clone(),
+        ImageResolution::Medium(ImageKind::EpisodeStill),
+    )
+}
+
+/// The path of the cached image of the given url scaled down for the given `ImageKind`
+///
+/// The file name starts with the hash of the url so that pinning by url covers all the variants.
+pub fn get_image_variant_path(image_url: &str, image_kind: ImageKind) -> path::PathBuf {
+    CACHER
+        .get_cache_folder_path(CacheFolderType::Images)
+        .join(format!(
+            "{}-{}",
+            image_cache::image_hash(image_url),
+            image_kind.variant_name()
+        ))
+}
+
+/// Loads the image from the provided url as it was downloaded, without any scaled down variant
+pub async fn load_original_image(image_url: String) -> Option<Bytes> {
+    let original_path = CACHER
+        .get_cache_folder_path(CacheFolderType::Images)
+        .join(image_cache::image_hash(&image_url));
+
+    match read_cached_image(&original_path).await.ok()? {
+        Some(image_bytes) => Some(image_bytes),
+        None => {
+            info!("falling back online for image with link {}", image_url);
+            let image_bytes = tv_maze::image::load_image(image_url).await?;
+            cache_image(&image_bytes, &original_path).await;
+            Some(image_bytes)
         }
     }
 }
 
+/// Reads the cached image at the given path, returning `Ok(None)` when it's not cached
+async fn read_cached_image(image_path: &path::Path) -> io::Result<Option<Bytes>> {
+    match fs::read(image_path).await {
+        Ok(image_bytes) => {
+            image_cache::record_image_use(image_path.to_owned());
+            Ok(Some(Bytes::from(image_bytes)))
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+async fn cache_image(image_bytes: &Bytes, image_path: &path::Path) {
+    write_cache(image_bytes, image_path).await;
+    tokio::spawn(image_cache::record_new_image(image_bytes.len() as u64));
+}
+
 pub async fn read_cache(cache_filepath: impl AsRef<path::Path>) -> io::Result<String> {
     fs::read_to_string(cache_filepath).await
 }
 
+/// Reads and deserializes the json cache at the given path
+///
+/// A cache that fails to deserialize is removed and reported as `ErrorKind::NotFound`
+/// so that callers fetch and cache it again as if it never existed.
+pub async fn read_json_cache<T: DeserializeOwned>(cache_filepath: &path::Path) -> io::Result<T> {
+    let json_string = read_cache(cache_filepath).await?;
+
+    match deserialize_json(&json_string) {
+        Ok(data) => Ok(data),
+        Err(err) => {
+            warn!(
+                "removing corrupt cache '{}': {}",
+                cache_filepath.display(),
+                err
+            );
+
+            fs::remove_file(cache_filepath).await.unwrap_or_else(|err| {
+                error!(
+                    "failed to remove corrupt cache '{}': {}",
+                    cache_filepath.display(),
+                    err
+                )
+            });
+
+            Err(io::Error::new(ErrorKind::NotFound, err))
+        }
+    }
+}
+
+/// Writes the data to a temporary file next to the given path and then renames it to the given path
+///
+/// Renaming is atomic, so the file at the given path is either the old one or the
+/// completely written new one, never a partially written one. The file is synced before
+/// renaming and the directory after it so that a crash doesn't leave an empty file behind.
+pub async fn atomic_write(
+    cache_filepath: &path::Path,
+    cache_data: impl AsRef<[u8]>,
+) -> io::Result<()> {
+    let mut temp_filename = cache_filepath
+        .file_name()
+        .expect("cache filepath should have a file name")
+        .to_os_string();
+    temp_filename.push(format!(".{:x}.tmp", rand::random::<u32>()));
+    let temp_filepath = cache_filepath.with_file_name(temp_filename);
+
+    let res = match write_synced(&temp_filepath, cache_data.as_ref()).await {
+        Ok(_) => fs::rename(&temp_filepath, cache_filepath).await,
+        Err(err) => Err(err),
+    };
+
+    if res.is_err() {
+        // The temporary file is of no use when writing or renaming fails
+        let _ = fs::remove_file(&temp_filepath).await;
+        return res;
+    }
+
+    if let Some(directory) = cache_filepath.parent() {
+        sync_directory(directory).await;
+    }
+    Ok(())
+}
+
+async fn write_synced(filepath: &path::Path, data: &[u8]) -> io::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = fs::File::create(filepath).await?;
+    file.write_all(data).await?;
+    file.sync_all().await
+}
+
+/// Makes the renames in the directory durable
+///
+/// Directories can't be opened for syncing on every platform (Windows), so failing is only logged.
+async fn sync_directory(directory: &path::Path) {
+    let res = match fs::File::open(directory).await {
+        Ok(directory) => directory.sync_all().await,
+        Err(err) => Err(err),
+    };
+
+    if let Err(err) = res {
+        if !cfg!(windows) {
+            warn!(
+                "failed to sync directory '{}': {}",
+                directory.display(),
+                err
+            );
+        }
+    }
+}
+
 pub async fn write_cache(cache_data: impl AsRef<[u8]>, cache_filepath: &path::Path) {
     loop {
-        if let Err(err) = fs::write(cache_filepath, &cache_data).await {
+        if let Err(err) = atomic_write(cache_filepath, &cache_data).await {
             if err.kind() == ErrorKind::NotFound {
                 let mut cache_folder = path::PathBuf::from(cache_filepath);
                 cache_folder.pop();
@@ -207,3 +388,73 @@ pub async fn write_cache(cache_data: impl AsRef<[u8]>, cache_filepath: &path::Pa
         break;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_variant_path_test() {
+        let image_url = "https://static.tvmaze.com/uploads/images/medium_landscape/1/1.jpg";
+        let image_hash = image_cache::image_hash(image_url);
+
+        let still_path = get_image_variant_path(image_url, ImageKind::EpisodeStill);
+        let thumb_path = get_image_variant_path(image_url, ImageKind::PosterThumb);
+
+        assert_ne!(still_path, thumb_path);
+        assert_eq!(
+            still_path.file_name().unwrap().to_string_lossy(),
+            format!("{image_hash}-episode-still")
+        );
+        assert_eq!(
+            still_path.parent().unwrap(),
+            CACHER.get_cache_folder_path(CacheFolderType::Images)
+        );
+        assert_eq!(
+            get_image_variant_path(image_url, ImageKind::EpisodeStill),
+            still_path
+        );
+    }
+
+    #[tokio::test]
+    async fn atomic_write_test() {
+        let directory = tempfile::tempdir().unwrap();
+        let filepath = directory.path().join("cache");
+
+        atomic_write(&filepath, "old").await.unwrap();
+        atomic_write(&filepath, "new").await.unwrap();
+        assert_eq!(fs::read_to_string(&filepath).await.unwrap(), "new");
+
+        // No temporary file is left behind
+        let mut entries = fs::read_dir(directory.path()).await.unwrap();
+        let mut file_names = vec![];
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            file_names.push(entry.file_name());
+        }
+        assert_eq!(file_names, ["cache"]);
+
+        let missing_directory_filepath = directory.path().join("missing").join("cache");
+        let err = atomic_write(&missing_directory_filepath, "data")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn read_json_cache_test() {
+        let directory = tempfile::tempdir().unwrap();
+        let filepath = directory.path().join("cache");
+
+        atomic_write(&filepath, "[1, 2, 3]").await.unwrap();
+        let data: Vec<u32> = read_json_cache(&filepath).await.unwrap();
+        assert_eq!(data, [1, 2, 3]);
+
+        atomic_write(&filepath, "[1, 2,").await.unwrap();
+        let err = read_json_cache::<Vec<u32>>(&filepath).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(!filepath.exists(), "the corrupt cache should be removed");
+
+        let err = read_json_cache::<Vec<u32>>(&filepath).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+}
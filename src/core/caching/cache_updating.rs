@@ -7,13 +7,30 @@ use std::path;
 use std::time;
 
 use anyhow::Context;
+use lazy_static::lazy_static;
 use tokio::fs;
+use tokio::sync::watch;
 use tracing::{error, info, warn};
 
+use super::fetch_limiter::FETCH_LIMITER;
+use super::memory_cache::MEMORY_CACHE;
+use super::series_changes::CachedSeriesData;
 use super::series_info_and_episode_list::SeriesInfoAndEpisodeList;
-use super::{CacheFolderType, CACHER};
+use super::{atomic_write, CacheFolderType, CACHER};
 use crate::core::api::tv_maze::updates::get_shows_updates_index;
 use crate::core::database::DB;
+use crate::core::notifications;
+use crate::core::offline::OFFLINE;
+
+lazy_static! {
+    /// Signals the completion of every series cache update
+    static ref CACHE_UPDATES: watch::Sender<()> = watch::channel(()).0;
+}
+
+/// Subscribes to the completion of series cache updates, as the cached releases may have changed
+pub fn watch_cache_updates() -> watch::Receiver<()> {
+    CACHE_UPDATES.subscribe()
+}
 
 async fn get_all_series_cache_directories(
 ) -> anyhow::Result<Vec<(String, path::PathBuf, time::Duration)>> {
@@ -49,6 +66,11 @@ async fn get_all_series_cache_directories(
 }
 
 pub async fn update_cache() -> anyhow::Result<()> {
+    if OFFLINE.should_skip_requests() {
+        info!("skipping series cache update while offline");
+        return Ok(());
+    }
+
     if !should_update().await? {
         return Ok(());
     }
@@ -63,21 +85,37 @@ pub async fn update_cache() -> anyhow::Result<()> {
     for (series_id, path, cache_timestamp) in series_cache_directories {
         let time_stamp = updates_index.get(&series_id).copied();
 
-        let handle = tokio::spawn(async move {
+        let handle = FETCH_LIMITER.spawn(async move {
             if let Some(time_stamp) = time_stamp {
                 let update_timestamp = time::Duration::from_secs(time_stamp as u64);
 
                 if update_timestamp > cache_timestamp {
-                    clean_cache_directory(&path).await;
+                    let series_id: u32 = series_id.parse().expect("series id should be parsable");
+
+                    // Keeping the data of tracked series before cleaning so that their changes can be notified
+                    let old_series_data = if DB
+                        .get_series(series_id)
+                        .is_some_and(|series| series.is_tracked())
+                    {
+                        CachedSeriesData::read(series_id).await
+                    } else {
+                        None
+                    };
+
+                    evict_series_cache(series_id, &path).await;
 
                     // Caching the series if it's in the database
-                    let series_id: u32 = series_id.parse().expect("series id should be parsable");
                     if DB.get_series(series_id).is_some() {
-                        SeriesInfoAndEpisodeList::cache_series(series_id)
-                            .await
-                            .unwrap_or_else(|err| {
+                        match SeriesInfoAndEpisodeList::cache_series(series_id).await {
+                            Ok(_) => {
+                                if let Some(old_series_data) = old_series_data {
+                                    notify_series_changes(series_id, old_series_data).await;
+                                }
+                            }
+                            Err(err) => {
                                 error!("failed to cache series with id '{}': {}", series_id, err)
-                            });
+                            }
+                        }
                     }
                 }
             } else {
@@ -85,7 +123,10 @@ pub async fn update_cache() -> anyhow::Result<()> {
                     "series cache with id '{}' not in updates, cleaning it anyways",
                     series_id
                 );
-                clean_cache_directory(&path).await
+                match series_id.parse() {
+                    Ok(series_id) => evict_series_cache(series_id, &path).await,
+                    Err(_) => clean_cache_directory(&path).await,
+                }
             }
         });
 
@@ -97,12 +138,29 @@ pub async fn update_cache() -> anyhow::Result<()> {
     }
 
     record_last_update().await?;
+    CACHE_UPDATES.send_replace(());
 
     info!("updating series cache complete!");
 
     Ok(())
 }
 
+async fn notify_series_changes(series_id: u32, old_series_data: CachedSeriesData) {
+    let Some(new_series_data) = CachedSeriesData::read(series_id).await else {
+        return;
+    };
+
+    for series_change in old_series_data.get_changes(&new_series_data) {
+        info!(
+            "detected change for \"{}\": {:?}",
+            new_series_data.get_series_info().name,
+            series_change
+        );
+        notifications::notify_series_change(new_series_data.get_series_info(), &series_change)
+            .await;
+    }
+}
+
 const LAST_UPDATE_FILENAME: &str = "last-cache-update";
 
 fn get_last_update_filepath() -> path::PathBuf {
@@ -147,20 +205,65 @@ async fn should_update() -> anyhow::Result<bool> {
     Ok((current_timestamp - last_update_timestamp) > time::Duration::from_secs(60 * 60 * 24))
 }
 
+/// Retrieves the time the series cache was last updated, `None` if it was never updated
+pub async fn get_last_update_time() -> Option<chrono::DateTime<chrono::Local>> {
+    let last_update_timestamp = fs::read_to_string(get_last_update_filepath())
+        .await
+        .ok()?
+        .parse()
+        .ok()?;
+
+    chrono::DateTime::from_timestamp(last_update_timestamp, 0)
+        .map(|date_time| date_time.with_timezone(&chrono::Local))
+}
+
 async fn record_last_update() -> anyhow::Result<()> {
     let last_update_file = get_last_update_filepath();
 
     let current_timestamp = duration_since_epoch()?;
 
-    fs::write(last_update_file, current_timestamp.as_secs().to_string())
+    atomic_write(&last_update_file, current_timestamp.as_secs().to_string())
         .await
         .context("failed to write 'last-cache-update' file")
 }
 
 /// Removes the directory and it's contents at the given path
+/// Removes the stale cache of the series both from the disk and the memory
+async fn evict_series_cache(series_id: u32, path: &path::Path) {
+    clean_cache_directory(path).await;
+    MEMORY_CACHE.invalidate(series_id);
+}
+
 async fn clean_cache_directory(path: &path::Path) {
     info!("cleaning cache: {}", path.display());
     fs::remove_dir_all(path)
         .await
         .unwrap_or_else(|err| error!("failed to clean cache for path {}: {}", path.display(), err));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::caching::episode_list::EpisodeList;
+
+    #[tokio::test]
+    async fn evict_series_cache_test() {
+        // An id no real series has, as the memory cache is shared by all the tests
+        let series_id = 4_000_027;
+        let cache_directory = tempfile::tempdir().unwrap();
+        let series_path = cache_directory.path().join(series_id.to_string());
+        fs::create_dir_all(&series_path).await.unwrap();
+        fs::write(series_path.join("episode-list"), "[]")
+            .await
+            .unwrap();
+
+        MEMORY_CACHE.insert_episode_list(EpisodeList::with_cache(series_id, "[]").unwrap());
+        MEMORY_CACHE.insert_watch_time(series_id, 1, Some(60));
+
+        evict_series_cache(series_id, &series_path).await;
+
+        assert!(!series_path.exists());
+        assert!(MEMORY_CACHE.get_episode_list(series_id).is_none());
+        assert!(MEMORY_CACHE.get_watch_time(series_id, 1).is_none());
+    }
+}
@@ -1,8 +1,10 @@
 use std::io::ErrorKind;
+use std::sync::Arc;
 
 use tracing::info;
 
-use super::{read_cache, write_cache, CacheFilePath};
+use super::memory_cache::MEMORY_CACHE;
+use super::{read_json_cache, write_cache, CacheFilePath};
 use crate::core::api::tv_maze::deserialize_json;
 pub use crate::core::api::tv_maze::episodes_information::EpisodeReleaseTime;
 use crate::core::api::tv_maze::episodes_information::{get_episode_list, Episode};
@@ -12,16 +14,27 @@ use crate::core::{caching::CACHER, database};
 #[derive(Clone, Debug)]
 pub struct EpisodeList {
     series_id: u32,
-    episodes: Vec<Episode>,
+    /// Shared between clones as `EpisodeList`s are kept in the `MEMORY_CACHE`
+    episodes: Arc<Vec<Episode>>,
 }
 
 impl EpisodeList {
     pub async fn new(series_id: u32) -> Result<Self, ApiError> {
+        if let Some(episode_list) = MEMORY_CACHE.get_episode_list(series_id) {
+            return Ok(episode_list);
+        }
+
+        let episode_list = Self::load(series_id).await?;
+        MEMORY_CACHE.insert_episode_list(episode_list.clone());
+        Ok(episode_list)
+    }
+
+    async fn load(series_id: u32) -> Result<Self, ApiError> {
         let episodes_list_path =
             CACHER.get_cache_file_path(CacheFilePath::SeriesEpisodeList(series_id));
 
-        let json_string = match read_cache(&episodes_list_path).await {
-            Ok(json_string) => json_string,
+        let episodes = match read_json_cache(&episodes_list_path).await {
+            Ok(episodes) => episodes,
             Err(err) => {
                 info!("falling back online for 'episode list' for series id: {series_id}");
                 let (episodes, json_string) = get_episode_list(series_id).await?;
@@ -29,17 +42,13 @@ impl EpisodeList {
                 if err.kind() == ErrorKind::NotFound {
                     write_cache(&json_string, &episodes_list_path).await;
                 }
-                return Ok(Self {
-                    series_id,
-                    episodes,
-                });
+                episodes
             }
         };
 
-        let episodes = deserialize_json::<Vec<Episode>>(&json_string)?;
         Ok(Self {
             series_id,
-            episodes,
+            episodes: Arc::new(episodes),
         })
     }
 
@@ -48,10 +57,14 @@ impl EpisodeList {
         let episodes = deserialize_json::<Vec<Episode>>(cache_str)?;
         Ok(Self {
             series_id,
-            episodes,
+            episodes: Arc::new(episodes),
         })
     }
 
+    pub fn get_series_id(&self) -> u32 {
+        self.series_id
+    }
+
     pub fn get_episode(&self, season_number: u32, episode_number: u32) -> Option<&Episode> {
         self.episodes.iter().find(|episode| {
             (episode.season == season_number) && (episode.number == Some(episode_number))
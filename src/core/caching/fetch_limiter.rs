@@ -0,0 +1,105 @@
+//! # Bounded concurrency for mass series fetches
+//!
+//! Operations like loading all the tracked series or importing from trakt used to
+//! spawn a task per series all at once. With hundreds of series, that ends up hammering
+//! both the tvmaze api and the filesystem at the same time.
+//!
+//! `FETCH_LIMITER` is shared by all of those fan-outs so that only a configurable amount of
+//! them run at the same time. It also keeps track of how many of the spawned fetches have
+//! completed so that the GUI can display the progress.
+
+use std::future::Future;
+
+use lazy_static::lazy_static;
+use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinHandle;
+
+use crate::core::settings_config::SETTINGS;
+
+lazy_static! {
+    pub static ref FETCH_LIMITER: FetchLimiter = FetchLimiter::new(
+        SETTINGS
+            .read()
+            .expect("failed to read settings")
+            .get_current_settings()
+            .caching
+            .max_concurrent_fetches
+    );
+}
+
+/// Progress of all the fetches spawned through the `FetchLimiter`
+///
+/// Gets reset back to it's default when all the spawned fetches have completed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchProgress {
+    pub completed: usize,
+    pub total: usize,
+}
+
+impl FetchProgress {
+    /// Whether there are fetches that have not completed yet
+    pub fn is_active(&self) -> bool {
+        self.total != 0
+    }
+}
+
+pub struct FetchLimiter {
+    semaphore: Semaphore,
+    progress: watch::Sender<FetchProgress>,
+}
+
+impl FetchLimiter {
+    pub fn new(max_concurrent_fetches: usize) -> Self {
+        let (progress, _) = watch::channel(FetchProgress::default());
+        Self {
+            semaphore: Semaphore::new(max_concurrent_fetches.max(1)),
+            progress,
+        }
+    }
+
+    /// Spawns the given future as a tokio task that only starts running when
+    /// a fetch slot is available
+    pub fn spawn<F>(&'static self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.progress.send_modify(|progress| progress.total += 1);
+
+        tokio::spawn(async move {
+            // Recording completion through a guard so that aborted or panicked
+            // fetches don't leave the progress hanging
+            let _completion_guard = CompletionGuard(self);
+
+            let _permit = self
+                .semaphore
+                .acquire()
+                .await
+                .expect("fetch limiter semaphore should never be closed");
+
+            future.await
+        })
+    }
+
+    /// Returns a receiver that gets notified whenever the fetch progress changes
+    pub fn subscribe(&self) -> watch::Receiver<FetchProgress> {
+        self.progress.subscribe()
+    }
+
+    fn record_completion(&self) {
+        self.progress.send_modify(|progress| {
+            progress.completed += 1;
+            if progress.completed >= progress.total {
+                *progress = FetchProgress::default();
+            }
+        });
+    }
+}
+
+struct CompletionGuard(&'static FetchLimiter);
+
+impl Drop for CompletionGuard {
+    fn drop(&mut self) {
+        self.0.record_completion();
+    }
+}
@@ -0,0 +1,452 @@
+//! # Image cache size limiting
+//!
+//! Images are cached for every url ever loaded, i.e. search results, cast photos and discover
+//! posters. To keep the images cache directory from growing forever, the least recently used images
+//! are evicted when it's size goes beyond the maximum size set in the settings.
+//!
+//! Images belonging to tracked series and to series made available offline are pinned and never
+//! evicted, that includes their posters, backgrounds, episode stills and cast photos so that series
+//! made available offline stay so.
+//!
+//! ## Recency
+//! Since access times are unreliable across filesystems(i.e. `noatime` mounts), the modification
+//! time of an image file is bumped every time it's read from the cache and used as it's last use.
+//! Images are read in bursts, so their uses are collected and recorded together after a short delay.
+
+use std::collections::HashSet;
+use std::path;
+use std::time::{Duration, SystemTime};
+
+use lazy_static::lazy_static;
+use tokio::fs;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use super::{read_json_cache, CacheFilePath, CacheFolderType, CACHER};
+use crate::core::api::tv_maze::episodes_information::Episode;
+use crate::core::api::tv_maze::people::show_cast::Cast;
+use crate::core::api::tv_maze::people::show_crew::Crew;
+use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+use crate::core::api::tv_maze::show_images::Image;
+use crate::core::api::tv_maze::Image as ImageUrls;
+use crate::core::database::DB;
+use crate::core::settings_config::SETTINGS;
+
+lazy_static! {
+    /// The current size of the images cache directory in bytes
+    ///
+    /// It's `None` until the images cache directory is scanned for the first time.
+    static ref IMAGE_CACHE_SIZE: Mutex<Option<u64>> = Mutex::new(None);
+
+    /// The images read from the cache whose use is yet to be recorded
+    static ref PENDING_IMAGE_USES: std::sync::Mutex<HashSet<path::PathBuf>> =
+        std::sync::Mutex::new(HashSet::new());
+}
+
+/// How long image uses are collected before being recorded together
+const IMAGE_USE_RECORDING_DELAY: Duration = Duration::from_secs(5);
+
+/// Usage statistics of the images cache directory
+#[derive(Debug, Clone, Default)]
+pub struct ImageCacheStats {
+    pub total_size: u64,
+    pub total_images: usize,
+    pub pinned_size: u64,
+    pub pinned_images: usize,
+    pub max_size: u64,
+}
+
+struct CachedImage {
+    path: path::PathBuf,
+    hash: String,
+    size: u64,
+    last_used: SystemTime,
+}
+
+/// Hashes the image url to be used as a file name as the forward slashes in web urls
+/// mimic paths
+pub fn image_hash(image_url: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(image_url);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Marks the cached image at the given path as just used
+///
+/// The use is recorded in the background together with the other uses in the next
+/// `IMAGE_USE_RECORDING_DELAY`, hence this has to be called within a tokio runtime.
+pub fn record_image_use(image_path: path::PathBuf) {
+    let mut pending_image_uses = PENDING_IMAGE_USES
+        .lock()
+        .expect("failed to lock pending image uses");
+
+    // The first pending use starts the recording of the whole batch
+    if pending_image_uses.is_empty() {
+        tokio::spawn(record_pending_image_uses());
+    }
+    pending_image_uses.insert(image_path);
+}
+
+async fn record_pending_image_uses() {
+    tokio::time::sleep(IMAGE_USE_RECORDING_DELAY).await;
+
+    let image_paths = std::mem::take(
+        &mut *PENDING_IMAGE_USES
+            .lock()
+            .expect("failed to lock pending image uses"),
+    );
+
+    tokio::task::spawn_blocking(move || {
+        let now = SystemTime::now();
+        for image_path in image_paths {
+            let res = std::fs::File::options()
+                .write(true)
+                .open(&image_path)
+                .and_then(|file| file.set_modified(now));
+
+            match res {
+                // The image might have been evicted in the meantime
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => error!(
+                    "failed to record use of image '{}': {}",
+                    image_path.display(),
+                    err
+                ),
+                Ok(_) => {}
+            }
+        }
+    })
+    .await
+    .expect("failed to join image use recording handle");
+}
+
+/// Records a newly cached image, evicting the least recently used images if the
+/// images cache directory has gone beyond it's maximum size
+pub async fn record_new_image(image_size: u64) {
+    let mut image_cache_size = IMAGE_CACHE_SIZE.lock().await;
+
+    let current_size = match *image_cache_size {
+        Some(size) => size + image_size,
+        None => match get_cached_images().await {
+            Ok(images) => images.iter().map(|image| image.size).sum(),
+            Err(err) => {
+                error!("failed to read images cache directory: {}", err);
+                return;
+            }
+        },
+    };
+
+    *image_cache_size = Some(keep_within_max_size(current_size).await);
+}
+
+/// Evicts the least recently used images if the images cache directory is beyond it's maximum size
+///
+/// Meant for when the maximum size might have been lowered, i.e. at startup and when the
+/// settings are saved, as otherwise eviction only happens when a new image is cached.
+pub async fn limit_image_cache_size() {
+    let mut image_cache_size = IMAGE_CACHE_SIZE.lock().await;
+
+    let current_size = match get_cached_images().await {
+        Ok(images) => images.iter().map(|image| image.size).sum(),
+        Err(err) => {
+            error!("failed to read images cache directory: {}", err);
+            return;
+        }
+    };
+
+    *image_cache_size = Some(keep_within_max_size(current_size).await);
+}
+
+/// Evicts images when the given images cache size is beyond the maximum size, returning
+/// the resulting images cache size
+async fn keep_within_max_size(current_size: u64) -> u64 {
+    let max_size = get_max_image_cache_size();
+
+    if current_size > max_size {
+        evict_least_recently_used(current_size, max_size).await
+    } else {
+        current_size
+    }
+}
+
+/// Computes the usage statistics of the images cache directory
+pub async fn get_image_cache_stats() -> anyhow::Result<ImageCacheStats> {
+    let images = get_cached_images().await?;
+    let pinned_hashes = get_pinned_image_hashes().await;
+
+    let mut stats = ImageCacheStats {
+        max_size: get_max_image_cache_size(),
+        ..Default::default()
+    };
+
+    for image in images {
+        stats.total_size += image.size;
+        stats.total_images += 1;
+
+        if pinned_hashes.contains(&image.hash) {
+            stats.pinned_size += image.size;
+            stats.pinned_images += 1;
+        }
+    }
+
+    *IMAGE_CACHE_SIZE.lock().await = Some(stats.total_size);
+
+    Ok(stats)
+}
+
+/// Computes the total size of the cached images of the given hashes, including their variants
+pub async fn get_images_size(image_hashes: &HashSet<String>) -> std::io::Result<u64> {
+    Ok(get_cached_images()
+        .await?
+        .into_iter()
+        .filter(|image| image_hashes.contains(&image.hash))
+        .map(|image| image.size)
+        .sum())
+}
+
+fn get_max_image_cache_size() -> u64 {
+    SETTINGS
+        .read()
+        .expect("failed to read settings")
+        .get_current_settings()
+        .caching
+        .max_image_cache_size
+        .saturating_mul(1024 * 1024)
+}
+
+/// Removes the least recently used unpinned images until the images cache size is at most
+/// the given maximum size, returning the new images cache size
+async fn evict_least_recently_used(current_size: u64, max_size: u64) -> u64 {
+    let images = match get_cached_images().await {
+        Ok(images) => images,
+        Err(err) => {
+            error!("failed to read images cache directory: {}", err);
+            return current_size;
+        }
+    };
+    let pinned_hashes = get_pinned_image_hashes().await;
+
+    // The directory might have changed since the size was last recorded
+    let mut current_size = images.iter().map(|image| image.size).sum();
+
+    let mut evicted_images = 0;
+    for image in select_images_to_evict(images, &pinned_hashes, current_size, max_size) {
+        match fs::remove_file(&image.path).await {
+            Ok(_) => {
+                current_size -= image.size;
+                evicted_images += 1;
+            }
+            Err(err) => error!(
+                "failed to evict cached image '{}': {}",
+                image.path.display(),
+                err
+            ),
+        }
+    }
+
+    info!("evicted {} least recently used images", evicted_images);
+
+    current_size
+}
+
+/// Picks the least recently used unpinned images whose removal brings the given size of
+/// the images down to at most the given maximum size
+fn select_images_to_evict(
+    mut images: Vec<CachedImage>,
+    pinned_hashes: &HashSet<String>,
+    mut current_size: u64,
+    max_size: u64,
+) -> Vec<CachedImage> {
+    images.sort_unstable_by_key(|image| image.last_used);
+
+    let mut images_to_evict = vec![];
+    for image in images
+        .into_iter()
+        .filter(|image| !pinned_hashes.contains(&image.hash))
+    {
+        if current_size <= max_size {
+            break;
+        }
+        current_size = current_size.saturating_sub(image.size);
+        images_to_evict.push(image);
+    }
+    images_to_evict
+}
+
+async fn get_cached_images() -> std::io::Result<Vec<CachedImage>> {
+    let images_directory = CACHER.get_cache_folder_path(CacheFolderType::Images);
+
+    let mut read_dir = match fs::read_dir(images_directory).await {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err),
+    };
+
+    let mut images = vec![];
+    while let Some(dir_entry) = read_dir.next_entry().await? {
+        let metadata = dir_entry.metadata().await?;
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let file_name = dir_entry.file_name().to_string_lossy().to_string();
+
+        // Temporary files are still being written by `atomic_write`
+        if file_name.ends_with(".tmp") {
+            continue;
+        }
+
+        images.push(CachedImage {
+            path: dir_entry.path(),
+            hash: get_hash_from_file_name(&file_name).to_owned(),
+            size: metadata.len(),
+            last_used: metadata.modified()?,
+        })
+    }
+    Ok(images)
+}
+
+/// Image file names start with the hash of their url, anything after it like a file
+/// extension is not part of the hash
+fn get_hash_from_file_name(file_name: &str) -> &str {
+    file_name
+        .split(['-', '.'])
+        .next()
+        .expect("split should yield at least one item")
+}
+
+/// Retrieves the hashes of all the image urls belonging to the tracked series and to the
+/// series made available offline
+///
+/// Only the data that is already cached is used, as pinning should never require
+/// going online.
+async fn get_pinned_image_hashes() -> HashSet<String> {
+    let mut pinned_hashes = HashSet::new();
+
+    let pinned_series_ids: HashSet<u32> = DB
+        .get_series_collection()
+        .into_iter()
+        .filter(|series| series.is_tracked())
+        .map(|series| series.id())
+        .chain(DB.get_offline_series_ids())
+        .collect();
+
+    for series_id in pinned_series_ids {
+        let series_info_path =
+            CACHER.get_cache_file_path(CacheFilePath::SeriesMainInformation(series_id));
+        if let Ok(series_info) = read_json_cache::<SeriesMainInformation>(&series_info_path).await {
+            if let Some(image) = series_info.image {
+                insert_image_hashes(&mut pinned_hashes, &image);
+            }
+        }
+
+        let image_list_path = CACHER.get_cache_file_path(CacheFilePath::SeriesImageList(series_id));
+        if let Ok(images) = read_json_cache::<Vec<Image>>(&image_list_path).await {
+            for image in images {
+                pinned_hashes.insert(image_hash(&image.resolutions.original.url));
+                if let Some(medium) = image.resolutions.medium {
+                    pinned_hashes.insert(image_hash(&medium.url));
+                }
+            }
+        }
+
+        let episode_list_path =
+            CACHER.get_cache_file_path(CacheFilePath::SeriesEpisodeList(series_id));
+        if let Ok(episodes) = read_json_cache::<Vec<Episode>>(&episode_list_path).await {
+            episodes
+                .into_iter()
+                .filter_map(|episode| episode.image)
+                .for_each(|image| insert_image_hashes(&mut pinned_hashes, &image));
+        }
+
+        let cast_path = CACHER.get_cache_file_path(CacheFilePath::SeriesShowCast(series_id));
+        if let Ok(cast) = read_json_cache::<Vec<Cast>>(&cast_path).await {
+            cast.into_iter()
+                .flat_map(|cast| [cast.person.image, cast.character.image])
+                .flatten()
+                .for_each(|image| insert_image_hashes(&mut pinned_hashes, &image));
+        }
+
+        let crew_path = CACHER.get_cache_file_path(CacheFilePath::SeriesShowCrew(series_id));
+        if let Ok(crew) = read_json_cache::<Vec<Crew>>(&crew_path).await {
+            crew.into_iter()
+                .filter_map(|crew| crew.person.image)
+                .for_each(|image| insert_image_hashes(&mut pinned_hashes, &image));
+        }
+    }
+
+    pinned_hashes
+}
+
+fn insert_image_hashes(image_hashes: &mut HashSet<String>, image: &ImageUrls) {
+    image_hashes.insert(image_hash(&image.original_image_url));
+    image_hashes.insert(image_hash(&image.medium_image_url));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached_image(hash: &str, size: u64, last_used_secs: u64) -> CachedImage {
+        CachedImage {
+            path: path::PathBuf::from(hash),
+            hash: hash.to_owned(),
+            size,
+            last_used: SystemTime::UNIX_EPOCH + Duration::from_secs(last_used_secs),
+        }
+    }
+
+    fn evicted_hashes(
+        images: Vec<CachedImage>,
+        pinned_hashes: &[&str],
+        max_size: u64,
+    ) -> Vec<String> {
+        let current_size = images.iter().map(|image| image.size).sum();
+        let pinned_hashes = pinned_hashes.iter().map(|hash| hash.to_string()).collect();
+
+        select_images_to_evict(images, &pinned_hashes, current_size, max_size)
+            .into_iter()
+            .map(|image| image.hash)
+            .collect()
+    }
+
+    #[test]
+    fn least_recently_used_eviction_test() {
+        let images = || {
+            vec![
+                cached_image("newest", 10, 300),
+                cached_image("oldest", 10, 100),
+                cached_image("middle", 10, 200),
+            ]
+        };
+
+        assert!(evicted_hashes(images(), &[], 30).is_empty());
+        assert_eq!(evicted_hashes(images(), &[], 20), ["oldest"]);
+        assert_eq!(evicted_hashes(images(), &[], 15), ["oldest", "middle"]);
+        assert_eq!(
+            evicted_hashes(images(), &[], 0),
+            ["oldest", "middle", "newest"]
+        );
+    }
+
+    #[test]
+    fn pinned_images_eviction_test() {
+        let images = vec![
+            cached_image("newest", 10, 300),
+            cached_image("oldest", 10, 100),
+            cached_image("middle", 10, 200),
+        ];
+
+        // Pinned images are skipped even when the maximum size can't be reached without them
+        assert_eq!(evicted_hashes(images, &["oldest"], 0), ["middle", "newest"]);
+    }
+
+    #[test]
+    fn hash_from_file_name_test() {
+        let hash = image_hash("https://static.tvmaze.com/uploads/images/original_untouched/1.jpg");
+        assert_eq!(get_hash_from_file_name(&hash), hash);
+        assert_eq!(get_hash_from_file_name(&format!("{hash}-poster")), hash);
+    }
+}
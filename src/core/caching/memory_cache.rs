@@ -0,0 +1,184 @@
+//! # In-memory cache for parsed series metadata
+//!
+//! Reading and parsing `main-info` and `episode-list` files from disk every time a tab
+//! or a widget needs them gets expensive when done for all the tracked series. This
+//! keeps the parsed `SeriesMainInformation` and `EpisodeList` around for the whole
+//! lifetime of the program.
+//!
+//! The watch times of the series, computed from the runtimes of their watched episodes, are also
+//! kept here for the Statistics tab.
+//!
+//! Entries are invalidated whenever their series cache gets cleaned by the cache updater,
+//! so the next read goes back to the disk(or online) for the fresh data.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+
+use super::episode_list::EpisodeList;
+use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+
+lazy_static! {
+    pub static ref MEMORY_CACHE: MemoryCache = MemoryCache::new();
+}
+
+pub struct MemoryCache {
+    series_information: RwLock<HashMap<u32, SeriesMainInformation>>,
+    episode_lists: RwLock<HashMap<u32, EpisodeList>>,
+    watch_times: RwLock<HashMap<u32, WatchTime>>,
+}
+
+/// The watch time of a series for a particular set of watched episodes
+struct WatchTime {
+    watched_episodes_hash: u64,
+    minutes: Option<u32>,
+}
+
+impl MemoryCache {
+    fn new() -> Self {
+        Self {
+            series_information: RwLock::new(HashMap::new()),
+            episode_lists: RwLock::new(HashMap::new()),
+            watch_times: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn get_series_information(&self, series_id: u32) -> Option<SeriesMainInformation> {
+        self.series_information
+            .read()
+            .expect("failed to read series information memory cache")
+            .get(&series_id)
+            .cloned()
+    }
+
+    pub fn insert_series_information(&self, series_info: SeriesMainInformation) {
+        self.series_information
+            .write()
+            .expect("failed to write series information memory cache")
+            .insert(series_info.id, series_info);
+    }
+
+    /// Retrieves the `EpisodeList` of the given series
+    ///
+    /// This is cheap as `EpisodeList` shares it's episodes between clones.
+    pub fn get_episode_list(&self, series_id: u32) -> Option<EpisodeList> {
+        self.episode_lists
+            .read()
+            .expect("failed to read episode lists memory cache")
+            .get(&series_id)
+            .cloned()
+    }
+
+    pub fn insert_episode_list(&self, episode_list: EpisodeList) {
+        self.episode_lists
+            .write()
+            .expect("failed to write episode lists memory cache")
+            .insert(episode_list.get_series_id(), episode_list);
+    }
+
+    /// Retrieves the watch time of the given series when it was computed for the same watched episodes
+    pub fn get_watch_time(
+        &self,
+        series_id: u32,
+        watched_episodes_hash: u64,
+    ) -> Option<Option<u32>> {
+        self.watch_times
+            .read()
+            .expect("failed to read watch times memory cache")
+            .get(&series_id)
+            .filter(|watch_time| watch_time.watched_episodes_hash == watched_episodes_hash)
+            .map(|watch_time| watch_time.minutes)
+    }
+
+    pub fn insert_watch_time(
+        &self,
+        series_id: u32,
+        watched_episodes_hash: u64,
+        minutes: Option<u32>,
+    ) {
+        self.watch_times
+            .write()
+            .expect("failed to write watch times memory cache")
+            .insert(
+                series_id,
+                WatchTime {
+                    watched_episodes_hash,
+                    minutes,
+                },
+            );
+    }
+
+    /// Removes all the cached data of the given series
+    pub fn invalidate(&self, series_id: u32) {
+        self.series_information
+            .write()
+            .expect("failed to write series information memory cache")
+            .remove(&series_id);
+        self.episode_lists
+            .write()
+            .expect("failed to write episode lists memory cache")
+            .remove(&series_id);
+        self.watch_times
+            .write()
+            .expect("failed to write watch times memory cache")
+            .remove(&series_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::api::tv_maze::test_fixtures;
+
+    fn series_info(id: u32, name: &str) -> SeriesMainInformation {
+        let mut series_info = test_fixtures::series_info(id);
+        series_info.name = name.to_owned();
+        series_info
+    }
+
+    #[test]
+    fn hits_and_misses_test() {
+        let memory_cache = MemoryCache::new();
+        assert!(memory_cache.get_series_information(1).is_none());
+        assert!(memory_cache.get_episode_list(1).is_none());
+
+        memory_cache.insert_series_information(series_info(1, "Old Name"));
+        memory_cache.insert_episode_list(EpisodeList::with_cache(1, "[]").unwrap());
+        memory_cache.insert_watch_time(1, 10, Some(120));
+
+        assert_eq!(
+            memory_cache.get_series_information(1).unwrap().name,
+            "Old Name"
+        );
+        assert_eq!(memory_cache.get_episode_list(1).unwrap().get_series_id(), 1);
+        assert!(memory_cache.get_series_information(2).is_none());
+
+        assert_eq!(memory_cache.get_watch_time(1, 10), Some(Some(120)));
+        // Different watched episodes make the watch time stale
+        assert_eq!(memory_cache.get_watch_time(1, 11), None);
+    }
+
+    #[test]
+    fn invalidate_test() {
+        let memory_cache = MemoryCache::new();
+        memory_cache.insert_series_information(series_info(1, "Old Name"));
+        memory_cache.insert_series_information(series_info(2, "Other"));
+        memory_cache.insert_episode_list(EpisodeList::with_cache(1, "[]").unwrap());
+        memory_cache.insert_watch_time(1, 10, Some(120));
+
+        memory_cache.invalidate(1);
+
+        assert!(memory_cache.get_series_information(1).is_none());
+        assert!(memory_cache.get_episode_list(1).is_none());
+        assert!(memory_cache.get_watch_time(1, 10).is_none());
+        assert!(memory_cache.get_series_information(2).is_some());
+
+        // The next read after invalidating gets the fresh data
+        memory_cache.insert_series_information(series_info(1, "New Name"));
+        assert_eq!(
+            memory_cache.get_series_information(1).unwrap().name,
+            "New Name"
+        );
+    }
+}
@@ -0,0 +1,316 @@
+//! # Making series available offline
+//!
+//! Series are normally cached lazily, only what the GUI has displayed so far ends up in the cache.
+//! This caches everything a series page can display upfront, i.e. the main info, episode list,
+//! cast, crew and image list together with all of the posters, backgrounds, episode stills and
+//! cast photos they refer to.
+//!
+//! Images of tracked series and of series made available offline are pinned in the images cache,
+//! so they are never evicted.
+
+use std::collections::HashSet;
+use std::path;
+use std::sync::Arc;
+
+use tokio::fs;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::error;
+
+use super::episode_list::EpisodeList;
+use super::fetch_limiter::FETCH_LIMITER;
+use super::image_cache::{self, image_hash};
+use super::{
+    episode_still_source, load_image, load_original_image, people, series_information, show_images,
+    ImageKind, ImageResolution, CACHER,
+};
+use crate::core::api::tv_maze::episodes_information::Episode;
+use crate::core::api::tv_maze::people::{show_cast::Cast, show_crew::Crew};
+use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+use crate::core::api::tv_maze::show_images::Image;
+use crate::core::database::DB;
+
+/// The maximum number of images of a single series that are loaded at the same time
+const MAX_CONCURRENT_IMAGE_LOADS: usize = 4;
+
+/// The outcome of making series available offline
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineAvailability {
+    pub available_series: usize,
+    pub failed_series: usize,
+    pub failed_images: usize,
+    /// The disk space taken by the cache of the series in bytes
+    pub disk_size: u64,
+}
+
+/// The url of an image together with the resolution it's displayed in
+///
+/// Images with `None` resolutions have no scaled down variant as the GUI does not display them yet.
+type ImageSource = (String, Option<ImageResolution>);
+
+/// Caches everything needed to use the given series offline
+///
+/// The images are loaded through the `FETCH_LIMITER` so their progress is reported by it.
+pub async fn make_series_available_offline(series_id: u32) -> anyhow::Result<OfflineAvailability> {
+    // Pinning before loading so that the images are not evicted while the rest are still loading
+    DB.add_offline_series(series_id);
+
+    let image_sources = cache_series_data(series_id).await?;
+    let image_hashes = get_image_hashes(&image_sources);
+
+    let handles: Vec<_> = image_sources
+        .into_iter()
+        .map(|image_source| FETCH_LIMITER.spawn(load_image_source(image_source)))
+        .collect();
+
+    let mut failed_images = 0;
+    for handle in handles {
+        if !handle.await.expect("failed to join image load handle") {
+            failed_images += 1;
+        }
+    }
+
+    // The background displayed in the series page is a scaled down variant
+    show_images::get_recent_banner(series_id).await;
+
+    Ok(OfflineAvailability {
+        available_series: 1,
+        failed_series: 0,
+        failed_images,
+        disk_size: get_disk_size(&[series_id], &image_hashes).await?,
+    })
+}
+
+/// Caches everything needed to use all the tracked series offline
+///
+/// The series are cached through the `FETCH_LIMITER` so their progress is reported by it.
+pub async fn make_tracked_series_available_offline() -> anyhow::Result<OfflineAvailability> {
+    let tracked_series_ids: Vec<u32> = DB
+        .get_series_collection()
+        .into_iter()
+        .filter(|series| series.is_tracked())
+        .map(|series| series.id())
+        .collect();
+
+    let handles: Vec<_> = tracked_series_ids
+        .iter()
+        .map(|series_id| FETCH_LIMITER.spawn(cache_series(*series_id)))
+        .collect();
+
+    let mut offline_availability = OfflineAvailability::default();
+    let mut image_hashes = HashSet::new();
+
+    for (handle, series_id) in handles.into_iter().zip(tracked_series_ids.iter()) {
+        match handle
+            .await
+            .expect("failed to join offline availability handle")
+        {
+            Ok((series_image_hashes, failed_images)) => {
+                offline_availability.available_series += 1;
+                offline_availability.failed_images += failed_images;
+                image_hashes.extend(series_image_hashes);
+            }
+            Err(err) => {
+                error!(
+                    "failed to make series with id '{}' available offline: {}",
+                    series_id, err
+                );
+                offline_availability.failed_series += 1;
+            }
+        }
+    }
+
+    offline_availability.disk_size = get_disk_size(&tracked_series_ids, &image_hashes).await?;
+
+    Ok(offline_availability)
+}
+
+/// Caches all the data and images of the series
+///
+/// Returns the hashes of the series images and how many of them failed to load.
+async fn cache_series(series_id: u32) -> anyhow::Result<(HashSet<String>, usize)> {
+    let image_sources = cache_series_data(series_id).await?;
+    let image_hashes = get_image_hashes(&image_sources);
+
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_IMAGE_LOADS));
+    let mut image_loads = JoinSet::new();
+    for image_source in image_sources {
+        let semaphore = semaphore.clone();
+        image_loads.spawn(async move {
+            let _permit = semaphore
+                .acquire()
+                .await
+                .expect("image loads semaphore should never be closed");
+
+            load_image_source(image_source).await
+        });
+    }
+
+    let mut failed_images = 0;
+    while let Some(is_loaded) = image_loads.join_next().await {
+        if !is_loaded.expect("failed to join image load handle") {
+            failed_images += 1;
+        }
+    }
+
+    // The background displayed in the series page is a scaled down variant
+    show_images::get_recent_banner(series_id).await;
+
+    Ok((image_hashes, failed_images))
+}
+
+/// Caches all the data of the series, returning the sources of all the images it refers to
+async fn cache_series_data(series_id: u32) -> anyhow::Result<Vec<ImageSource>> {
+    let series_info = series_information::get_series_main_info_with_id(series_id).await?;
+    let episode_list = EpisodeList::new(series_id).await?;
+    let cast = people::get_show_cast(series_id).await?;
+    let crew = people::get_show_crew(series_id).await?;
+    let images = show_images::get_show_images(series_id).await?;
+
+    Ok(get_image_sources(
+        series_info,
+        images,
+        episode_list.get_all_episodes(),
+        cast,
+        crew,
+    ))
+}
+
+/// Collects the images of the series with the same urls and resolutions the GUI loads them with
+fn get_image_sources(
+    series_info: SeriesMainInformation,
+    images: Vec<Image>,
+    episodes: &[Episode],
+    cast: Vec<Cast>,
+    crew: Vec<Crew>,
+) -> Vec<ImageSource> {
+    let mut image_sources: Vec<ImageSource> = vec![];
+
+    if let Some(image) = series_info.image {
+        image_sources.push((
+            image.medium_image_url,
+            Some(ImageResolution::Medium(ImageKind::PosterThumb)),
+        ));
+        image_sources.push((
+            image.original_image_url,
+            Some(ImageResolution::Original(ImageKind::Poster)),
+        ));
+    }
+
+    image_sources.extend(
+        images
+            .into_iter()
+            .map(|image| (image.resolutions.original.url, None)),
+    );
+
+    image_sources.extend(
+        episodes
+            .iter()
+            .filter_map(|episode| episode.image.as_ref())
+            .map(|image| {
+                let (image_url, image_resolution) = episode_still_source(image);
+                (image_url, Some(image_resolution))
+            }),
+    );
+
+    image_sources.extend(
+        cast.into_iter()
+            .flat_map(|cast| [cast.person.image, cast.character.image])
+            .chain(crew.into_iter().map(|crew| crew.person.image))
+            .flatten()
+            .map(|image| {
+                (
+                    image.medium_image_url,
+                    Some(ImageResolution::Medium(ImageKind::CastAvatar)),
+                )
+            }),
+    );
+
+    image_sources
+}
+
+fn get_image_hashes(image_sources: &[ImageSource]) -> HashSet<String> {
+    image_sources
+        .iter()
+        .map(|(image_url, _)| image_hash(image_url))
+        .collect()
+}
+
+/// Loads the image into the cache, returning whether it got loaded
+async fn load_image_source((image_url, image_resolution): ImageSource) -> bool {
+    match image_resolution {
+        Some(image_resolution) => load_image(image_url, image_resolution).await,
+        None => load_original_image(image_url).await,
+    }
+    .is_some()
+}
+
+/// Computes the disk space taken by the cache of the given series and images
+async fn get_disk_size(series_ids: &[u32], image_hashes: &HashSet<String>) -> anyhow::Result<u64> {
+    let mut disk_size = image_cache::get_images_size(image_hashes).await?;
+
+    for series_id in series_ids {
+        disk_size += get_directory_size(&CACHER.get_series_cache_folder_path(*series_id)).await?;
+    }
+
+    Ok(disk_size)
+}
+
+async fn get_directory_size(directory: &path::Path) -> std::io::Result<u64> {
+    let mut read_dir = match fs::read_dir(directory).await {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err),
+    };
+
+    let mut size = 0;
+    while let Some(dir_entry) = read_dir.next_entry().await? {
+        let metadata = dir_entry.metadata().await?;
+        if metadata.is_file() {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::api::tv_maze::{test_fixtures, Image};
+    use crate::core::caching::get_image_variant_path;
+
+    #[test]
+    fn episode_still_source_test() {
+        let mut episode = test_fixtures::episode(1, Some(1));
+        episode.image = Some(Image {
+            original_image_url: "https://static.tvmaze.com/original_untouched/1.jpg".to_owned(),
+            medium_image_url: "https://static.tvmaze.com/medium_landscape/1.jpg".to_owned(),
+        });
+
+        let image_sources = get_image_sources(
+            test_fixtures::series_info(1),
+            vec![],
+            std::slice::from_ref(&episode),
+            vec![],
+            vec![],
+        );
+        let [(image_url, Some(image_resolution))] = image_sources.as_slice() else {
+            panic!(
+                "expected the episode still only, got {} images",
+                image_sources.len()
+            );
+        };
+
+        // The prefetched still has to be the cached image the episode widget loads
+        let (widget_image_url, widget_image_resolution) =
+            episode_still_source(episode.image.as_ref().unwrap());
+        assert_eq!(
+            get_image_variant_path(image_url, image_resolution.image_kind()),
+            get_image_variant_path(&widget_image_url, widget_image_resolution.image_kind())
+        );
+        assert_eq!(
+            widget_image_url,
+            "https://static.tvmaze.com/medium_landscape/1.jpg"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+use std::io::ErrorKind;
+
+use super::{read_json_cache, write_cache, CacheFilePath, CACHER};
+use crate::core::api::tv_maze::{
+    deserialize_json, episodes_information::Episode,
+    seasons_list::get_seasons_list as get_seasons_list_api, ApiError,
+};
+use tracing::info;
+
+pub use crate::core::api::tv_maze::seasons_list::Season;
+
+pub async fn get_seasons_list(series_id: u32) -> Result<Vec<Season>, ApiError> {
+    let series_season_list_path =
+        CACHER.get_cache_file_path(CacheFilePath::SeriesSeasonList(series_id));
+
+    match read_json_cache(&series_season_list_path).await {
+        Ok(seasons) => Ok(seasons),
+        Err(err) => {
+            info!(
+                "falling back online for 'series season list' for series id {}",
+                series_id
+            );
+            let json_string = get_seasons_list_api(series_id).await?;
+            let seasons = deserialize_json(&json_string)?;
+            if err.kind() == ErrorKind::NotFound {
+                write_cache(&json_string, &series_season_list_path).await;
+            }
+            Ok(seasons)
+        }
+    }
+}
+
+/// Whether the episode is the last one announced for it's season
+///
+/// Always `false` when the number of episodes of the season has not been announced, as the last
+/// known episode of a season still airing is not necessarily it's finale.
+pub fn is_season_finale(seasons: &[Season], episode: &Episode) -> bool {
+    let Some(episode_number) = episode.number else {
+        return false;
+    };
+
+    seasons
+        .iter()
+        .find(|season| season.number == episode.season)
+        .and_then(|season| season.episode_order)
+        == Some(episode_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::api::tv_maze::test_fixtures::episode;
+
+    fn season(number: u32, episode_order: Option<u32>) -> Season {
+        Season {
+            number,
+            episode_order,
+            premiere_date: None,
+            end_date: None,
+        }
+    }
+
+    #[test]
+    fn is_season_finale_test() {
+        let seasons = [season(1, Some(10)), season(2, None)];
+
+        assert!(is_season_finale(&seasons, &episode(1, Some(10))));
+        assert!(!is_season_finale(&seasons, &episode(1, Some(9))));
+        assert!(!is_season_finale(&seasons, &episode(1, None)));
+
+        // Unknown episode counts are never guessed from the episodes aired so far
+        assert!(!is_season_finale(&seasons, &episode(2, Some(3))));
+        assert!(!is_season_finale(&seasons, &episode(3, Some(1))));
+        assert!(!is_season_finale(&[], &episode(1, Some(10))));
+    }
+}
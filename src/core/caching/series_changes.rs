@@ -0,0 +1,214 @@
+//! # Detecting changes of tracked series
+//!
+//! The cache updater replaces the cache of series that have changed upstream. The replaced cache
+//! of tracked series is read beforehand and compared with the new one so that the changes worth
+//! knowing about, like a series ending or a new season being announced, can be notified.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Local};
+
+use super::{read_json_cache, CacheFilePath, CACHER};
+use crate::core::api::tv_maze::episodes_information::Episode;
+use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeriesChange {
+    /// The status of the series changed to Ended
+    Ended,
+    /// A season that did not exist before appeared, with it's premiere date when already scheduled
+    NewSeason {
+        season: u32,
+        premiere: Option<DateTime<Local>>,
+    },
+    /// An existing season which had no premiere date got one
+    PremiereScheduled {
+        season: u32,
+        premiere: DateTime<Local>,
+    },
+}
+
+/// The cached series data the changes are detected from
+pub struct CachedSeriesData {
+    series_info: SeriesMainInformation,
+    episodes: Vec<Episode>,
+}
+
+impl CachedSeriesData {
+    /// Reads the series data from the cache, `None` when any of it is not cached
+    pub async fn read(series_id: u32) -> Option<Self> {
+        let series_info_path =
+            CACHER.get_cache_file_path(CacheFilePath::SeriesMainInformation(series_id));
+        let episode_list_path =
+            CACHER.get_cache_file_path(CacheFilePath::SeriesEpisodeList(series_id));
+
+        Some(Self {
+            series_info: read_json_cache(&series_info_path).await.ok()?,
+            episodes: read_json_cache(&episode_list_path).await.ok()?,
+        })
+    }
+
+    pub fn get_series_info(&self) -> &SeriesMainInformation {
+        &self.series_info
+    }
+
+    /// Compares this older data with the given newer data of the same series
+    pub fn get_changes(&self, new_data: &Self) -> Vec<SeriesChange> {
+        let mut changes = vec![];
+
+        if !self.series_info.has_ended() && new_data.series_info.has_ended() {
+            changes.push(SeriesChange::Ended);
+        }
+
+        let old_seasons: HashSet<u32> =
+            self.episodes.iter().map(|episode| episode.season).collect();
+
+        let mut new_seasons: Vec<u32> = new_data
+            .episodes
+            .iter()
+            .map(|episode| episode.season)
+            .collect::<HashSet<u32>>()
+            .into_iter()
+            .collect();
+        new_seasons.sort_unstable();
+
+        for season in new_seasons {
+            let premiere = new_data.get_scheduled_premiere(season);
+
+            if !old_seasons.contains(&season) {
+                changes.push(SeriesChange::NewSeason { season, premiere });
+            } else if let Some(premiere) = premiere {
+                if self.get_scheduled_premiere(season).is_none() {
+                    changes.push(SeriesChange::PremiereScheduled { season, premiere });
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// The release time of the first episode of the season if it's in the future
+    fn get_scheduled_premiere(&self, season: u32) -> Option<DateTime<Local>> {
+        self.episodes
+            .iter()
+            .find(|episode| episode.season == season && episode.number == Some(1))
+            .and_then(|episode| episode.local_date_time().ok())
+            .filter(|release_time| *release_time > Local::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::api::tv_maze::test_fixtures;
+
+    fn episode(season: u32, number: u32, airstamp: Option<DateTime<Local>>) -> Episode {
+        let mut episode = test_fixtures::episode(season, Some(number));
+        episode.airstamp = airstamp.map(|airstamp| airstamp.to_rfc3339());
+        episode
+    }
+
+    fn series_data(status: &str, episodes: Vec<Episode>) -> CachedSeriesData {
+        let mut series_info = test_fixtures::series_info(1);
+        series_info.status = status.to_owned();
+
+        CachedSeriesData {
+            series_info,
+            episodes,
+        }
+    }
+
+    fn date_time(date_time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(date_time)
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    #[test]
+    fn status_change_test() {
+        let old_data = series_data("Running", vec![]);
+
+        assert_eq!(
+            old_data.get_changes(&series_data("Ended", vec![])),
+            [SeriesChange::Ended]
+        );
+        assert!(old_data
+            .get_changes(&series_data("Running", vec![]))
+            .is_empty());
+        assert!(series_data("Ended", vec![])
+            .get_changes(&series_data("Ended", vec![]))
+            .is_empty());
+    }
+
+    #[test]
+    fn new_episodes_test() {
+        let aired = Some(date_time("2020-01-01T20:00:00+00:00"));
+        let premiere = Local::now() + chrono::Duration::days(30);
+        let old_data = series_data("Running", vec![episode(1, 1, aired), episode(1, 2, aired)]);
+
+        // New episodes of an existing season are not worth a notification
+        let new_data = series_data(
+            "Running",
+            vec![
+                episode(1, 1, aired),
+                episode(1, 2, aired),
+                episode(1, 3, None),
+            ],
+        );
+        assert!(old_data.get_changes(&new_data).is_empty());
+
+        let new_data = series_data(
+            "Running",
+            vec![
+                episode(1, 1, aired),
+                episode(1, 2, aired),
+                episode(2, 1, Some(premiere)),
+                episode(3, 1, None),
+            ],
+        );
+        assert_eq!(
+            old_data.get_changes(&new_data),
+            [
+                SeriesChange::NewSeason {
+                    season: 2,
+                    premiere: Some(premiere)
+                },
+                SeriesChange::NewSeason {
+                    season: 3,
+                    premiere: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_airdates_test() {
+        let aired = Some(date_time("2020-01-01T20:00:00+00:00"));
+        let premiere = Local::now() + chrono::Duration::days(30);
+        let old_data = series_data("Running", vec![episode(1, 1, aired), episode(2, 1, None)]);
+
+        let new_data = series_data(
+            "Running",
+            vec![episode(1, 1, aired), episode(2, 1, Some(premiere))],
+        );
+        assert_eq!(
+            old_data.get_changes(&new_data),
+            [SeriesChange::PremiereScheduled {
+                season: 2,
+                premiere
+            }]
+        );
+
+        // Premieres that were already scheduled are not reported again when moved
+        let moved_premiere = premiere + chrono::Duration::days(7);
+        let moved_data = series_data(
+            "Running",
+            vec![episode(1, 1, aired), episode(2, 1, Some(moved_premiere))],
+        );
+        assert!(new_data.get_changes(&moved_data).is_empty());
+
+        // Premieres in the past are not scheduled
+        let past_data = series_data("Running", vec![episode(1, 1, aired), episode(2, 1, aired)]);
+        assert!(old_data.get_changes(&past_data).is_empty());
+    }
+}
@@ -3,8 +3,9 @@ use tokio::sync::mpsc;
 use tracing::info;
 
 use super::episode_list::EpisodeList;
+use super::fetch_limiter::FETCH_LIMITER;
 use super::series_information::get_series_main_info_with_id;
-use super::CACHER;
+use super::{atomic_write, CACHER};
 use crate::core::api::tv_maze::series_information::get_series_info_and_episode_list;
 
 #[derive(Copy, Clone)]
@@ -44,7 +45,7 @@ impl SeriesInfoAndEpisodeList {
             .map(|series_id| {
                 let series_id = *series_id;
                 let sender = self.completion_signal_sender.clone();
-                tokio::spawn(async move {
+                FETCH_LIMITER.spawn(async move {
                     let res = Self::cache_series(series_id).await;
                     if report_progress {
                         sender
@@ -87,11 +88,10 @@ impl SeriesInfoAndEpisodeList {
 
                     info!("caching 'series information' for series id {}", series_id);
 
-                    fs::write(
-                        series_cache_path,
-                        serde_json::to_string_pretty(&series_info)
-                            .expect("series information should be serializable"),
-                    )
+                    let series_info_json = serde_json::to_string_pretty(&series_info)
+                        .expect("series information should be serializable");
+
+                    async move { atomic_write(&series_cache_path, series_info_json).await }
                 });
 
                 let handle_2 = tokio::spawn({
@@ -107,11 +107,10 @@ impl SeriesInfoAndEpisodeList {
 
                     info!("caching 'episode list' for series id {}", series_id);
 
-                    fs::write(
-                        episode_cache_path,
-                        serde_json::to_string_pretty(&episode_list)
-                            .expect("series information should be serializable"),
-                    )
+                    let episode_list_json = serde_json::to_string_pretty(&episode_list)
+                        .expect("series information should be serializable");
+
+                    async move { atomic_write(&episode_cache_path, episode_list_json).await }
                 });
 
                 handle_1.await??;
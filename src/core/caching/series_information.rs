@@ -1,3 +1,5 @@
+use super::fetch_limiter::FETCH_LIMITER;
+use super::memory_cache::MEMORY_CACHE;
 use super::tv_maze::series_information;
 use super::*;
 
@@ -6,7 +8,7 @@ use std::io::ErrorKind;
 pub async fn get_series_main_info_with_url(url: String) -> Result<SeriesMainInformation, ApiError> {
     let id = url
         .split('/')
-        .last()
+        .next_back()
         .expect("invalid url, no series id at the end of url")
         .parse::<u32>()
         .expect("could not parse series id from url");
@@ -17,22 +19,32 @@ pub async fn get_series_main_info_with_url(url: String) -> Result<SeriesMainInfo
 pub async fn get_series_main_info_with_id(
     series_id: u32,
 ) -> Result<SeriesMainInformation, ApiError> {
+    if let Some(series_info) = MEMORY_CACHE.get_series_information(series_id) {
+        return Ok(series_info);
+    }
+
+    let series_info = load_series_main_info(series_id).await?;
+    MEMORY_CACHE.insert_series_information(series_info.clone());
+    Ok(series_info)
+}
+
+async fn load_series_main_info(series_id: u32) -> Result<SeriesMainInformation, ApiError> {
     let series_information_path =
         CACHER.get_cache_file_path(CacheFilePath::SeriesMainInformation(series_id));
 
-    let series_information_json = match read_cache(&series_information_path).await {
-        Ok(json_string) => json_string,
+    match read_json_cache(&series_information_path).await {
+        Ok(series_info) => Ok(series_info),
         Err(err) => {
             info!("falling back online for 'series information' for series id: {series_id}");
             let json_string = series_information::get_series_main_info_with_id(series_id).await?;
+            let series_info = deserialize_json(&json_string)?;
 
             if err.kind() == ErrorKind::NotFound {
                 write_cache(&json_string, &series_information_path).await;
             }
-            json_string
+            Ok(series_info)
         }
-    };
-    deserialize_json(&series_information_json)
+    }
 }
 
 /// Caches the given `SeriesMainInformation`'s `&str` if not cached already
@@ -47,7 +59,7 @@ pub async fn cache_series_information(series_id: u32, series_info_str: &str) {
 pub async fn get_series_main_info_with_ids(series_ids: Vec<String>) -> Vec<SeriesMainInformation> {
     let handles: Vec<_> = series_ids
         .iter()
-        .map(|id| tokio::spawn(get_series_main_info_with_id(id.parse().unwrap())))
+        .map(|id| FETCH_LIMITER.spawn(get_series_main_info_with_id(id.parse().unwrap())))
         .collect();
 
     let mut series_infos = Vec::with_capacity(handles.len());
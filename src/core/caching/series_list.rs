@@ -1,6 +1,6 @@
 //! Perform different operations on the database series
 
-use super::{episode_list::EpisodeReleaseTime, series_information};
+use super::{episode_list::EpisodeReleaseTime, fetch_limiter::FETCH_LIMITER, series_information};
 use crate::core::{
     api::tv_maze::{episodes_information::Episode, series_information::SeriesMainInformation},
     database::{self, Series},
@@ -58,7 +58,7 @@ impl SeriesList {
 
         let handles: Vec<_> = untracked_ids
             .iter()
-            .map(|id| tokio::spawn(series_information::get_series_main_info_with_id(*id)))
+            .map(|id| FETCH_LIMITER.spawn(series_information::get_series_main_info_with_id(*id)))
             .collect();
 
         let mut series_information = Vec::with_capacity(handles.len());
@@ -92,7 +92,7 @@ impl SeriesList {
 
         let handles: Vec<_> = tracked_ids
             .iter()
-            .map(|id| tokio::spawn(series_information::get_series_main_info_with_id(*id)))
+            .map(|id| FETCH_LIMITER.spawn(series_information::get_series_main_info_with_id(*id)))
             .collect();
 
         let mut series_information = Vec::with_capacity(handles.len());
@@ -112,7 +112,7 @@ impl SeriesList {
             .iter()
             .map(|(id, _)| {
                 let id = id.parse().expect("could not parse series id");
-                tokio::spawn(series_information::get_series_main_info_with_id(id))
+                FETCH_LIMITER.spawn(series_information::get_series_main_info_with_id(id))
             })
             .collect();
 
@@ -153,16 +153,12 @@ impl SeriesList {
 
         let mut episode_list_handles = Vec::with_capacity(series_infos.len());
         for series_info in series_infos.iter() {
-            episode_list_handles.push(tokio::spawn(super::episode_list::EpisodeList::new(
-                series_info.id,
-            )))
+            episode_list_handles
+                .push(FETCH_LIMITER.spawn(super::episode_list::EpisodeList::new(series_info.id)))
         }
 
         let mut waiting_releases_series_infos = Vec::with_capacity(series_infos.len());
-        for (handle, series_info) in episode_list_handles
-            .into_iter()
-            .zip(series_infos.into_iter())
-        {
+        for (handle, series_info) in episode_list_handles.into_iter().zip(series_infos) {
             let episode_list = handle.await??;
             if episode_list.get_next_episode_to_air().is_none() {
                 waiting_releases_series_infos.push(series_info)
@@ -179,10 +175,12 @@ impl SeriesList {
 
         let handles: Vec<_> = series_infos
             .iter()
-            .map(|series_info| tokio::spawn(super::episode_list::EpisodeList::new(series_info.id)))
+            .map(|series_info| {
+                FETCH_LIMITER.spawn(super::episode_list::EpisodeList::new(series_info.id))
+            })
             .collect();
 
-        for (handle, series_info) in handles.into_iter().zip(series_infos.into_iter()) {
+        for (handle, series_info) in handles.into_iter().zip(series_infos) {
             let episode_list = handle.await??;
             if let Some((next_episode, Some(release_time))) = episode_list
                 .get_next_episode_to_air()
@@ -1,7 +1,7 @@
 use std::io::ErrorKind;
 
 use super::{
-    load_image, read_cache, write_cache, CacheFilePath, ImageKind, ImageResolution, CACHER,
+    load_image, read_json_cache, write_cache, CacheFilePath, ImageKind, ImageResolution, CACHER,
 };
 use crate::core::api::tv_maze::{
     deserialize_json,
@@ -14,22 +14,21 @@ pub async fn get_show_images(series_id: u32) -> Result<Vec<Image>, ApiError> {
     let series_image_list_path =
         CACHER.get_cache_file_path(CacheFilePath::SeriesImageList(series_id));
 
-    let image_list_json = match read_cache(&series_image_list_path).await {
-        Ok(info) => info,
+    match read_json_cache(&series_image_list_path).await {
+        Ok(images) => Ok(images),
         Err(err) => {
             info!(
                 "falling back online for 'series image list' for series id {}",
                 series_id
             );
             let json_string = get_show_images_api(series_id).await?;
+            let images = deserialize_json(&json_string)?;
             if err.kind() == ErrorKind::NotFound {
                 write_cache(&json_string, &series_image_list_path).await;
             }
-            json_string
+            Ok(images)
         }
-    };
-
-    deserialize_json(&image_list_json)
+    }
 }
 
 /// Loads the most recent image banner from the provided series id
@@ -39,8 +38,7 @@ pub async fn get_recent_banner(series_id: u32) -> Option<bytes::Bytes> {
     // Trying to take a background first if any
     if let Some(recent_background) = images
         .iter()
-        .filter(|image| image.get_image_type() == Some(ImageType::Background))
-        .last()
+        .rfind(|image| image.get_image_type() == Some(ImageType::Background))
     {
         return load_image(
             recent_background.resolutions.original.url.clone(),
@@ -52,8 +50,7 @@ pub async fn get_recent_banner(series_id: u32) -> Option<bytes::Bytes> {
     // Falling to anything that is not a poster as poster dimensions don't look great as a background
     let recent_banner = images
         .into_iter()
-        .filter(|image| image.get_image_type() != Some(ImageType::Poster))
-        .last()?;
+        .rfind(|image| image.get_image_type() != Some(ImageType::Poster))?;
 
     load_image(
         recent_banner.resolutions.original.url,
@@ -6,6 +6,8 @@ use crate::core::api::tv_maze::tv_schedule::{get_episodes_with_country, get_epis
 use crate::core::api::tv_maze::Rated;
 use crate::core::posters_hiding::HIDDEN_SERIES;
 
+use super::fetch_limiter::FETCH_LIMITER;
+
 pub mod full_schedule;
 
 /// Retrieves series aired on a specific date through the provided optional &str
@@ -105,7 +107,7 @@ async fn get_series_infos_from_episodes(
         .into_iter()
         .filter_map(|mut episode| episode.take())
         .map(|episode| {
-            tokio::spawn(super::series_information::get_series_main_info_with_url(
+            FETCH_LIMITER.spawn(super::series_information::get_series_main_info_with_url(
                 episode.links.show.href,
             ))
         })
@@ -12,7 +12,8 @@ use crate::core::api::tv_maze::series_information::{
 };
 use crate::core::api::tv_maze::tv_schedule::get_full_schedule;
 use crate::core::api::tv_maze::{deserialize_json, Rated};
-use crate::core::caching::CACHER;
+use crate::core::caching::{atomic_write, read_json_cache, CACHER};
+use crate::core::offline::OFFLINE;
 
 const FULL_SCHEDULE_CACHE_FILENAME: &str = "full-schedule";
 
@@ -68,7 +69,10 @@ impl FullSchedule {
                         error!("failed to get daily episode schedule age: {}", err);
                         std::time::Duration::default()
                     });
-                    if daily_schedule_age > std::time::Duration::from_secs(24 * 60 * 60) {
+                    // An outdated schedule is better than none when offline
+                    if daily_schedule_age > std::time::Duration::from_secs(24 * 60 * 60)
+                        && !OFFLINE.should_skip_requests()
+                    {
                         info!("cleaning outdated daily episode schedule");
                         fs::remove_file(&cache_path).await.unwrap_or_else(|err| {
                             error!("failed to clean outdated daily episode schedule: {}", err)
@@ -83,14 +87,15 @@ impl FullSchedule {
             Err(err) => error!("failed to get daily episode schedule metadata: {}", err),
         }
 
-        let json_string = match fs::read_to_string(&cache_path).await {
-            Ok(json_string) => json_string,
+        let episodes = match read_json_cache(&cache_path).await {
+            Ok(episodes) => episodes,
             Err(err) => {
                 if let std::io::ErrorKind::NotFound = err.kind() {
                     info!("downloading daily episode schedule");
                     let cache_str = get_full_schedule()
                         .await
                         .context("failed to download daily episode schedule")?;
+                    let episodes = deserialize_json::<Vec<Episode>>(&cache_str)?;
                     {
                         // Creating cache directory if it does not exist
                         let mut cache_dir = cache_path.clone();
@@ -100,10 +105,10 @@ impl FullSchedule {
                             .context("failed to create cache dir")?;
                     }
 
-                    fs::write(cache_path, &cache_str)
+                    atomic_write(&cache_path, &cache_str)
                         .await
                         .context("failed to save daily episode schedule")?;
-                    cache_str
+                    episodes
                 } else {
                     bail!(
                         "critical error when reading daily episode schedule: {}",
@@ -113,7 +118,6 @@ impl FullSchedule {
             }
         };
 
-        let episodes = deserialize_json::<Vec<Episode>>(&json_string)?;
         Ok(Self { episodes })
     }
 
@@ -251,7 +255,7 @@ impl FullSchedule {
             series_info
                 .get_genres()
                 .into_iter()
-                .any(|series_genre| genres.iter().any(|genre| *genre == series_genre))
+                .any(|series_genre| genres.contains(&series_genre))
         })
     }
 
@@ -412,7 +416,7 @@ impl FullSchedule {
             .filter(|episode| {
                 episode
                     .date_naive()
-                    .map(|naive_date| all_dates_of_month.iter().any(|date| *date == naive_date))
+                    .map(|naive_date| all_dates_of_month.contains(&naive_date))
                     .unwrap_or(false)
             })
             .collect();
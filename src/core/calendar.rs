@@ -0,0 +1,228 @@
+//! # iCalendar export of upcoming episodes
+//!
+//! The upcoming episodes of the tracked series are exported as an iCalendar ([RFC 5545](https://datatracker.ietf.org/doc/html/rfc5545))
+//! file so that they can be viewed in regular calendar apps.
+//!
+//! Every event has a UID made from the series id, season and episode number of it's episode, so
+//! importing a newer export updates the events of an older one instead of duplicating them.
+//!
+//! The same events can also be subscribed to through the local calendar feed of the [`feed`] module.
+
+use std::path;
+
+use anyhow::Context;
+use chrono::{DateTime, Duration, Local, Utc};
+
+use super::api::tv_maze::episodes_information::Episode;
+use super::api::tv_maze::series_information::SeriesMainInformation;
+use super::caching::episode_list::EpisodeList;
+use super::caching::series_list::SeriesList;
+use crate::gui::helpers::season_episode_str_gen;
+
+pub mod feed;
+
+const PRODUCT_IDENTIFIER: &str = "-//Series Troxide//Upcoming Episodes//EN";
+const CALENDAR_NAME: &str = "Series Troxide";
+
+/// The maximum length of a content line in octets, longer lines are folded
+const MAX_LINE_LENGTH: usize = 75;
+
+/// An episode release as a calendar event
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: String,
+    pub start: DateTime<Utc>,
+    /// `None` when the runtime of the episode is unknown
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl CalendarEvent {
+    /// Creates the event of the episode, `None` when it has no number or release time
+    pub fn from_episode(series_info: &SeriesMainInformation, episode: &Episode) -> Option<Self> {
+        let episode_number = episode.number?;
+        let start = episode.local_date_time().ok()?.with_timezone(&Utc);
+        let end = episode
+            .runtime
+            .or(series_info.average_runtime)
+            .map(|runtime| start + Duration::minutes(runtime as i64));
+
+        Some(Self {
+            uid: episode_uid(series_info.id, episode.season, episode_number),
+            summary: format!(
+                "{} {}",
+                series_info.name,
+                season_episode_str_gen(episode.season, episode_number)
+            ),
+            description: episode.name.clone(),
+            start,
+            end,
+        })
+    }
+}
+
+/// The UID of the event of the given episode, always the same for the same episode
+fn episode_uid(series_id: u32, season: u32, episode_number: u32) -> String {
+    format!("{}-{}-{}@series-troxide", series_id, season, episode_number)
+}
+
+/// Retrieves the events of the episodes of the tracked series that are yet to be released
+///
+/// The events are sorted by their start.
+pub async fn get_upcoming_episode_events() -> anyhow::Result<Vec<CalendarEvent>> {
+    let now = Local::now();
+    let mut events = vec![];
+
+    for series_info in SeriesList::new()
+        .get_running_tracked_series_information()
+        .await
+        .context("failed to get running tracked series")?
+    {
+        let episode_list = match EpisodeList::new(series_info.id).await {
+            Ok(episode_list) => episode_list,
+            Err(err) => {
+                tracing::error!(
+                    "failed to get the episode list of \"{}\" for the calendar: {}",
+                    series_info.name,
+                    err
+                );
+                continue;
+            }
+        };
+
+        events.extend(
+            episode_list
+                .get_all_episodes()
+                .iter()
+                .filter(|episode| {
+                    episode
+                        .local_date_time()
+                        .is_ok_and(|release_time| release_time > now)
+                })
+                .filter_map(|episode| CalendarEvent::from_episode(&series_info, episode)),
+        );
+    }
+
+    events.sort_by_key(|event| event.start);
+    Ok(events)
+}
+
+/// Generates the iCalendar file contents of the given events
+pub fn ics_str(events: &[CalendarEvent]) -> String {
+    let time_stamp = format_date_time(&Utc::now());
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        format!("PRODID:{}", PRODUCT_IDENTIFIER),
+        "CALSCALE:GREGORIAN".to_owned(),
+        "METHOD:PUBLISH".to_owned(),
+        format!("X-WR-CALNAME:{}", CALENDAR_NAME),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_owned());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", time_stamp));
+        lines.push(format!("DTSTART:{}", format_date_time(&event.start)));
+        if let Some(end) = event.end {
+            lines.push(format!("DTEND:{}", format_date_time(&end)));
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&event.description)));
+        lines.push("END:VEVENT".to_owned());
+    }
+
+    lines.push("END:VCALENDAR".to_owned());
+
+    lines.iter().map(|line| fold_line(line) + "\r\n").collect()
+}
+
+/// Exports the upcoming episodes of the tracked series to an iCalendar file at the given path
+///
+/// Returns the exported events, ordered by their start.
+pub async fn export_upcoming_episodes(
+    path: impl AsRef<path::Path>,
+) -> anyhow::Result<Vec<CalendarEvent>> {
+    let events = get_upcoming_episode_events().await?;
+
+    tokio::fs::write(path, ics_str(&events))
+        .await
+        .context("failed to write the calendar file")?;
+
+    Ok(events)
+}
+
+pub fn blocking_export_upcoming_episodes(
+    path: impl AsRef<path::Path>,
+) -> anyhow::Result<Vec<CalendarEvent>> {
+    tokio::runtime::Runtime::new()?.block_on(export_upcoming_episodes(path))
+}
+
+fn format_date_time(date_time: &DateTime<Utc>) -> String {
+    date_time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits the line into lines of at most `MAX_LINE_LENGTH` octets, continued lines starting with a space
+fn fold_line(line: &str) -> String {
+    let mut folded_line = String::with_capacity(line.len());
+    let mut line_length = 0;
+
+    for character in line.chars() {
+        if line_length + character.len_utf8() > MAX_LINE_LENGTH {
+            folded_line.push_str("\r\n ");
+            line_length = 1;
+        }
+        folded_line.push(character);
+        line_length += character.len_utf8();
+    }
+
+    folded_line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ics_str_test() {
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let events = vec![CalendarEvent {
+            uid: episode_uid(1, 2, 3),
+            summary: "Show, The S02E03".to_owned(),
+            description: "Part one; the beginning".to_owned(),
+            start,
+            end: Some(start + Duration::minutes(45)),
+        }];
+
+        let ics = ics_str(&events);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("\r\nUID:1-2-3@series-troxide\r\n"));
+        assert!(ics.contains("\r\nDTSTART:20231114T221320Z\r\n"));
+        assert!(ics.contains("\r\nDTEND:20231114T225820Z\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:Show\\, The S02E03\r\n"));
+        assert!(ics.contains("\r\nDESCRIPTION:Part one\\; the beginning\r\n"));
+    }
+
+    #[test]
+    fn fold_line_test() {
+        let line = format!("DESCRIPTION:{}", "é".repeat(50));
+        let folded_line = fold_line(&line);
+
+        assert!(folded_line
+            .split("\r\n")
+            .all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert_eq!(folded_line.replace("\r\n ", ""), line);
+    }
+}
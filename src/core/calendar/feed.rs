@@ -0,0 +1,283 @@
+//! # Local calendar feed
+//!
+//! An opt-in HTTP server on the loopback interface serving a live iCalendar feed at `/calendar.ics`,
+//! so that calendar apps can subscribe to the upcoming releases instead of importing one-off exports.
+//!
+//! The feed is made from the same upcoming releases as the ones displayed in the My Shows tab and
+//! is generated anew for every request.
+
+use std::future::Future;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+use anyhow::{bail, Context};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{error, info};
+
+use super::{ics_str, CalendarEvent};
+use crate::core::caching::series_list::SeriesList;
+use crate::core::settings_config;
+
+pub const FEED_PATH: &str = "/calendar.ics";
+
+/// How long a client has to send it's request before the connection is dropped
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The longest request and header lines accepted, longer ones dropping the connection
+const MAX_LINE_LENGTH: u64 = 8 * 1024;
+
+/// The most header lines read from a request
+const MAX_HEADER_LINES: usize = 100;
+
+/// How long to wait before accepting connections again after failing to accept one, as the failure
+/// may be from running out of file descriptors
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// The url calendar apps can subscribe to when the feed is served at the given port
+pub fn feed_url(port: u16) -> String {
+    format!("http://{}:{}{}", Ipv4Addr::LOCALHOST, port, FEED_PATH)
+}
+
+/// Serves the calendar feed when it's enabled in the settings, otherwise returns right away
+pub async fn serve_if_enabled() -> anyhow::Result<()> {
+    let calendar_feed_settings = settings_config::Settings::new()
+        .get_current_settings()
+        .calendar_feed
+        .clone();
+
+    if !calendar_feed_settings.enabled {
+        return Ok(());
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, calendar_feed_settings.port))
+        .await
+        .with_context(|| {
+            format!(
+                "failed to bind the calendar feed to port {}",
+                calendar_feed_settings.port
+            )
+        })?;
+
+    info!(
+        "serving calendar feed at {}",
+        feed_url(calendar_feed_settings.port)
+    );
+
+    serve(listener, get_feed_events).await;
+    Ok(())
+}
+
+/// Serves the events given by `get_events` for every feed request
+async fn serve<F, Fut>(listener: TcpListener, get_events: F)
+where
+    F: Fn() -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = Vec<CalendarEvent>> + Send,
+{
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(err) => {
+                // Failing to accept a connection does not affect the next ones
+                error!("failed to accept calendar feed connection: {}", err);
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+
+        let get_events = get_events.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, get_events).await {
+                error!("failed to respond to calendar feed request: {}", err);
+            }
+        });
+    }
+}
+
+async fn handle_connection<F, Fut>(mut stream: TcpStream, get_events: F) -> anyhow::Result<()>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Vec<CalendarEvent>>,
+{
+    let mut reader = BufReader::new(&mut stream);
+
+    let request_line = tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request(&mut reader))
+        .await
+        .context("timed out reading the request")??;
+
+    let mut request_parts = request_line.split_whitespace();
+    let method = request_parts.next().unwrap_or_default();
+    let path = request_parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    // Some calendar apps check the feed with a HEAD request before getting it
+    let (status, content_type, body) = match (method, path) {
+        ("GET" | "HEAD", FEED_PATH) => (
+            "200 OK",
+            "text/calendar; charset=utf-8",
+            ics_str(&get_events().await),
+        ),
+        (_, FEED_PATH) => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed".to_owned(),
+        ),
+        _ => ("404 Not Found", "text/plain", "not found".to_owned()),
+    };
+
+    let mut response = response_head(status, content_type, body.len());
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Reads the request returning it's request line
+async fn read_request(reader: &mut BufReader<&mut TcpStream>) -> anyhow::Result<String> {
+    let request_line = read_line(reader).await?;
+
+    // The headers are of no use but are read so that the client is done sending before the response
+    for _ in 0..MAX_HEADER_LINES {
+        let header = read_line(reader).await?;
+        if header.trim().is_empty() {
+            break;
+        }
+    }
+
+    Ok(request_line)
+}
+
+async fn read_line(reader: &mut BufReader<&mut TcpStream>) -> anyhow::Result<String> {
+    let mut line = String::new();
+    (&mut *reader)
+        .take(MAX_LINE_LENGTH)
+        .read_line(&mut line)
+        .await?;
+
+    if line.len() as u64 >= MAX_LINE_LENGTH && !line.ends_with('\n') {
+        bail!("request line longer than {} bytes", MAX_LINE_LENGTH)
+    }
+
+    Ok(line)
+}
+
+/// The status line and headers of a response with a body of the given length
+fn response_head(status: &str, content_type: &str, content_length: usize) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status, content_type, content_length
+    )
+}
+
+/// The events of the upcoming releases of the tracked series, as displayed in the My Shows tab
+async fn get_feed_events() -> Vec<CalendarEvent> {
+    SeriesList::new()
+        .get_upcoming_release_series_information_and_episodes()
+        .await
+        .unwrap_or_else(|err| {
+            error!(
+                "failed to get upcoming releases for the calendar feed: {}",
+                err
+            );
+            vec![]
+        })
+        .iter()
+        .filter_map(|(series_info, episode, _)| CalendarEvent::from_episode(series_info, episode))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn unknown_requests_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, || async { vec![] }));
+
+        let client = reqwest::Client::new();
+
+        let response = client
+            .get(format!("http://{}/other.ics", address))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+        let response = client
+            .post(format!("http://{}{}", address, FEED_PATH))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn feed_request_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, || async {
+            vec![CalendarEvent {
+                uid: "1-1-1@series-troxide".to_owned(),
+                summary: "Series S01E01".to_owned(),
+                description: "Pilot".to_owned(),
+                start: chrono::DateTime::from_timestamp(1_715_000_000, 0).unwrap(),
+                end: None,
+            }]
+        }));
+
+        let response = reqwest::get(format!("http://{}{}?token=1", address, FEED_PATH))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(
+            response.headers()[reqwest::header::CONTENT_TYPE],
+            "text/calendar; charset=utf-8"
+        );
+
+        let body = response.text().await.unwrap();
+        let lines: Vec<&str> = body.split("\r\n").filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines.first(), Some(&"BEGIN:VCALENDAR"));
+        assert_eq!(lines.last(), Some(&"END:VCALENDAR"));
+        assert!(lines.contains(&"BEGIN:VEVENT"));
+        assert!(lines.contains(&"SUMMARY:Series S01E01"));
+        assert!(lines.contains(&"DTSTART:20240506T125320Z"));
+
+        let response = reqwest::Client::new()
+            .head(format!("http://{}{}", address, FEED_PATH))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(
+            response.headers()[reqwest::header::CONTENT_LENGTH],
+            body.len().to_string().as_str()
+        );
+        assert!(response.bytes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn long_request_line_test() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, || async { vec![] }));
+
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        let request_line = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(10 * 1024));
+        // The connection may be dropped before the whole request is written
+        let _ = stream.write_all(request_line.as_bytes()).await;
+
+        // Dropped without a response
+        let mut response = vec![];
+        let _ = stream.read_to_end(&mut response).await;
+        assert!(response.is_empty());
+    }
+}
@@ -7,9 +7,12 @@ pub mod cli_handler {
     use std::process::exit;
 
     use super::cli_data::*;
+    use crate::core::calendar;
     use crate::core::database;
+    use crate::core::date_time_display::DateTimeDisplay;
     use crate::core::paths;
     use crate::core::settings_config;
+    use crate::core::year_in_review;
 
     /// Handles all the logic for the command line arguments
     pub fn handle_cli() -> anyhow::Result<()> {
@@ -35,6 +38,29 @@ pub mod cli_handler {
                     println!("data exported successfully!");
                     exit(0);
                 }
+                Command::ExportCalendar { file_path } => {
+                    let exported_events = calendar::blocking_export_upcoming_episodes(file_path)?;
+                    println!(
+                        "{} upcoming episodes exported successfully!",
+                        exported_events.len()
+                    );
+                    if let Some(next_event) = exported_events.first() {
+                        println!(
+                            "next release: {} on {}",
+                            next_event.summary,
+                            DateTimeDisplay::from_settings().date_time(&next_event.start)
+                        );
+                    }
+                    exit(0);
+                }
+                Command::ExportYearInReview { directory, year } => {
+                    let exported_files = year_in_review::blocking_export(year, directory)?;
+                    println!("year in review exported successfully!");
+                    for exported_file in exported_files {
+                        println!("{}", exported_file.display());
+                    }
+                    exit(0);
+                }
             }
         }
         Ok(())
@@ -114,5 +140,21 @@ pub mod cli_data {
             /// Export filepath
             file_path: PathBuf,
         },
+
+        /// Export the upcoming episodes of the tracked series as an iCalendar (.ics) file
+        ExportCalendar {
+            /// Export filepath
+            file_path: PathBuf,
+        },
+
+        /// Export a year in review of the watched series as SVG, PNG, Markdown and HTML files
+        ExportYearInReview {
+            /// Export directory
+            directory: PathBuf,
+
+            /// The year to review, the most recent year with watched episodes by default
+            #[clap(short, long)]
+            year: Option<i32>,
+        },
     }
 }
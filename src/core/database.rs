@@ -2,23 +2,62 @@ use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use sled::Db;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{hash_map::DefaultHasher, BTreeMap, HashMap, HashSet},
+    hash::{Hash, Hasher},
     ops::RangeInclusive,
 };
-use tracing::info;
+use tracing::{info, warn};
 
-use super::{api::tv_maze::series_information::SeriesMainInformation, caching};
+use super::{
+    api::tv_maze::{series_information::SeriesMainInformation, ApiError},
+    caching,
+};
+use crate::core::caching::memory_cache::MEMORY_CACHE;
+use crate::core::goals::Goal;
+use crate::core::notifications::Notification;
 use crate::core::paths;
 
 // The last digit represents the version of the database.
 const DATABASE_FOLDER_NAME: &str = "series-troxide-db-1";
 
+/// The tree storing per series notification rules, kept apart from the series themselves
+/// so that their serialization format stays the same.
+const NOTIFICATION_RULES_TREE_NAME: &str = "notification-rules";
+
+/// The tree storing the state of the notifications across program runs
+const NOTIFICATIONS_TREE_NAME: &str = "notifications";
+const LAST_NOTIFICATION_CHECK_KEY: &str = "last-check";
+const DEFERRED_NOTIFICATIONS_KEY: &str = "deferred";
+
+/// The tree storing the notifications that have been sent, oldest first
+const NOTIFICATION_HISTORY_TREE_NAME: &str = "notification-history";
+
+/// The tree storing when the episodes were marked watched, kept apart from the series so that their
+/// serialization format stays the same. Episodes watched before it existed or imported ones have none.
+const WATCH_TIMES_TREE_NAME: &str = "watch-times";
+
+/// The tree storing the watching goals by their generated ids
+const GOALS_TREE_NAME: &str = "goals";
+
+/// The tree storing the ids of the series made available offline, whose images are pinned
+/// in the images cache whether they are tracked or not
+const OFFLINE_SERIES_TREE_NAME: &str = "offline-series";
+
+/// The maximum number of notifications kept in the notification history
+const MAX_NOTIFICATION_HISTORY_ENTRIES: usize = 200;
+
 lazy_static! {
     pub static ref DB: Database = Database::init();
 }
 
 pub struct Database {
     db: Db,
+    notification_rules: sled::Tree,
+    notifications: sled::Tree,
+    notification_history: sled::Tree,
+    watch_times: sled::Tree,
+    goals: sled::Tree,
+    offline_series: sled::Tree,
 }
 
 impl Database {
@@ -32,11 +71,29 @@ impl Database {
         info!("initializing database at {}", database_path.display());
 
         database_path.push(DATABASE_FOLDER_NAME);
+        Self::open(database_path)
+    }
+
+    fn open(database_path: impl AsRef<std::path::Path>) -> Self {
         let db = sled::open(database_path).unwrap();
         if !db.was_recovered() {
             info!("created a fresh database as none was found");
         }
-        Self { db }
+        let notification_rules = db.open_tree(NOTIFICATION_RULES_TREE_NAME).unwrap();
+        let notifications = db.open_tree(NOTIFICATIONS_TREE_NAME).unwrap();
+        let notification_history = db.open_tree(NOTIFICATION_HISTORY_TREE_NAME).unwrap();
+        let watch_times = db.open_tree(WATCH_TIMES_TREE_NAME).unwrap();
+        let goals = db.open_tree(GOALS_TREE_NAME).unwrap();
+        let offline_series = db.open_tree(OFFLINE_SERIES_TREE_NAME).unwrap();
+        Self {
+            db,
+            notification_rules,
+            notifications,
+            notification_history,
+            watch_times,
+            goals,
+            offline_series,
+        }
     }
 
     /// Adds the given series to the database.
@@ -55,6 +112,19 @@ impl Database {
     /// Does nothing when the series does not exist
     pub fn remove_series(&self, series_id: u32) {
         self.db.remove(series_id.to_string()).unwrap();
+
+        for key in self
+            .watch_times
+            .scan_prefix(watch_times_prefix(series_id))
+            .keys()
+        {
+            self.watch_times.remove(key.unwrap()).unwrap();
+        }
+
+        self.notification_rules
+            .remove(series_id.to_string())
+            .unwrap();
+        self.offline_series.remove(series_id.to_be_bytes()).unwrap();
     }
 
     pub fn get_series(&self, series_id: u32) -> Option<Series> {
@@ -121,18 +191,290 @@ impl Database {
             .sum()
     }
 
+    /// Retrieves the notification rule of the given series, the default rule being
+    /// returned when the series has none
+    pub fn get_notification_rule(&self, series_id: u32) -> NotificationRule {
+        self.notification_rules
+            .get(series_id.to_string())
+            .unwrap()
+            .map(|rule_bytes| bincode::deserialize(&rule_bytes).unwrap())
+            .unwrap_or_default()
+    }
+
+    /// Sets the notification rule of the given series
+    ///
+    /// # Note
+    /// Default rules are not stored at all.
+    pub fn set_notification_rule(&self, series_id: u32, notification_rule: NotificationRule) {
+        if notification_rule == NotificationRule::default() {
+            self.notification_rules
+                .remove(series_id.to_string())
+                .unwrap();
+        } else {
+            self.notification_rules
+                .insert(
+                    series_id.to_string(),
+                    bincode::serialize(&notification_rule).unwrap(),
+                )
+                .unwrap();
+        }
+    }
+
+    /// Retrieves all the notification rules by the ids of their series
+    pub fn get_notification_rules(&self) -> BTreeMap<u32, NotificationRule> {
+        self.notification_rules
+            .iter()
+            .filter_map(|entry| {
+                let (id_bytes, rule_bytes) = entry.unwrap();
+                let series_id = String::from_utf8_lossy(&id_bytes).parse().ok()?;
+                Some((series_id, bincode::deserialize(&rule_bytes).unwrap()))
+            })
+            .collect()
+    }
+
+    /// Returns a subscriber that gets notified whenever any notification rule changes
+    pub fn watch_notification_rules(&self) -> sled::Subscriber {
+        self.notification_rules.watch_prefix(vec![])
+    }
+
+    /// Gets the last time the notifications were checked, `None` when they never were
+    pub fn get_last_notification_check(&self) -> Option<chrono::DateTime<chrono::Local>> {
+        self.notifications
+            .get(LAST_NOTIFICATION_CHECK_KEY)
+            .unwrap()
+            .map(|timestamp_bytes| bincode::deserialize::<i64>(&timestamp_bytes).unwrap())
+            .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+            .map(|date_time| date_time.with_timezone(&chrono::Local))
+    }
+
+    /// Records the given time as the last time the notifications were checked
+    pub fn set_last_notification_check(&self, date_time: chrono::DateTime<chrono::Local>) {
+        self.notifications
+            .insert(
+                LAST_NOTIFICATION_CHECK_KEY,
+                bincode::serialize(&date_time.timestamp()).unwrap(),
+            )
+            .unwrap();
+    }
+
+    /// Keeps the notification to be sent at the end of quiet hours
+    pub fn add_deferred_notification(&self, notification: &Notification) {
+        self.notifications
+            .fetch_and_update(DEFERRED_NOTIFICATIONS_KEY, |deferred_bytes| {
+                let mut deferred_notifications = deserialize_deferred_notifications(deferred_bytes);
+                deferred_notifications.push(notification.clone());
+                Some(bincode::serialize(&deferred_notifications).unwrap())
+            })
+            .unwrap();
+    }
+
+    /// Removes and returns all the notifications deferred by quiet hours, the oldest first
+    pub fn take_deferred_notifications(&self) -> Vec<Notification> {
+        let deferred_bytes = self
+            .notifications
+            .remove(DEFERRED_NOTIFICATIONS_KEY)
+            .unwrap();
+        deserialize_deferred_notifications(deferred_bytes.as_deref())
+    }
+
+    /// Records a sent notification, removing the oldest ones beyond the maximum number of entries
+    pub fn add_notification_history_entry(&self, entry: &NotificationHistoryEntry) {
+        // Generated ids are increasing, keeping the entries in the order they were sent
+        let id = self.db.generate_id().unwrap();
+        self.notification_history
+            .insert(id.to_be_bytes(), bincode::serialize(entry).unwrap())
+            .unwrap();
+
+        while self.notification_history.len() > MAX_NOTIFICATION_HISTORY_ENTRIES {
+            self.notification_history.pop_min().unwrap();
+        }
+    }
+
+    /// Gets the sent notifications, the most recent first
+    pub fn get_notification_history(&self) -> Vec<NotificationHistoryEntry> {
+        self.notification_history
+            .iter()
+            .values()
+            .rev()
+            .map(|entry_bytes| bincode::deserialize(&entry_bytes.unwrap()).unwrap())
+            .collect()
+    }
+
+    /// Returns a subscriber that gets notified whenever a notification is recorded
+    pub fn watch_notification_history(&self) -> sled::Subscriber {
+        self.notification_history.watch_prefix(vec![])
+    }
+
+    /// Records the time the given episode was marked watched
+    pub fn set_episode_watch_time(
+        &self,
+        series_id: u32,
+        season_number: u32,
+        episode_number: Episode,
+        date_time: chrono::DateTime<chrono::Local>,
+    ) {
+        self.watch_times
+            .insert(
+                watch_times_key(series_id, season_number, episode_number),
+                bincode::serialize(&date_time.timestamp()).unwrap(),
+            )
+            .unwrap();
+    }
+
+    pub fn remove_episode_watch_time(
+        &self,
+        series_id: u32,
+        season_number: u32,
+        episode_number: Episode,
+    ) {
+        self.watch_times
+            .remove(watch_times_key(series_id, season_number, episode_number))
+            .unwrap();
+    }
+
+    /// Gets the times the episodes of the given series were marked watched by their season and
+    /// episode numbers
+    ///
+    /// # Note
+    /// Only the episodes marked watched since the times started being recorded have one.
+    pub fn get_episode_watch_times(
+        &self,
+        series_id: u32,
+    ) -> HashMap<(u32, Episode), chrono::DateTime<chrono::Local>> {
+        self.watch_times
+            .scan_prefix(watch_times_prefix(series_id))
+            .filter_map(|entry| {
+                let (key, timestamp_bytes) = entry.unwrap();
+                let key = String::from_utf8_lossy(&key).into_owned();
+                let mut numbers = key.split('/').skip(1);
+                let season_number = numbers.next()?.parse().ok()?;
+                let episode_number = numbers.next()?.parse().ok()?;
+
+                let timestamp = bincode::deserialize::<i64>(&timestamp_bytes).unwrap();
+                let date_time = chrono::DateTime::from_timestamp(timestamp, 0)?;
+
+                Some((
+                    (season_number, episode_number),
+                    date_time.with_timezone(&chrono::Local),
+                ))
+            })
+            .collect()
+    }
+
+    /// Gets the times all the episodes were marked watched
+    pub fn get_watch_times(&self) -> Vec<chrono::DateTime<chrono::Local>> {
+        self.watch_times
+            .iter()
+            .values()
+            .filter_map(|timestamp_bytes| {
+                let timestamp = bincode::deserialize::<i64>(&timestamp_bytes.unwrap()).unwrap();
+                chrono::DateTime::from_timestamp(timestamp, 0)
+            })
+            .map(|date_time| date_time.with_timezone(&chrono::Local))
+            .collect()
+    }
+
+    /// Adds a watching goal, returning it's id
+    pub fn add_goal(&self, goal: &Goal) -> u64 {
+        // Generated ids are increasing, keeping the goals in the order they were added
+        let id = self.db.generate_id().unwrap();
+        self.goals
+            .insert(id.to_be_bytes(), bincode::serialize(goal).unwrap())
+            .unwrap();
+        id
+    }
+
+    pub fn remove_goal(&self, id: u64) {
+        self.goals.remove(id.to_be_bytes()).unwrap();
+    }
+
+    /// Gets the watching goals with their ids, the oldest first
+    pub fn get_goals(&self) -> Vec<(u64, Goal)> {
+        self.goals
+            .iter()
+            .map(|entry| {
+                let (id_bytes, goal_bytes) = entry.unwrap();
+                let id = u64::from_be_bytes(id_bytes.as_ref().try_into().unwrap());
+                (id, bincode::deserialize(&goal_bytes).unwrap())
+            })
+            .collect()
+    }
+
+    /// Records that the given series has been made available offline
+    pub fn add_offline_series(&self, series_id: u32) {
+        self.offline_series
+            .insert(series_id.to_be_bytes(), vec![])
+            .unwrap();
+    }
+
+    /// Gets the ids of the series that have been made available offline
+    pub fn get_offline_series_ids(&self) -> Vec<u32> {
+        self.offline_series
+            .iter()
+            .keys()
+            .map(|id_bytes| u32::from_be_bytes(id_bytes.unwrap().as_ref().try_into().unwrap()))
+            .collect()
+    }
+
     pub fn export(&self) -> database_transfer::TransferData {
-        database_transfer::TransferData::new(self.get_series_collection())
+        database_transfer::TransferData::new(
+            self.get_series_collection(),
+            self.get_goals().into_iter().map(|(_, goal)| goal).collect(),
+            self.get_notification_rules(),
+        )
     }
 
     pub fn import(&self, transfer_data: &database_transfer::TransferData) {
         for series in transfer_data.get_series() {
             self.add_series(series.id, series);
         }
+
+        // Leaving out the goals that are already there so that importing twice does not repeat them
+        let existing_goals: Vec<Goal> =
+            self.get_goals().into_iter().map(|(_, goal)| goal).collect();
+        for goal in transfer_data.get_goals() {
+            if !existing_goals.contains(goal) {
+                self.add_goal(goal);
+            }
+        }
+
+        for (series_id, notification_rule) in transfer_data.get_notification_rules() {
+            self.set_notification_rule(*series_id, *notification_rule);
+        }
         self.db.flush().expect("flushing database");
     }
 }
 
+/// Deferred notifications stored by a version with a different format are dropped
+fn deserialize_deferred_notifications(deferred_bytes: Option<&[u8]>) -> Vec<Notification> {
+    deferred_bytes
+        .and_then(|deferred_bytes| {
+            bincode::deserialize(deferred_bytes)
+                .map_err(|err| warn!("failed to read deferred notifications: {}", err))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+fn watch_times_prefix(series_id: u32) -> String {
+    format!("{}/", series_id)
+}
+
+fn watch_times_key(series_id: u32, season_number: u32, episode_number: Episode) -> String {
+    format!("{}/{}/{}", series_id, season_number, episode_number)
+}
+
+/// Per series overrides of the notification settings
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NotificationRule {
+    /// No notifications at all for the series
+    pub muted: bool,
+    /// Minutes before an episode release to notify, overriding the ones in the settings
+    pub time_to_notify: Option<u32>,
+    /// Only notify for the first and last episodes of seasons
+    pub only_premieres_and_finales: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Series {
     id: u32,
@@ -201,7 +543,11 @@ impl Series {
     }
 
     pub fn remove_season(&mut self, season_number: u32) {
-        self.seasons.remove(&season_number);
+        if let Some(season) = self.seasons.remove(&season_number) {
+            for episode_number in season.episodes {
+                DB.remove_episode_watch_time(self.id, season_number, episode_number);
+            }
+        }
     }
 
     /// adds an episode into the series
@@ -257,7 +603,8 @@ impl Series {
     /// removes an episode from the series
     pub fn remove_episode(&mut self, season_number: u32, episode_number: Episode) {
         if let Some(season) = self.seasons.get_mut(&season_number) {
-            season.untrack_episode(episode_number)
+            season.untrack_episode(episode_number);
+            DB.remove_episode_watch_time(self.id, season_number, episode_number);
         }
     }
 
@@ -293,23 +640,93 @@ impl Series {
             .map(|(season_number, season)| (*season_number, season))
     }
 
+    /// Returns all the watched episodes as season and episode numbers, in order
+    pub fn get_watched_episodes(&self) -> Vec<(u32, Episode)> {
+        let mut watched_episodes: Vec<(u32, Episode)> = self
+            .seasons
+            .iter()
+            .flat_map(|(season_number, season)| {
+                season
+                    .episodes
+                    .iter()
+                    .map(|episode| (*season_number, *episode))
+            })
+            .collect();
+        watched_episodes.sort_unstable();
+        watched_episodes
+    }
+
     /// Get the total time that has been spent watching the series
     ///
+    /// Every watched episode counts with it's own runtime, the average runtime of the series
+    /// being used only for the episodes without one. The time is kept in the `MEMORY_CACHE`
+    /// until the watched episodes change or the series cache gets updated.
+    ///
     /// This method returns SeriesMainInformation associated with the Series
-    /// together with it's total runtime
-    pub async fn get_total_average_watchtime(&self) -> (SeriesMainInformation, Option<u32>) {
-        let series_info = caching::series_information::get_series_main_info_with_id(self.id)
-            .await
-            .unwrap();
-        let episode_average_watchtime = series_info.average_runtime;
+    /// together with it's total runtime, failing when the SeriesMainInformation can't be loaded
+    pub async fn get_total_watchtime(
+        &self,
+    ) -> Result<(SeriesMainInformation, Option<u32>), ApiError> {
+        let series_info =
+            caching::series_information::get_series_main_info_with_id(self.id).await?;
+
+        let watched_episodes = self.get_watched_episodes();
+        let watched_episodes_hash = {
+            let mut hasher = DefaultHasher::new();
+            watched_episodes.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        if let Some(watch_time) = MEMORY_CACHE.get_watch_time(self.id, watched_episodes_hash) {
+            return Ok((series_info, watch_time));
+        }
 
-        (
-            series_info,
-            episode_average_watchtime.map(|time| time * self.get_total_episodes() as u32),
-        )
+        let episode_list = match caching::episode_list::EpisodeList::new(self.id).await {
+            Ok(episode_list) => episode_list,
+            Err(err) => {
+                warn!(
+                    "failed to get the episode list of \"{}\", using the average runtime for it's watch time: {}",
+                    series_info.name, err
+                );
+                let watch_time = series_info
+                    .average_runtime
+                    .map(|time| time * watched_episodes.len() as u32);
+                return Ok((series_info, watch_time));
+            }
+        };
+
+        let watch_time = total_runtime(
+            watched_episodes
+                .iter()
+                .map(|(season_number, episode_number)| {
+                    episode_list
+                        .get_episode(*season_number, *episode_number)
+                        .and_then(|episode| episode.runtime)
+                }),
+            series_info.average_runtime,
+        );
+
+        MEMORY_CACHE.insert_watch_time(self.id, watched_episodes_hash, watch_time);
+
+        Ok((series_info, watch_time))
     }
 }
 
+/// Sums up the episode runtimes, using the average runtime for the episodes without one
+///
+/// Returns `None` when none of the episodes has a runtime to go by.
+fn total_runtime(
+    episode_runtimes: impl Iterator<Item = Option<u32>>,
+    average_runtime: Option<u32>,
+) -> Option<u32> {
+    episode_runtimes
+        .map(|runtime| runtime.or(average_runtime))
+        .fold(None, |total, runtime| match (total, runtime) {
+            (Some(total), Some(runtime)) => Some(total + runtime),
+            (total, runtime) => total.or(runtime),
+        })
+}
+
 impl Drop for Series {
     fn drop(&mut self) {
         // Making sure database series is updated
@@ -353,7 +770,16 @@ impl Season {
 
         if let Some(episode) = episode_list.get_episode(season_number, episode_number) {
             if let Ok(false) = episode.is_future_release() {
-                return self.episodes.insert(episode_number);
+                let is_newly_added = self.episodes.insert(episode_number);
+                if is_newly_added {
+                    DB.set_episode_watch_time(
+                        series_id,
+                        season_number,
+                        episode_number,
+                        chrono::Local::now(),
+                    );
+                }
+                return is_newly_added;
             }
         }
         false
@@ -425,6 +851,29 @@ impl Default for Season {
 
 pub type Episode = u32;
 
+/// A notification that has been sent
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationHistoryEntry {
+    /// When the notification was sent as a unix timestamp
+    pub timestamp: i64,
+    pub kind: String,
+    pub summary: String,
+    pub body: String,
+    pub series_id: Option<u32>,
+    pub series_name: Option<String>,
+    pub season: Option<u32>,
+    pub episode_number: Option<u32>,
+    /// The backends the notification was delivered to
+    pub backends: Vec<String>,
+}
+
+impl NotificationHistoryEntry {
+    pub fn sent_at(&self) -> Option<chrono::DateTime<chrono::Local>> {
+        chrono::DateTime::from_timestamp(self.timestamp, 0)
+            .map(|date_time| date_time.with_timezone(&chrono::Local))
+    }
+}
+
 /// Indicates if adding episodes has been fully added(when none of the episodes were present before adding) or
 /// partial(when some were already present) and none when all the added apisode where already present
 #[derive(Debug, Clone)]
@@ -440,10 +889,10 @@ pub enum AddResult {
 pub mod database_transfer {
     //! Implementations of importing and exporting series tracking data
 
-    use std::{io, path};
+    use std::{collections::BTreeMap, io, path};
 
-    use super::Series;
-    use super::DB;
+    use super::{NotificationRule, Series, DB};
+    use crate::core::goals::Goal;
 
     use ron::ser;
     use serde::{Deserialize, Serialize};
@@ -465,13 +914,26 @@ pub mod database_transfer {
     pub struct TransferData {
         version: u16,
         series: Vec<Series>,
+        /// Missing from the data exported before there were goals
+        #[serde(default)]
+        goals: Vec<Goal>,
+        /// The notification rules by the ids of their series, missing from the data exported
+        /// before there were notification rules
+        #[serde(default)]
+        notification_rules: BTreeMap<u32, NotificationRule>,
     }
 
     impl TransferData {
-        pub fn new(series: Vec<Series>) -> Self {
+        pub fn new(
+            series: Vec<Series>,
+            goals: Vec<Goal>,
+            notification_rules: BTreeMap<u32, NotificationRule>,
+        ) -> Self {
             Self {
                 version: CURRENT_DATA_VERSION,
                 series,
+                goals,
+                notification_rules,
             }
         }
 
@@ -518,6 +980,14 @@ pub mod database_transfer {
             &self.series
         }
 
+        pub fn get_goals(&self) -> &[Goal] {
+            &self.goals
+        }
+
+        pub fn get_notification_rules(&self) -> &BTreeMap<u32, NotificationRule> {
+            &self.notification_rules
+        }
+
         fn ron_str(&self) -> String {
             let pretty_config = ser::PrettyConfig::new().depth_limit(4);
             ser::to_string_pretty(self, pretty_config).expect("transfer data serialization")
@@ -541,4 +1011,90 @@ pub mod database_transfer {
             DB.export().async_export(path).await
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn notification_rules_transfer_test() {
+            let notification_rule = NotificationRule {
+                muted: false,
+                time_to_notify: Some(30),
+                only_premieres_and_finales: true,
+            };
+            // No series as they write themselves to the database when dropped
+            let transfer_data = TransferData::new(
+                vec![],
+                vec![Goal::EpisodesPerWeek(5)],
+                BTreeMap::from([(1, notification_rule)]),
+            );
+
+            let imported_data = ron::from_str::<TransferData>(&transfer_data.ron_str()).unwrap();
+            assert_eq!(imported_data.get_goals(), [Goal::EpisodesPerWeek(5)]);
+            assert_eq!(
+                imported_data.get_notification_rules(),
+                &BTreeMap::from([(1, notification_rule)])
+            );
+        }
+
+        #[test]
+        fn older_transfer_data_test() {
+            // Exported before there were goals and notification rules
+            let imported_data =
+                ron::from_str::<TransferData>(r#"(version: 1, series: [])"#).unwrap();
+
+            assert_eq!(imported_data.version, CURRENT_DATA_VERSION);
+            assert!(imported_data.get_goals().is_empty());
+            assert!(imported_data.get_notification_rules().is_empty());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_series_test() {
+        let database_dir = tempfile::tempdir().unwrap();
+        let database = Database::open(database_dir.path());
+
+        let notification_rule = NotificationRule {
+            muted: true,
+            ..Default::default()
+        };
+        database.set_notification_rule(1, notification_rule);
+        database.set_notification_rule(2, notification_rule);
+        database.add_offline_series(1);
+
+        database.remove_series(1);
+
+        // Nothing of the removed series is left behind to be exported
+        assert_eq!(
+            database.get_notification_rule(1),
+            NotificationRule::default()
+        );
+        assert_eq!(
+            database
+                .get_notification_rules()
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert!(database.get_offline_series_ids().is_empty());
+    }
+
+    #[test]
+    fn total_runtime_test() {
+        let runtimes = [Some(45), None, Some(60)];
+
+        // The average runtime stands in for the episodes without one
+        assert_eq!(total_runtime(runtimes.into_iter(), Some(50)), Some(155));
+        assert_eq!(total_runtime(runtimes.into_iter(), None), Some(105));
+
+        assert_eq!(total_runtime([None, None].into_iter(), Some(50)), Some(100));
+        assert_eq!(total_runtime([None, None].into_iter(), None), None);
+        assert_eq!(total_runtime(std::iter::empty(), Some(50)), None);
+    }
 }
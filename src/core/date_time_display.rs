@@ -0,0 +1,112 @@
+//! # Displaying dates and times
+//!
+//! Release times are worked with in the system time zone, this module is only concerned with
+//! how they are displayed, converting them to the time zone and formats of the locale settings.
+
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, TimeZone, Timelike};
+
+use crate::core::settings_config::{ClockFormat, DateFormat, LocaleSettings, SETTINGS};
+
+#[derive(Clone, Copy, Debug)]
+pub struct DateTimeDisplay {
+    time_zone: Option<chrono_tz::Tz>,
+    clock_format: ClockFormat,
+    date_format: DateFormat,
+}
+
+impl DateTimeDisplay {
+    pub fn new(locale_settings: &LocaleSettings) -> Self {
+        Self {
+            time_zone: locale_settings.time_zone,
+            clock_format: locale_settings.clock_format,
+            date_format: locale_settings.date_format,
+        }
+    }
+
+    /// Uses the current locale settings
+    pub fn from_settings() -> Self {
+        Self::new(&SETTINGS.read().unwrap().get_current_settings().locale)
+    }
+
+    /// Converts the date time to the display time zone
+    pub fn zoned<Tz: TimeZone>(&self, date_time: &DateTime<Tz>) -> DateTime<FixedOffset> {
+        match self.time_zone {
+            Some(time_zone) => date_time.with_timezone(&time_zone).fixed_offset(),
+            None => date_time.with_timezone(&Local).fixed_offset(),
+        }
+    }
+
+    /// The current date in the display time zone
+    pub fn today(&self) -> NaiveDate {
+        self.zoned(&Local::now()).date_naive()
+    }
+
+    /// The date, weekday and time e.g. `2024-01-31 Wed 8:05 p.m.`
+    pub fn date_time<Tz: TimeZone>(&self, date_time: &DateTime<Tz>) -> String {
+        let date_time = self.zoned(date_time);
+        format!(
+            "{} {} {}",
+            self.naive_date(date_time.date_naive()),
+            date_time.weekday(),
+            self.clock_time(&date_time)
+        )
+    }
+
+    pub fn date<Tz: TimeZone>(&self, date_time: &DateTime<Tz>) -> String {
+        self.naive_date(self.zoned(date_time).date_naive())
+    }
+
+    pub fn time<Tz: TimeZone>(&self, date_time: &DateTime<Tz>) -> String {
+        self.clock_time(&self.zoned(date_time))
+    }
+
+    /// Formats a date that has no time zone, like the premiere dates from TVmaze
+    pub fn naive_date(&self, date: NaiveDate) -> String {
+        date.format(self.date_format.format_str()).to_string()
+    }
+
+    /// Formats a `YYYY-MM-DD` date string from TVmaze, giving it back as it is when it can't be parsed
+    pub fn date_str(&self, date_str: &str) -> String {
+        NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+            .map(|date| self.naive_date(date))
+            .unwrap_or_else(|_| date_str.to_owned())
+    }
+
+    fn clock_time(&self, date_time: &DateTime<FixedOffset>) -> String {
+        match self.clock_format {
+            ClockFormat::TwentyFourHour => date_time.format("%H:%M").to_string(),
+            ClockFormat::TwelveHour => {
+                let (is_pm, hour) = date_time.hour12();
+                let pm_am = if is_pm { "p.m." } else { "a.m." };
+                format!("{}:{:02} {}", hour, date_time.minute(), pm_am)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn date_time_test() {
+        let release_time = Utc.with_ymd_and_hms(2024, 1, 31, 1, 5, 0).unwrap();
+
+        let display = DateTimeDisplay::new(&LocaleSettings {
+            time_zone: Some(chrono_tz::America::New_York),
+            ..Default::default()
+        });
+        assert_eq!(display.date_time(&release_time), "2024-01-30 Tue 8:05 p.m.");
+
+        let display = DateTimeDisplay::new(&LocaleSettings {
+            time_zone: Some(chrono_tz::Europe::Berlin),
+            clock_format: ClockFormat::TwentyFourHour,
+            date_format: DateFormat::DayMonthYear,
+            ..Default::default()
+        });
+        assert_eq!(display.date_time(&release_time), "31/01/2024 Wed 02:05");
+        assert_eq!(display.date_str("2013-06-24"), "24/06/2013");
+        assert_eq!(display.date_str("2013"), "2013");
+    }
+}
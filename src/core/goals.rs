@@ -0,0 +1,361 @@
+//! # Watching goals and streaks
+//!
+//! Goals are stored in the database and their progress worked out from what has been watched.
+//! Weekly goals and streaks only go by the recorded watch times, episodes marked watched before
+//! they started being recorded have no day to count towards.
+
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::core::caching::episode_list::EpisodeList;
+use crate::core::database;
+use crate::core::date_time_display::DateTimeDisplay;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Goal {
+    /// Watching at least the given number of episodes every week
+    EpisodesPerWeek(u32),
+    /// Watching all the episodes of a series by the deadline
+    FinishSeries {
+        series_id: u32,
+        series_name: String,
+        deadline: NaiveDate,
+    },
+}
+
+impl Goal {
+    pub fn description(&self, date_time_display: &DateTimeDisplay) -> String {
+        match self {
+            Goal::EpisodesPerWeek(episodes) => format!("Watch {} episodes per week", episodes),
+            Goal::FinishSeries {
+                series_name,
+                deadline,
+                ..
+            } => format!(
+                "Finish {} by {}",
+                series_name,
+                date_time_display.naive_date(*deadline)
+            ),
+        }
+    }
+}
+
+/// The most episodes a week an `EpisodesPerWeek` goal can be set to
+pub const MAX_EPISODES_PER_WEEK: u32 = 1000;
+
+pub const ALL_GOAL_KINDS: [GoalKind; 2] = [GoalKind::EpisodesPerWeek, GoalKind::FinishSeries];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalKind {
+    EpisodesPerWeek,
+    FinishSeries,
+}
+
+impl std::fmt::Display for GoalKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            GoalKind::EpisodesPerWeek => "Episodes per week",
+            GoalKind::FinishSeries => "Finish a series",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalStatus {
+    Achieved,
+    OnTrack,
+    /// Less has been watched this week than the weekly goal needs by now
+    Behind,
+    /// The deadline has passed
+    Missed,
+}
+
+impl std::fmt::Display for GoalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            GoalStatus::Achieved => "Achieved",
+            GoalStatus::OnTrack => "On track",
+            GoalStatus::Behind => "Behind",
+            GoalStatus::Missed => "Missed",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    /// The id of the goal in the database
+    pub id: u64,
+    pub goal: Goal,
+    /// The episodes watched towards the goal
+    pub done: usize,
+    /// The episodes the goal needs
+    pub target: usize,
+    /// The days left to reach the goal, today included
+    pub days_left: i64,
+    pub status: GoalStatus,
+}
+
+impl GoalProgress {
+    /// # Arguments
+    /// * `watch_dates` - the days every episode was watched on
+    /// * `watched_episodes` and `watchable_episodes` - of the series when it's a `FinishSeries` goal,
+    ///   the number of watchable episodes is `None` when it's not known
+    pub fn new(
+        id: u64,
+        goal: Goal,
+        watch_dates: &[NaiveDate],
+        watched_episodes: usize,
+        watchable_episodes: Option<usize>,
+        today: NaiveDate,
+    ) -> Self {
+        let (done, target, days_left, status) = match &goal {
+            Goal::EpisodesPerWeek(episodes) => {
+                let days_into_week = today.weekday().num_days_from_monday();
+                let week_start = today - Duration::days(days_into_week as i64);
+                let done = watch_dates
+                    .iter()
+                    .filter(|date| (week_start..=today).contains(date))
+                    .count();
+                let target = *episodes as usize;
+
+                // The episodes that should have been watched by the start of today at a steady pace
+                let expected = target.saturating_mul(days_into_week as usize) / 7;
+
+                let status = if done >= target {
+                    GoalStatus::Achieved
+                } else if done >= expected {
+                    GoalStatus::OnTrack
+                } else {
+                    GoalStatus::Behind
+                };
+
+                (done, target, 7 - days_into_week as i64, status)
+            }
+            Goal::FinishSeries { deadline, .. } => {
+                let target = watchable_episodes.unwrap_or(watched_episodes);
+                let days_left = ((*deadline - today).num_days() + 1).max(0);
+
+                let status = if watchable_episodes.is_some() && watched_episodes >= target {
+                    GoalStatus::Achieved
+                } else if today > *deadline {
+                    GoalStatus::Missed
+                } else {
+                    GoalStatus::OnTrack
+                };
+
+                (watched_episodes, target, days_left, status)
+            }
+        };
+
+        Self {
+            id,
+            goal,
+            done,
+            target,
+            days_left,
+            status,
+        }
+    }
+
+    pub fn completion(&self) -> f32 {
+        if self.target == 0 {
+            1.0
+        } else {
+            (self.done as f32 / self.target as f32).min(1.0)
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.target.saturating_sub(self.done)
+    }
+}
+
+/// Consecutive days and weeks with at least an episode watched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Streaks {
+    /// The current streak is kept until a whole day or week goes by without watching
+    pub current_days: usize,
+    pub longest_days: usize,
+    pub current_weeks: usize,
+    pub longest_weeks: usize,
+}
+
+impl Streaks {
+    pub fn new(watch_dates: &[NaiveDate], today: NaiveDate) -> Self {
+        let days: BTreeSet<NaiveDate> = watch_dates.iter().copied().collect();
+        let weeks: BTreeSet<NaiveDate> = days.iter().map(|date| week_start(*date)).collect();
+
+        let (current_days, longest_days) = streaks(&days, today, Duration::days(1));
+        let (current_weeks, longest_weeks) = streaks(&weeks, week_start(today), Duration::weeks(1));
+
+        Self {
+            current_days,
+            longest_days,
+            current_weeks,
+            longest_weeks,
+        }
+    }
+}
+
+/// Gives the current and the longest runs of the given dates being a step apart
+///
+/// The current run is the one ending at the given current date or a step before it.
+fn streaks(dates: &BTreeSet<NaiveDate>, current: NaiveDate, step: Duration) -> (usize, usize) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous_date: Option<NaiveDate> = None;
+
+    for date in dates {
+        run = match previous_date {
+            Some(previous_date) if *date - previous_date == step => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous_date = Some(*date);
+    }
+
+    let current_run = match previous_date {
+        Some(last_date) if last_date == current || last_date == current - step => run,
+        _ => 0,
+    };
+
+    (current_run, longest)
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GoalsOverview {
+    pub goals: Vec<GoalProgress>,
+    pub streaks: Streaks,
+}
+
+/// Gets the progress of all the goals in the database together with the watching streaks
+///
+/// Days start at midnight of the display time zone.
+pub async fn get_goals_overview() -> GoalsOverview {
+    let date_time_display = DateTimeDisplay::from_settings();
+    let today = date_time_display.today();
+    let watch_dates: Vec<NaiveDate> = database::DB
+        .get_watch_times()
+        .into_iter()
+        .map(|watch_time| date_time_display.zoned(&watch_time).date_naive())
+        .collect();
+
+    let mut goals = vec![];
+    for (id, goal) in database::DB.get_goals() {
+        let (watched_episodes, watchable_episodes) = match &goal {
+            Goal::EpisodesPerWeek(_) => (0, None),
+            Goal::FinishSeries { series_id, .. } => (
+                database::DB
+                    .get_series(*series_id)
+                    .map(|series| series.get_total_episodes())
+                    .unwrap_or(0),
+                EpisodeList::new(*series_id)
+                    .await
+                    .ok()
+                    .map(|episode_list| episode_list.get_total_watchable_episodes()),
+            ),
+        };
+
+        goals.push(GoalProgress::new(
+            id,
+            goal,
+            &watch_dates,
+            watched_episodes,
+            watchable_episodes,
+            today,
+        ));
+    }
+
+    GoalsOverview {
+        goals,
+        streaks: Streaks::new(&watch_dates, today),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn goal_progress_test() {
+        // A Thursday, three days into the week
+        let today = date(5, 16);
+        let watch_dates = [date(5, 10), date(5, 13), date(5, 15)];
+
+        let progress = GoalProgress::new(1, Goal::EpisodesPerWeek(7), &watch_dates, 0, None, today);
+        assert_eq!(progress.done, 2);
+        assert_eq!(progress.days_left, 4);
+        assert_eq!(progress.status, GoalStatus::Behind);
+
+        let progress = GoalProgress::new(1, Goal::EpisodesPerWeek(4), &watch_dates, 0, None, today);
+        assert_eq!(progress.status, GoalStatus::OnTrack);
+
+        // Goals too big for the expected pace to fit in a u32 must not overflow
+        let progress = GoalProgress::new(
+            1,
+            Goal::EpisodesPerWeek(u32::MAX),
+            &watch_dates,
+            0,
+            None,
+            today,
+        );
+        assert_eq!(progress.status, GoalStatus::Behind);
+
+        let finish_series = |deadline| Goal::FinishSeries {
+            series_id: 1,
+            series_name: "Series".to_owned(),
+            deadline,
+        };
+
+        let progress = GoalProgress::new(1, finish_series(date(5, 20)), &[], 8, Some(10), today);
+        assert_eq!(progress.remaining(), 2);
+        assert_eq!(progress.days_left, 5);
+        assert_eq!(progress.status, GoalStatus::OnTrack);
+
+        let progress = GoalProgress::new(1, finish_series(date(5, 1)), &[], 8, Some(10), today);
+        assert_eq!(progress.status, GoalStatus::Missed);
+
+        let progress = GoalProgress::new(1, finish_series(date(5, 1)), &[], 10, Some(10), today);
+        assert_eq!(progress.status, GoalStatus::Achieved);
+    }
+
+    #[test]
+    fn streaks_test() {
+        let watch_dates = [
+            date(4, 1),
+            date(4, 2),
+            date(4, 3),
+            date(4, 3),
+            date(5, 14),
+            date(5, 15),
+        ];
+
+        let streaks = Streaks::new(&watch_dates, date(5, 16));
+        assert_eq!(streaks.current_days, 2);
+        assert_eq!(streaks.longest_days, 3);
+        assert_eq!(streaks.current_weeks, 1);
+        assert_eq!(streaks.longest_weeks, 1);
+
+        let streaks = Streaks::new(&watch_dates, date(5, 17));
+        assert_eq!(streaks.current_days, 0);
+
+        let streaks = Streaks::new(&[date(5, 1), date(5, 8), date(5, 15)], date(5, 21));
+        assert_eq!(streaks.current_weeks, 3);
+        assert_eq!(streaks.longest_weeks, 3);
+        assert_eq!(Streaks::new(&[], date(5, 21)), Streaks::default());
+    }
+}
@@ -1,8 +1,14 @@
 pub mod api;
 pub mod caching;
+pub mod calendar;
 pub mod cli;
 pub mod database;
+pub mod date_time_display;
+pub mod goals;
 pub mod notifications;
+pub mod offline;
 pub mod paths;
 pub mod posters_hiding;
 pub mod settings_config;
+pub mod statistics;
+pub mod year_in_review;
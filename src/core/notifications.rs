@@ -1,132 +1,439 @@
 use super::{
     api::tv_maze::{episodes_information::Episode, series_information::SeriesMainInformation},
-    caching::series_list,
-    paths, settings_config,
+    caching::{
+        cache_updating, episode_list::EpisodeList, seasons_list, series_changes::SeriesChange,
+        series_list,
+    },
+    database::{NotificationHistoryEntry, NotificationRule, DB},
+    date_time_display::DateTimeDisplay,
+    paths,
+    settings_config::{self, NotificationSettings},
 };
 use anyhow::Context;
-use chrono::Duration;
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+
+use super::api::tv_maze::episodes_information::EpisodeReleaseTime;
 use notify::{recommended_watcher, EventHandler, Watcher};
-use std::sync::mpsc;
-use tokio::task::JoinHandle;
+use scheduler::{Reminder, ScheduledKey, Scheduler};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+pub mod scheduler;
+pub mod sinks;
+
+/// How often the last notification check is recorded while running, it's also recorded whenever
+/// a notification is sent
+///
+/// Releases are considered missed when they happened after the last recorded check, so this is
+/// how late a release can be and still not be mentioned by the catch-up notification.
+const LAST_CHECK_RECORD_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// How long the scheduled notifications go without being refreshed, picking up newly tracked series
+const SCHEDULE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// The longest the scheduler waits before checking the due notifications against the clock again
+const MAX_SCHEDULER_SLEEP: std::time::Duration = std::time::Duration::from_secs(60);
 
 enum Signal {
     SettingsFileChanged,
-    NotificationSent,
+    NotificationRulesChanged,
+    CacheUpdated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationKind {
+    EpisodeRelease,
+    MissedReleases,
+    DailyDigest,
+    SeriesChange,
+    /// Notifications deferred by quiet hours sent as one
+    Deferred,
+}
+
+impl std::fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind_str = match self {
+            NotificationKind::EpisodeRelease => "episode-release",
+            NotificationKind::MissedReleases => "missed-releases",
+            NotificationKind::DailyDigest => "daily-digest",
+            NotificationKind::SeriesChange => "series-change",
+            NotificationKind::Deferred => "deferred",
+        };
+        write!(f, "{}", kind_str)
+    }
+}
+
+/// A notification as it's delivered to the notification sinks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub summary: String,
+    pub body: String,
+    /// The series the notification is about, if it's about a single series
+    pub series: Option<SeriesDetails>,
+    /// The episode the notification is about, if it's about a single episode
+    pub episode: Option<EpisodeDetails>,
+}
+
+impl Notification {
+    pub fn new(kind: NotificationKind, (summary, body): (String, String)) -> Self {
+        Self {
+            kind,
+            summary,
+            body,
+            series: None,
+            episode: None,
+        }
+    }
+
+    pub fn with_series(mut self, series_info: &SeriesMainInformation) -> Self {
+        self.series = Some(SeriesDetails {
+            id: series_info.id,
+            name: series_info.name.clone(),
+        });
+        self
+    }
+
+    pub fn with_episode(
+        self,
+        series_info: &SeriesMainInformation,
+        episode: &Episode,
+        minutes_to_release: u32,
+    ) -> Self {
+        let mut notification = self.with_series(series_info);
+        notification.episode = Some(EpisodeDetails {
+            season: episode.season,
+            number: episode.number,
+            name: episode.name.clone(),
+            release_time: episode.local_date_time().ok(),
+            minutes_to_release,
+        });
+        notification
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeriesDetails {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpisodeDetails {
+    pub season: u32,
+    pub number: Option<u32>,
+    pub name: String,
+    pub release_time: Option<DateTime<Local>>,
+    pub minutes_to_release: u32,
+}
+
+/// Sends the notification to all the notification sinks of the current settings and records it
+/// in the notification history
+///
+/// Notifications sent during quiet hours are deferred until they are over, they are kept in the
+/// database so that they are still sent when the program is restarted in the meantime.
+pub async fn send_notification(notification: Notification) {
+    let notification_settings = get_current_notification_settings();
+
+    if is_quiet_now(&notification_settings) {
+        tracing::info!(
+            "deferring notification \"{}\" until the end of quiet hours",
+            notification.summary
+        );
+        DB.add_deferred_notification(&notification);
+        return;
+    }
+
+    deliver_notifications(&notification_settings, vec![notification]).await;
+}
+
+/// Sends all the notifications deferred by quiet hours
+async fn send_deferred_notifications() {
+    let deferred_notifications = DB.take_deferred_notifications();
+
+    if !deferred_notifications.is_empty() {
+        tracing::info!(
+            "sending {} notifications deferred by quiet hours",
+            deferred_notifications.len()
+        );
+        deliver_notifications(&get_current_notification_settings(), deferred_notifications).await;
+    }
+}
+
+/// Delivers the notifications to the notification sinks, several notifications being batched into one
+///
+/// Every notification is recorded in the notification history on it's own.
+async fn deliver_notifications(
+    notification_settings: &NotificationSettings,
+    notifications: Vec<Notification>,
+) {
+    let notification_sinks = sinks::get_sinks(notification_settings);
+
+    let delivered_sinks = if let [notification] = notifications.as_slice() {
+        sinks::send_to_sinks(&notification_sinks, notification).await
+    } else {
+        let batched_notification = Notification::new(
+            NotificationKind::Deferred,
+            notify_setup::notify_deferred_setup(&notifications),
+        );
+        sinks::send_to_sinks(&notification_sinks, &batched_notification).await
+    };
+
+    // The releases notified so far must not be reported as missed by the next catch-up
+    DB.set_last_notification_check(Local::now());
+
+    for notification in notifications {
+        DB.add_notification_history_entry(&NotificationHistoryEntry {
+            timestamp: Local::now().timestamp(),
+            kind: notification.kind.to_string(),
+            summary: notification.summary,
+            body: notification.body,
+            series_id: notification.series.as_ref().map(|series| series.id),
+            series_name: notification.series.map(|series| series.name),
+            season: notification.episode.as_ref().map(|episode| episode.season),
+            episode_number: notification.episode.and_then(|episode| episode.number),
+            backends: delivered_sinks.clone(),
+        });
+    }
+}
+
+/// What is sent once a scheduled notification is due
+enum ScheduledNotification {
+    EpisodeRelease {
+        series_info: Arc<SeriesMainInformation>,
+        episode: Box<Episode>,
+        time_to_notify: u32,
+    },
+    DailyDigest,
+    QuietHoursEnd,
 }
 
 pub struct TroxideNotify {
-    signal_receiver: mpsc::Receiver<Signal>,
-    signal_sender: mpsc::Sender<Signal>,
+    signal_receiver: mpsc::UnboundedReceiver<Signal>,
+    signal_sender: mpsc::UnboundedSender<Signal>,
 }
 
 impl TroxideNotify {
     pub fn new() -> anyhow::Result<Self> {
-        let (signal_sender, signal_receiver) = mpsc::channel();
+        let (signal_sender, signal_receiver) = mpsc::unbounded_channel();
 
         let file_change_signal_sender = signal_sender.clone();
         std::thread::spawn(move || Self::file_change_watcher(file_change_signal_sender));
 
+        let notification_rules_signal_sender = signal_sender.clone();
+        std::thread::spawn(move || {
+            Self::notification_rules_watcher(notification_rules_signal_sender)
+        });
+
         Ok(Self {
             signal_receiver,
             signal_sender,
         })
     }
 
-    pub fn run(&self) -> anyhow::Result<()> {
+    pub fn run(mut self) -> anyhow::Result<()> {
         tokio::runtime::Runtime::new()?.block_on(async {
-            let mut current_notification_time_setting = get_current_notification_time_setting();
+            notify_missed_releases().await;
+            tokio::spawn(Self::last_check_recorder());
+            tokio::spawn(Self::cache_updates_watcher(self.signal_sender.clone()));
+
+            let mut current_notification_settings = get_current_notification_settings();
+
+            let mut scheduler = Scheduler::default();
+            Self::refresh_schedule(&mut scheduler, &current_notification_settings).await;
+            let mut last_refresh = Instant::now();
 
             loop {
-                // This is the time before the actual release of an episode that should be used by the notification
-                // to send notifications before the actual release of an episode.
-                let duration_before_release =
-                    Duration::minutes(current_notification_time_setting as i64);
-
-                // Creating a handle for each episode release notification so that we can be able to abort them at anytime
-                // we want.
-                let notification_handles: Vec<_> = get_releases_with_duration_to_release()
-                    .await
-                    .into_iter()
-                    .map(|(series_info, episode, duration)| {
-                        (series_info, episode, duration - duration_before_release)
-                    })
-                    .filter(|(_, _, duration)| duration.to_std().is_ok())
-                    .map(|(series_info, episode, duration)| {
-                        let signal_sender = self.signal_sender.clone();
-                        tokio::spawn(async move {
-                            tracing::info!(
-                                "waiting {} minutes for \"{}'s\" notification",
-                                duration.num_minutes(),
-                                series_info.name,
-                            );
-
-                            tokio::time::sleep(duration.to_std().unwrap()).await;
-
-                            // For some reasons, async version of notify-rust = "4.9.0" does not work on macos
-                            // and windows so we use the sync version here and async for the linux
-                            #[cfg(not(target_os = "linux"))]
-                            {
-                                platform_notify::not_linux::notify_episode_release(
-                                    &series_info,
-                                    &episode,
-                                    current_notification_time_setting,
-                                )
-                                .await;
-                            }
+                // Waiting in steps of at most `MAX_SCHEDULER_SLEEP` as the timer does not advance while
+                // the system is suspended, unlike the wall clock the notifications are due by
+                let sleep_duration = scheduler
+                    .next_due()
+                    .map(|due| (due - Local::now()).to_std().unwrap_or_default())
+                    .unwrap_or(MAX_SCHEDULER_SLEEP)
+                    .min(MAX_SCHEDULER_SLEEP);
+
+                tokio::select! {
+                    _ = tokio::time::sleep(sleep_duration) => {
+                        for (_, scheduled_notification) in scheduler.pop_due(Local::now()) {
+                            tokio::spawn(Self::send_scheduled_notification(scheduled_notification));
+                        }
+                        Self::schedule_recurring_notifications(
+                            &mut scheduler,
+                            &current_notification_settings,
+                        );
 
-                            #[cfg(target_os = "linux")]
-                            {
-                                platform_notify::linux::notify_episode_release(
-                                    &series_info,
-                                    &episode,
-                                    current_notification_time_setting,
-                                )
+                        if last_refresh.elapsed() >= SCHEDULE_REFRESH_INTERVAL {
+                            Self::refresh_schedule(&mut scheduler, &current_notification_settings)
                                 .await;
-                            }
-                            signal_sender.send(Signal::NotificationSent).unwrap();
-                        })
-                    })
-                    .collect();
-
-                match &self.signal_receiver.recv().unwrap() {
-                    Signal::SettingsFileChanged => {
-                        /*
-                        Since the settings file can change the time to notify before the actual release, our notifications will
-                        be waiting to notify with a delay that is no longer correct, so be obtain the current settings from the
-                        settings file and abort all the upcoming notifications and reobtain all of them in the next loop iteration
-                        TODO: Make it detect only when the nofification settings changed
-                        */
-                        tracing::info!("config file change detected, refreshing notifications");
-                        current_notification_time_setting = get_current_notification_time_setting();
-
-                        Self::abort_notifications(notification_handles);
+                            last_refresh = Instant::now();
+                        }
                     }
-                    Signal::NotificationSent => {
-                        /*
-                        When a new episode has been notified, when can't keep on using the same obtained episode releases as it might
-                        turn out that that series is being released regularly(weekly) and thus the currently obtained releases won't
-                        have that information. So we just abort all the handles to reobtain all the releases information in the next
-                        iteration of the loop.
-                        */
-                        tracing::info!(
-                            "episode release notification sent, refreshing notifications"
-                        );
+                    Some(signal) = self.signal_receiver.recv() => {
+                        match signal {
+                            Signal::SettingsFileChanged => {
+                                let notification_settings = get_current_notification_settings();
 
-                        Self::abort_notifications(notification_handles);
+                                // The rest of the settings have no effect on the notifications
+                                if notification_settings == current_notification_settings {
+                                    continue;
+                                }
+
+                                tracing::info!(
+                                    "notification settings change detected, refreshing notifications"
+                                );
+                                current_notification_settings = notification_settings;
+                            }
+                            Signal::NotificationRulesChanged => tracing::info!(
+                                "notification rules change detected, refreshing notifications"
+                            ),
+                            Signal::CacheUpdated => tracing::info!(
+                                "series cache update detected, refreshing notifications"
+                            ),
+                        }
+
+                        Self::refresh_schedule(&mut scheduler, &current_notification_settings).await;
+                        last_refresh = Instant::now();
                     }
                 }
             }
-        });
-        Ok(())
+        })
+    }
+
+    /// Diffs the scheduled notifications against freshly obtained releases and the given settings
+    async fn refresh_schedule(
+        scheduler: &mut Scheduler<ScheduledNotification>,
+        notification_settings: &NotificationSettings,
+    ) {
+        let daily_digest = &notification_settings.daily_digest;
+        let times_to_notify = if daily_digest.enabled && daily_digest.replaces_reminders {
+            vec![]
+        } else {
+            notification_settings.times_to_notify.clone()
+        };
+
+        let reminders_change = scheduler.set_reminders(get_reminders(&times_to_notify).await);
+        tracing::info!(
+            "{} notifications scheduled, {} reminders added, {} rescheduled and {} removed",
+            scheduler.len(),
+            reminders_change.added,
+            reminders_change.rescheduled,
+            reminders_change.removed
+        );
+
+        Self::schedule_recurring_notifications(scheduler, notification_settings);
+
+        // Quiet hours could have been turned off or changed to end already while some notifications were
+        // deferred, including by a previous run of the program
+        if !is_quiet_now(notification_settings) {
+            tokio::spawn(send_deferred_notifications());
+        }
+    }
+
+    /// Schedules the next daily digest and end of quiet hours, unscheduling those turned off
+    ///
+    /// Both their times are set in the display time zone.
+    fn schedule_recurring_notifications(
+        scheduler: &mut Scheduler<ScheduledNotification>,
+        notification_settings: &NotificationSettings,
+    ) {
+        let now = DateTimeDisplay::from_settings().zoned(&Local::now());
+
+        let daily_digest = &notification_settings.daily_digest;
+        if daily_digest.enabled {
+            scheduler.schedule(
+                ScheduledKey::DailyDigest,
+                get_next_time_of_day(daily_digest.time, now),
+                ScheduledNotification::DailyDigest,
+            );
+        } else {
+            scheduler.unschedule(&ScheduledKey::DailyDigest);
+        }
+
+        let quiet_hours = &notification_settings.quiet_hours;
+        if quiet_hours.enabled {
+            scheduler.schedule(
+                ScheduledKey::QuietHoursEnd,
+                get_next_time_of_day(quiet_hours.end, now),
+                ScheduledNotification::QuietHoursEnd,
+            );
+        } else {
+            scheduler.unschedule(&ScheduledKey::QuietHoursEnd);
+        }
+    }
+
+    async fn send_scheduled_notification(scheduled_notification: ScheduledNotification) {
+        match scheduled_notification {
+            ScheduledNotification::EpisodeRelease {
+                series_info,
+                episode,
+                time_to_notify,
+            } => {
+                send_notification(
+                    Notification::new(
+                        NotificationKind::EpisodeRelease,
+                        notify_setup::notify_episode_release_setup(
+                            &series_info,
+                            &episode,
+                            time_to_notify,
+                        ),
+                    )
+                    .with_episode(&series_info, &episode, time_to_notify),
+                )
+                .await
+            }
+            ScheduledNotification::DailyDigest => Self::send_daily_digest().await,
+            ScheduledNotification::QuietHoursEnd => send_deferred_notifications().await,
+        }
+    }
+
+    async fn send_daily_digest() {
+        let (todays_releases, unwatched_episodes) = get_daily_digest().await;
+
+        if todays_releases.is_empty() && unwatched_episodes == 0 {
+            tracing::info!("nothing to report in the daily digest");
+        } else {
+            send_notification(Notification::new(
+                NotificationKind::DailyDigest,
+                notify_setup::notify_daily_digest_setup(&todays_releases, unwatched_episodes),
+            ))
+            .await;
+        }
+    }
+
+    async fn last_check_recorder() {
+        let mut interval = tokio::time::interval(LAST_CHECK_RECORD_INTERVAL);
+        loop {
+            interval.tick().await;
+            DB.set_last_notification_check(Local::now());
+        }
+    }
+
+    async fn cache_updates_watcher(signal_sender: mpsc::UnboundedSender<Signal>) {
+        let mut cache_updates = cache_updating::watch_cache_updates();
+
+        while cache_updates.changed().await.is_ok() {
+            if signal_sender.send(Signal::CacheUpdated).is_err() {
+                break;
+            }
+        }
     }
 
-    fn abort_notifications(notification_handles: Vec<JoinHandle<()>>) {
-        notification_handles
-            .into_iter()
-            .for_each(|handle| handle.abort())
+    fn notification_rules_watcher(signal_sender: mpsc::UnboundedSender<Signal>) {
+        for _event in DB.watch_notification_rules() {
+            if signal_sender
+                .send(Signal::NotificationRulesChanged)
+                .is_err()
+            {
+                break;
+            }
+        }
     }
 
-    fn file_change_watcher(signal_sender: mpsc::Sender<Signal>) {
+    fn file_change_watcher(signal_sender: mpsc::UnboundedSender<Signal>) {
         let file_watcher_event_handler = FileWatcherEventHandler::new(signal_sender);
         let mut settings_file_watcher = recommended_watcher(file_watcher_event_handler)
             .context("failed to create settings file watcher")
@@ -149,37 +456,333 @@ impl TroxideNotify {
     }
 }
 
-async fn get_releases_with_duration_to_release() -> Vec<(SeriesMainInformation, Episode, Duration)>
-{
-    series_list::SeriesList::new()
+/// Retrieves the reminders of the future releases of the running tracked series according to their
+/// notification rules
+///
+/// A release has a reminder for each of the minutes before it that it's notified at, reminders
+/// already due are left out.
+async fn get_reminders(
+    default_times_to_notify: &[u32],
+) -> Vec<(Reminder, DateTime<Local>, ScheduledNotification)> {
+    let series_infos = match series_list::SeriesList::new()
+        .get_running_tracked_series_information()
+        .await
+    {
+        Ok(series_infos) => series_infos,
+        Err(err) => {
+            tracing::error!("failed to get running tracked series: {}", err);
+            return vec![];
+        }
+    };
+
+    let now = Local::now();
+    let mut reminders = vec![];
+
+    for series_info in series_infos {
+        let notification_rule = DB.get_notification_rule(series_info.id);
+        if notification_rule.muted {
+            continue;
+        }
+
+        let episode_list = match EpisodeList::new(series_info.id).await {
+            Ok(episode_list) => episode_list,
+            Err(err) => {
+                tracing::error!(
+                    "failed to get the episode list of \"{}\" for reminders: {}",
+                    series_info.name,
+                    err
+                );
+                continue;
+            }
+        };
+
+        // These are the times before the actual release of an episode that should be used by the notification
+        // to send notifications before the actual release of an episode.
+        let times_to_notify = match notification_rule.time_to_notify {
+            Some(time_to_notify) => vec![time_to_notify],
+            None => default_times_to_notify.to_vec(),
+        };
+
+        let seasons = get_seasons_for_rule(series_info.id, &notification_rule).await;
+
+        let series_info = Arc::new(series_info);
+
+        for episode in episode_list.get_all_episodes() {
+            let (Some(episode_number), Ok(release_time)) =
+                (episode.number, episode.local_date_time())
+            else {
+                continue;
+            };
+
+            if release_time <= now
+                || (notification_rule.only_premieres_and_finales
+                    && episode_number != 1
+                    && !seasons_list::is_season_finale(&seasons, episode))
+            {
+                continue;
+            }
+
+            for time_to_notify in times_to_notify.iter().copied() {
+                let due = release_time - Duration::minutes(time_to_notify as i64);
+
+                if due > now {
+                    reminders.push((
+                        Reminder {
+                            series_id: series_info.id,
+                            season: episode.season,
+                            episode_number: Some(episode_number),
+                            time_to_notify,
+                        },
+                        due,
+                        ScheduledNotification::EpisodeRelease {
+                            series_info: series_info.clone(),
+                            episode: Box::new(episode.clone()),
+                            time_to_notify,
+                        },
+                    ));
+                }
+            }
+        }
+    }
+
+    reminders
+}
+
+/// Notifies the change of the tracked series unless it's muted or change notifications are turned off
+pub async fn notify_series_change(
+    series_info: &SeriesMainInformation,
+    series_change: &SeriesChange,
+) {
+    if !get_current_notification_settings().notify_series_changes
+        || DB.get_notification_rule(series_info.id).muted
+    {
+        return;
+    }
+
+    send_notification(
+        Notification::new(
+            NotificationKind::SeriesChange,
+            notify_setup::notify_series_change_setup(series_info, series_change),
+        )
+        .with_series(series_info),
+    )
+    .await;
+}
+
+/// Whether the current time, in the display time zone, is within the quiet hours
+fn is_quiet_now(notification_settings: &NotificationSettings) -> bool {
+    let now = DateTimeDisplay::from_settings().zoned(&Local::now());
+    notification_settings.quiet_hours.is_quiet(now.time())
+}
+
+/// Gets the next time the clock of the time zone of `now` hits the given time of the day
+fn get_next_time_of_day<Tz: TimeZone>(time: NaiveTime, now: DateTime<Tz>) -> DateTime<Local> {
+    let mut next_time = now.date_naive().and_time(time);
+    if next_time <= now.naive_local() {
+        next_time += Duration::days(1);
+    }
+
+    // A time skipped by a daylight saving transition is kept as far away from now
+    next_time
+        .and_local_timezone(now.timezone())
+        .earliest()
+        .unwrap_or_else(|| now.clone() + (next_time - now.naive_local()))
+        .with_timezone(&Local)
+}
+
+/// Retrieves the releases of today that are yet to be released together with the total number of
+/// the released episodes of the tracked series that are yet to be watched
+///
+/// Today is the one of the display time zone, and releases of series muted by their notification
+/// rules are left out.
+async fn get_daily_digest() -> (
+    Vec<(SeriesMainInformation, Episode, EpisodeReleaseTime)>,
+    usize,
+) {
+    let date_time_display = DateTimeDisplay::from_settings();
+    let today = date_time_display.today();
+
+    let todays_releases = series_list::SeriesList::new()
         .get_upcoming_release_series_information_and_episodes()
         .await
-        .context("failed to get upcoming series releases")
-        .unwrap()
+        .unwrap_or_else(|err| {
+            tracing::error!("failed to get upcoming series releases: {}", err);
+            vec![]
+        })
         .into_iter()
-        .map(|(series_info, next_episode, release_time)| {
-            (
-                series_info,
-                next_episode,
-                release_time.get_remaining_release_duration(),
-            )
+        .filter(|(_, _, release_time)| {
+            date_time_display
+                .zoned(&release_time.local_date_time())
+                .date_naive()
+                == today
+        })
+        .filter(|(series_info, _, _)| !DB.get_notification_rule(series_info.id).muted)
+        .collect();
+
+    let mut unwatched_episodes = 0;
+    for series in DB
+        .get_series_collection()
+        .into_iter()
+        .filter(|series| series.is_tracked())
+    {
+        match EpisodeList::new(series.id()).await {
+            Ok(episode_list) => {
+                unwatched_episodes += episode_list
+                    .get_total_watchable_episodes()
+                    .saturating_sub(series.get_total_episodes())
+            }
+            Err(err) => tracing::error!(
+                "failed to get the episode list of \"{}\" for the daily digest: {}",
+                series.get_name(),
+                err
+            ),
+        }
+    }
+
+    (todays_releases, unwatched_episodes)
+}
+
+/// Sends a single notification summarizing the releases of the tracked series that happened
+/// since the last notification check, i.e. while the program was not running
+async fn notify_missed_releases() {
+    let now = Local::now();
+
+    // Nothing could have been missed before the notifications were ever checked
+    if let Some(last_check) = DB.get_last_notification_check() {
+        let missed_releases = get_missed_releases(last_check, now).await;
+
+        if !missed_releases.is_empty() {
+            let notification = Notification::new(
+                NotificationKind::MissedReleases,
+                notify_setup::notify_missed_releases_setup(&missed_releases),
+            );
+
+            tracing::info!("sending catch-up notification: {}", notification.summary);
+
+            send_notification(notification).await;
+        }
+    }
+
+    DB.set_last_notification_check(now);
+}
+
+/// Retrieves the names of the tracked series released in the given period together with how many
+/// of their episodes were released
+///
+/// Series muted by their notification rules and episodes already watched are left out.
+async fn get_missed_releases(
+    since: DateTime<Local>,
+    until: DateTime<Local>,
+) -> Vec<(String, usize)> {
+    let mut missed_releases = vec![];
+
+    let tracked_series = DB
+        .get_series_collection()
+        .into_iter()
+        .filter(|series| series.is_tracked());
+
+    for series in tracked_series {
+        let notification_rule = DB.get_notification_rule(series.id());
+        if notification_rule.muted {
+            continue;
+        }
+
+        let episode_list = match EpisodeList::new(series.id()).await {
+            Ok(episode_list) => episode_list,
+            Err(err) => {
+                tracing::error!(
+                    "failed to get the episode list of \"{}\" for missed releases: {}",
+                    series.get_name(),
+                    err
+                );
+                continue;
+            }
+        };
+
+        let seasons = get_seasons_for_rule(series.id(), &notification_rule).await;
+
+        let missed_episodes = count_missed_episodes(
+            episode_list.get_all_episodes(),
+            &series.get_watched_episodes().into_iter().collect(),
+            &notification_rule,
+            &seasons,
+            (since, until),
+        );
+
+        if missed_episodes > 0 {
+            missed_releases.push((series.get_name().to_owned(), missed_episodes));
+        }
+    }
+
+    missed_releases
+}
+
+/// Counts the episodes released in the given period that are not watched yet, leaving out the
+/// ones the notification rule does not notify for
+fn count_missed_episodes(
+    episodes: &[Episode],
+    watched_episodes: &HashSet<(u32, u32)>,
+    notification_rule: &NotificationRule,
+    seasons: &[seasons_list::Season],
+    (since, until): (DateTime<Local>, DateTime<Local>),
+) -> usize {
+    episodes
+        .iter()
+        .filter(|episode| {
+            episode
+                .local_date_time()
+                .map(|release_time| release_time > since && release_time <= until)
+                .unwrap_or(false)
+        })
+        .filter(|episode| {
+            episode.number.is_none_or(|episode_number| {
+                !watched_episodes.contains(&(episode.season, episode_number))
+            })
+        })
+        .filter(|episode| {
+            !notification_rule.only_premieres_and_finales
+                || episode.number == Some(1)
+                || seasons_list::is_season_finale(seasons, episode)
+        })
+        .count()
+}
+
+/// Retrieves the seasons of the series when the notification rule needs to know the season finales
+///
+/// Without them, finales are left out as they can't be told apart.
+async fn get_seasons_for_rule(
+    series_id: u32,
+    notification_rule: &NotificationRule,
+) -> Vec<seasons_list::Season> {
+    if !notification_rule.only_premieres_and_finales {
+        return vec![];
+    }
+
+    seasons_list::get_seasons_list(series_id)
+        .await
+        .unwrap_or_else(|err| {
+            tracing::error!(
+                "failed to get the seasons of series with id '{}': {}",
+                series_id,
+                err
+            );
+            vec![]
         })
-        .collect()
 }
 
 struct FileWatcherEventHandler {
-    sender: mpsc::Sender<Signal>,
+    sender: mpsc::UnboundedSender<Signal>,
 }
 
-fn get_current_notification_time_setting() -> u32 {
+fn get_current_notification_settings() -> NotificationSettings {
     settings_config::Settings::new()
         .get_current_settings()
         .notifications
-        .time_to_notify
+        .clone()
 }
 
 impl FileWatcherEventHandler {
-    fn new(sender: mpsc::Sender<Signal>) -> Self {
+    fn new(sender: mpsc::UnboundedSender<Signal>) -> Self {
         Self { sender }
     }
 }
@@ -200,8 +803,11 @@ impl EventHandler for FileWatcherEventHandler {
 mod notify_setup {
     //! Reusable useful functions for `platform_notify` module
 
-    use crate::core::api::tv_maze::episodes_information::Episode;
+    use crate::core::api::tv_maze::episodes_information::{Episode, EpisodeReleaseTime};
     use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+    use crate::core::caching::series_changes::SeriesChange;
+    use crate::core::date_time_display::DateTimeDisplay;
+    use crate::core::notifications::Notification;
 
     pub fn notification_setup(
         notification: &mut notify_rust::Notification,
@@ -233,40 +839,148 @@ mod notify_setup {
         let notification_summary = format!("\"{}\" episode release", series_name);
 
         let notification_body = format!(
-            "{}: {}, will be released in {} minutes",
-            episode_order, episode_name, release_time_in_minute
+            "{}: {}, will be released in {}",
+            episode_order,
+            episode_name,
+            crate::gui::helpers::time::NaiveTime::new(release_time_in_minute)
+                .to_string()
+                .trim_end()
         );
 
         (notification_summary, notification_body)
     }
 
-    pub fn log_notification_error(
-        notification_result: Result<(), notify_rust::error::Error>,
-        notification_summary: &str,
-    ) {
-        if let Err(err) = notification_result {
-            tracing::error!(
-                "failed to show notification for \"{}\": {}",
-                notification_summary,
-                err
-            );
+    /// Builds the catch-up notification from the names of the series with missed releases
+    /// and how many of their episodes were released
+    pub fn notify_missed_releases_setup(missed_releases: &[(String, usize)]) -> (String, String) {
+        let total_episodes: usize = missed_releases
+            .iter()
+            .map(|(_, missed_episodes)| missed_episodes)
+            .sum();
+        let total_series = missed_releases.len();
+
+        let notification_summary = format!(
+            "{} new {} of {} {} aired since you were last here",
+            total_episodes,
+            if total_episodes == 1 {
+                "episode"
+            } else {
+                "episodes"
+            },
+            total_series,
+            if total_series == 1 { "show" } else { "shows" },
+        );
+
+        let notification_body = missed_releases
+            .iter()
+            .map(|(series_name, missed_episodes)| format!("{}: {}", series_name, missed_episodes))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        (notification_summary, notification_body)
+    }
+
+    /// Builds the daily digest notification from the releases of today and the number of
+    /// unwatched episodes
+    pub fn notify_daily_digest_setup(
+        todays_releases: &[(SeriesMainInformation, Episode, EpisodeReleaseTime)],
+        unwatched_episodes: usize,
+    ) -> (String, String) {
+        let notification_summary = match todays_releases.len() {
+            0 => "Daily digest: no releases today".to_owned(),
+            1 => "Daily digest: 1 release today".to_owned(),
+            total_releases => format!("Daily digest: {} releases today", total_releases),
+        };
+
+        let mut notification_body: Vec<String> = todays_releases
+            .iter()
+            .map(|(series_info, episode, release_time)| {
+                let episode_order = episode
+                    .number
+                    .map(|number| {
+                        crate::gui::helpers::season_episode_str_gen(episode.season, number)
+                    })
+                    .unwrap_or_else(|| format!("Season {}", episode.season));
+
+                format!(
+                    "{} {} at {}",
+                    series_info.name,
+                    episode_order,
+                    DateTimeDisplay::from_settings().time(&release_time.local_date_time())
+                )
+            })
+            .collect();
+
+        notification_body.push(match unwatched_episodes {
+            1 => "1 unwatched episode".to_owned(),
+            unwatched_episodes => format!("{} unwatched episodes", unwatched_episodes),
+        });
+
+        (notification_summary, notification_body.join("\n"))
+    }
+
+    /// Builds a single notification out of the notifications deferred by quiet hours
+    pub fn notify_deferred_setup(deferred_notifications: &[Notification]) -> (String, String) {
+        let notification_summary = format!(
+            "{} notifications during quiet hours",
+            deferred_notifications.len()
+        );
+
+        let notification_body = deferred_notifications
+            .iter()
+            .map(|notification| notification.summary.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        (notification_summary, notification_body)
+    }
+
+    pub fn notify_series_change_setup(
+        series_info: &SeriesMainInformation,
+        series_change: &SeriesChange,
+    ) -> (String, String) {
+        let series_name = series_info.name.as_str();
+
+        match series_change {
+            SeriesChange::Ended => (
+                format!("\"{}\" has ended", series_name),
+                "No more episodes will be released".to_owned(),
+            ),
+            SeriesChange::NewSeason { season, premiere } => (
+                format!("\"{}\" new season", series_name),
+                match premiere {
+                    Some(premiere) => format!(
+                        "Season {} has been announced, premiering on {}",
+                        season,
+                        DateTimeDisplay::from_settings().date(premiere)
+                    ),
+                    None => format!("Season {} has been announced", season),
+                },
+            ),
+            SeriesChange::PremiereScheduled { season, premiere } => (
+                format!("\"{}\" premiere date", series_name),
+                format!(
+                    "Season {} premieres on {}",
+                    season,
+                    DateTimeDisplay::from_settings().date(premiere)
+                ),
+            ),
         }
     }
 }
 
 pub mod platform_notify {
     //! For some reasons, async version of notify-rust = "4.9.0" does not work on macos
-    //! and windows so we handle 'notify' and 'notify_episode_release' functions separately
-    //! for linux and other oses
+    //! and windows so we handle the 'notify' function separately for linux and other oses
 
     #[cfg(target_os = "linux")]
     pub mod linux {
-        //! 'notify' and 'notification_episode_release' implementations for linux
-
-        use crate::core::api::tv_maze::episodes_information::Episode;
-        use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+        //! 'notify' implementation for linux
 
-        pub async fn notify(notification_summary: &str, notification_body: &str) {
+        pub async fn notify(
+            notification_summary: &str,
+            notification_body: &str,
+        ) -> notify_rust::error::Result<()> {
             let mut notification = notify_rust::Notification::new();
 
             super::super::notify_setup::notification_setup(
@@ -275,38 +989,18 @@ pub mod platform_notify {
                 notification_body,
             );
 
-            let res = notification.show_async().await;
-
-            super::super::notify_setup::log_notification_error(
-                res.map(|_| ()),
-                notification_summary,
-            );
-        }
-
-        pub async fn notify_episode_release(
-            series_info: &SeriesMainInformation,
-            episode: &Episode,
-            release_time_in_minute: u32,
-        ) {
-            let (notification_summary, notification_body) =
-                super::super::notify_setup::notify_episode_release_setup(
-                    series_info,
-                    episode,
-                    release_time_in_minute,
-                );
-
-            notify(&notification_summary, &notification_body).await;
+            notification.show_async().await.map(|_| ())
         }
     }
 
     #[cfg(not(target_os = "linux"))]
     pub mod not_linux {
-        //! 'notify' and 'notification_episode_release' implementations for linux
-
-        use crate::core::api::tv_maze::episodes_information::Episode;
-        use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+        //! 'notify' implementation for other oses
 
-        pub async fn notify(notification_summary: &str, notification_body: &str) {
+        pub async fn notify(
+            notification_summary: &str,
+            notification_body: &str,
+        ) -> notify_rust::error::Result<()> {
             let mut notification = notify_rust::Notification::new();
 
             super::super::notify_setup::notification_setup(
@@ -317,27 +1011,98 @@ pub mod platform_notify {
 
             let handle = tokio::task::spawn_blocking(move || notification.show());
 
-            let res = handle.await.expect("failed to await notification handle");
-
-            super::super::notify_setup::log_notification_error(
-                res.map(|_| ()),
-                notification_summary,
-            );
+            handle
+                .await
+                .expect("failed to await notification handle")
+                .map(|_| ())
         }
+    }
+}
 
-        pub async fn notify_episode_release(
-            series_info: &SeriesMainInformation,
-            episode: &Episode,
-            release_time_in_minute: u32,
-        ) {
-            let (notification_summary, notification_body) =
-                super::super::notify_setup::notify_episode_release_setup(
-                    series_info,
-                    episode,
-                    release_time_in_minute,
-                );
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::api::tv_maze::test_fixtures;
 
-            notify(&notification_summary, &notification_body).await;
-        }
+    fn episode(season: u32, number: u32, airstamp: &str) -> Episode {
+        let mut episode = test_fixtures::episode(season, Some(number));
+        episode.airstamp = Some(airstamp.to_owned());
+        episode
+    }
+
+    fn date_time(date_time: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(date_time)
+            .unwrap()
+            .with_timezone(&Local)
+    }
+
+    #[test]
+    fn next_time_of_day_test() {
+        let time = NaiveTime::from_hms_opt(7, 0, 0).unwrap();
+        let now = DateTime::parse_from_rfc3339("2024-05-01T06:00:00+02:00").unwrap();
+
+        // Going by the clock of the time zone of now, regardless of the system time zone
+        assert_eq!(
+            get_next_time_of_day(time, now),
+            date_time("2024-05-01T07:00:00+02:00")
+        );
+        assert_eq!(
+            get_next_time_of_day(time, now.with_timezone(&chrono_tz::Asia::Tokyo)),
+            date_time("2024-05-02T07:00:00+09:00")
+        );
+    }
+
+    #[test]
+    fn count_missed_episodes_test() {
+        let episodes = [
+            episode(1, 1, "2024-05-01T20:00:00+00:00"),
+            episode(1, 2, "2024-05-08T20:00:00+00:00"),
+            episode(1, 3, "2024-05-15T20:00:00+00:00"),
+            episode(1, 4, "2024-05-22T20:00:00+00:00"),
+        ];
+        let period = (
+            date_time("2024-05-01T20:00:00+00:00"),
+            date_time("2024-05-15T20:00:00+00:00"),
+        );
+        let notification_rule = NotificationRule::default();
+
+        // Only the releases after the last check up to now
+        assert_eq!(
+            count_missed_episodes(&episodes, &HashSet::new(), &notification_rule, &[], period),
+            2
+        );
+
+        // Episodes watched in the meantime, i.e. from another device, are not missed
+        assert_eq!(
+            count_missed_episodes(
+                &episodes,
+                &HashSet::from([(1, 2)]),
+                &notification_rule,
+                &[],
+                period
+            ),
+            1
+        );
+
+        let notification_rule = NotificationRule {
+            only_premieres_and_finales: true,
+            ..Default::default()
+        };
+        let seasons = [seasons_list::Season {
+            number: 1,
+            episode_order: Some(3),
+            premiere_date: None,
+            end_date: None,
+        }];
+        assert_eq!(
+            count_missed_episodes(
+                &episodes,
+                &HashSet::new(),
+                &notification_rule,
+                &seasons,
+                period
+            ),
+            1
+        );
     }
 }
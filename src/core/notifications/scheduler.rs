@@ -0,0 +1,236 @@
+//! # Notification scheduling
+//!
+//! Pending notifications are kept in a priority queue ordered by when they are due, so that only
+//! the earliest one has to be waited for. Reminders of episode releases are keyed by their series
+//! and episode, which lets freshly obtained releases be diffed against the pending ones, keeping
+//! the reminders that did not change and rescheduling only those whose release time did.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use chrono::{DateTime, Local};
+
+/// Identifies a single reminder of an episode release as an episode can have several of them
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Reminder {
+    pub series_id: u32,
+    pub season: u32,
+    pub episode_number: Option<u32>,
+    /// The minutes before the release the reminder is sent at
+    pub time_to_notify: u32,
+}
+
+/// Identifies a pending notification
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ScheduledKey {
+    Reminder(Reminder),
+    DailyDigest,
+    QuietHoursEnd,
+}
+
+/// How the scheduled reminders changed after being replaced
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RemindersChange {
+    pub added: usize,
+    pub rescheduled: usize,
+    pub removed: usize,
+}
+
+/// A priority queue of pending notifications carrying a payload of type `T`
+pub struct Scheduler<T> {
+    /// The pending notifications ordered by when they are due
+    queue: BTreeSet<(DateTime<Local>, ScheduledKey)>,
+    pending: HashMap<ScheduledKey, (DateTime<Local>, T)>,
+}
+
+impl<T> Scheduler<T> {
+    /// Schedules the notification, replacing the pending one with the same key
+    ///
+    /// Returns the time the replaced notification was due at.
+    pub fn schedule(
+        &mut self,
+        key: ScheduledKey,
+        due: DateTime<Local>,
+        payload: T,
+    ) -> Option<DateTime<Local>> {
+        let previous_due = self
+            .pending
+            .insert(key.clone(), (due, payload))
+            .map(|(previous_due, _)| previous_due);
+
+        if let Some(previous_due) = previous_due {
+            self.queue.remove(&(previous_due, key.clone()));
+        }
+        self.queue.insert((due, key));
+
+        previous_due
+    }
+
+    pub fn unschedule(&mut self, key: &ScheduledKey) -> Option<T> {
+        let (due, payload) = self.pending.remove(key)?;
+        self.queue.remove(&(due, key.clone()));
+        Some(payload)
+    }
+
+    /// The time the earliest pending notification is due at
+    pub fn next_due(&self) -> Option<DateTime<Local>> {
+        self.queue.first().map(|(due, _)| *due)
+    }
+
+    /// Removes and returns the notifications due at or before the given time, earliest first
+    pub fn pop_due(&mut self, now: DateTime<Local>) -> Vec<(ScheduledKey, T)> {
+        let mut due_notifications = vec![];
+
+        while self.next_due().is_some_and(|due| due <= now) {
+            let (_, key) = self.queue.pop_first().expect("queue should not be empty");
+            let (_, payload) = self
+                .pending
+                .remove(&key)
+                .expect("queued notification should be pending");
+            due_notifications.push((key, payload));
+        }
+
+        due_notifications
+    }
+
+    /// Replaces the scheduled reminders with the given ones
+    ///
+    /// Reminders that are due at the same time as before are left untouched.
+    pub fn set_reminders(
+        &mut self,
+        reminders: Vec<(Reminder, DateTime<Local>, T)>,
+    ) -> RemindersChange {
+        let mut change = RemindersChange::default();
+
+        let new_keys: HashSet<ScheduledKey> = reminders
+            .iter()
+            .map(|(reminder, _, _)| ScheduledKey::Reminder(reminder.clone()))
+            .collect();
+
+        let removed_keys: Vec<ScheduledKey> = self
+            .pending
+            .keys()
+            .filter(|key| matches!(key, ScheduledKey::Reminder(_)) && !new_keys.contains(key))
+            .cloned()
+            .collect();
+
+        for key in removed_keys {
+            self.unschedule(&key);
+            change.removed += 1;
+        }
+
+        for (reminder, due, payload) in reminders {
+            let key = ScheduledKey::Reminder(reminder);
+            match self.schedule(key, due, payload) {
+                None => change.added += 1,
+                Some(previous_due) if previous_due != due => change.rescheduled += 1,
+                Some(_) => {}
+            }
+        }
+
+        change
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl<T> Default for Scheduler<T> {
+    fn default() -> Self {
+        Self {
+            queue: BTreeSet::new(),
+            pending: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Local};
+
+    use super::*;
+
+    fn reminder(series_id: u32, episode_number: u32) -> Reminder {
+        Reminder {
+            series_id,
+            season: 1,
+            episode_number: Some(episode_number),
+            time_to_notify: 60,
+        }
+    }
+
+    #[test]
+    fn pop_due_test() {
+        let now = Local::now();
+        let mut scheduler = Scheduler::default();
+
+        scheduler.schedule(
+            ScheduledKey::DailyDigest,
+            now + Duration::hours(2),
+            "digest",
+        );
+        scheduler.schedule(
+            ScheduledKey::Reminder(reminder(1, 1)),
+            now - Duration::minutes(1),
+            "first",
+        );
+        scheduler.schedule(
+            ScheduledKey::Reminder(reminder(1, 2)),
+            now - Duration::minutes(5),
+            "second",
+        );
+
+        let due: Vec<_> = scheduler
+            .pop_due(now)
+            .into_iter()
+            .map(|(_, payload)| payload)
+            .collect();
+        assert_eq!(due, vec!["second", "first"]);
+        assert_eq!(scheduler.next_due(), Some(now + Duration::hours(2)));
+        assert_eq!(scheduler.len(), 1);
+    }
+
+    #[test]
+    fn set_reminders_test() {
+        let now = Local::now();
+        let mut scheduler = Scheduler::default();
+
+        scheduler.schedule(ScheduledKey::QuietHoursEnd, now + Duration::hours(8), ());
+        scheduler.set_reminders(vec![
+            (reminder(1, 1), now + Duration::hours(1), ()),
+            (reminder(2, 1), now + Duration::hours(2), ()),
+        ]);
+
+        let change = scheduler.set_reminders(vec![
+            (reminder(1, 1), now + Duration::hours(1), ()),
+            (reminder(2, 1), now + Duration::hours(3), ()),
+            (reminder(3, 1), now + Duration::hours(4), ()),
+        ]);
+        assert_eq!(
+            change,
+            RemindersChange {
+                added: 1,
+                rescheduled: 1,
+                removed: 0
+            }
+        );
+
+        let change = scheduler.set_reminders(vec![(reminder(3, 1), now + Duration::hours(4), ())]);
+        assert_eq!(
+            change,
+            RemindersChange {
+                added: 0,
+                rescheduled: 0,
+                removed: 2
+            }
+        );
+
+        // Reminders being replaced leaves the rest of the notifications alone
+        assert_eq!(scheduler.len(), 2);
+        assert_eq!(scheduler.next_due(), Some(now + Duration::hours(4)));
+    }
+}
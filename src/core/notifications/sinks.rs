@@ -0,0 +1,589 @@
+//! # Notification sinks
+//!
+//! A notification is delivered to every sink set in the notification settings. The desktop sink
+//! shows it through notify-rust while the sinks of the `[[notifications.backends]]` configured in
+//! the config file forward it elsewhere, so that headless machines and phones receive it too.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use anyhow::{bail, Context};
+use base64::Engine;
+use tracing::error;
+
+use super::Notification;
+use crate::core::settings_config::{NotificationBackend, NotificationSettings};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How long the command of the command sink can run before it's killed
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The prefix of the environment variables the command sink passes the notification with
+const ENV_VAR_PREFIX: &str = "SERIES_TROXIDE_";
+
+pub type SendFuture<'a> = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send + 'a>>;
+
+pub trait NotificationSink: Send + Sync {
+    /// The name identifying the sink when reporting failures
+    fn name(&self) -> &str;
+
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a>;
+}
+
+/// Creates the sinks for the given notification settings
+pub fn get_sinks(notification_settings: &NotificationSettings) -> Vec<Box<dyn NotificationSink>> {
+    let mut sinks: Vec<Box<dyn NotificationSink>> = vec![];
+
+    if notification_settings.desktop_notifications {
+        sinks.push(Box::new(DesktopSink));
+    }
+
+    sinks.extend(notification_settings.backends.iter().map(get_backend_sink));
+
+    sinks
+}
+
+fn get_backend_sink(backend: &NotificationBackend) -> Box<dyn NotificationSink> {
+    match backend {
+        NotificationBackend::Webhook { url } => Box::new(WebhookSink::new(url.clone())),
+        NotificationBackend::Ntfy { url, topic, token } => {
+            Box::new(NtfySink::new(url.clone(), topic.clone(), token.clone()))
+        }
+        NotificationBackend::Gotify {
+            url,
+            token,
+            priority,
+        } => Box::new(GotifySink::new(url.clone(), token.clone(), *priority)),
+        NotificationBackend::Command { command } => Box::new(CommandSink::new(command.clone())),
+    }
+}
+
+/// Sends the notification to all the given sinks at the same time, failures are logged without
+/// stopping the rest
+///
+/// Returns the names of the sinks the notification was delivered to.
+pub async fn send_to_sinks(
+    sinks: &[Box<dyn NotificationSink>],
+    notification: &Notification,
+) -> Vec<String> {
+    let results = futures::future::join_all(sinks.iter().map(|sink| sink.send(notification))).await;
+
+    let mut delivered_sinks = vec![];
+    for (sink, result) in sinks.iter().zip(results) {
+        match result {
+            Ok(_) => delivered_sinks.push(sink.name().to_owned()),
+            Err(err) => error!(
+                "failed to send notification \"{}\" to {}: {:#}",
+                notification.summary,
+                sink.name(),
+                err
+            ),
+        }
+    }
+
+    delivered_sinks
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("failed to build http client")
+}
+
+/// Makes the value fit in a http header, values that are not plain ascii are sent as a RFC 2047
+/// encoded word which is what ntfy decodes
+fn encode_header_value(value: &str) -> String {
+    // A header value can't span several lines
+    let value = value.replace(['\r', '\n'], " ");
+
+    if value
+        .chars()
+        .all(|char| char.is_ascii() && !char.is_ascii_control())
+    {
+        value
+    } else {
+        format!(
+            "=?UTF-8?B?{}?=",
+            base64::engine::general_purpose::STANDARD.encode(value)
+        )
+    }
+}
+
+fn check_response(response: reqwest::Response) -> anyhow::Result<()> {
+    let status = response.status();
+    if !status.is_success() {
+        bail!("server responded with status {}", status);
+    }
+    Ok(())
+}
+
+pub struct DesktopSink;
+
+impl NotificationSink for DesktopSink {
+    fn name(&self) -> &str {
+        "desktop"
+    }
+
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a> {
+        Box::pin(async move {
+            // For some reasons, async version of notify-rust = "4.9.0" does not work on macos
+            // and windows so we use the sync version here and async for the linux
+            #[cfg(not(target_os = "linux"))]
+            let result = super::platform_notify::not_linux::notify(
+                &notification.summary,
+                &notification.body,
+            )
+            .await;
+
+            #[cfg(target_os = "linux")]
+            let result =
+                super::platform_notify::linux::notify(&notification.summary, &notification.body)
+                    .await;
+
+            result.context("failed to show desktop notification")
+        })
+    }
+}
+
+/// Posts the notification as a json payload
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: http_client(),
+            url,
+        }
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post(&self.url)
+                .json(notification)
+                .send()
+                .await
+                .context("failed to post to webhook")?;
+
+            check_response(response)
+        })
+    }
+}
+
+/// Publishes the notification to a ntfy topic, the summary being the title of the message
+pub struct NtfySink {
+    client: reqwest::Client,
+    url: String,
+    topic: String,
+    token: Option<String>,
+}
+
+impl NtfySink {
+    pub fn new(url: String, topic: String, token: Option<String>) -> Self {
+        Self {
+            client: http_client(),
+            url,
+            topic,
+            token,
+        }
+    }
+}
+
+impl NotificationSink for NtfySink {
+    fn name(&self) -> &str {
+        "ntfy"
+    }
+
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a> {
+        Box::pin(async move {
+            let url = format!("{}/{}", self.url.trim_end_matches('/'), self.topic);
+
+            let mut request = self
+                .client
+                .post(url)
+                .header("Title", encode_header_value(&notification.summary))
+                .body(notification.body.clone());
+
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+
+            let response = request.send().await.context("failed to publish to ntfy")?;
+
+            check_response(response)
+        })
+    }
+}
+
+/// Posts the notification as a gotify message
+pub struct GotifySink {
+    client: reqwest::Client,
+    url: String,
+    token: String,
+    priority: u8,
+}
+
+impl GotifySink {
+    pub fn new(url: String, token: String, priority: u8) -> Self {
+        Self {
+            client: http_client(),
+            url,
+            token,
+            priority,
+        }
+    }
+}
+
+impl NotificationSink for GotifySink {
+    fn name(&self) -> &str {
+        "gotify"
+    }
+
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a> {
+        Box::pin(async move {
+            let url = format!("{}/message", self.url.trim_end_matches('/'));
+
+            let response = self
+                .client
+                .post(url)
+                .header("X-Gotify-Key", &self.token)
+                .json(&serde_json::json!({
+                    "title": notification.summary,
+                    "message": notification.body,
+                    "priority": self.priority,
+                }))
+                .send()
+                .await
+                .context("failed to post to gotify")?;
+
+            check_response(response)
+        })
+    }
+}
+
+/// Runs a shell command with the notification details in environment variables
+///
+/// The command is killed when it runs for longer than `COMMAND_TIMEOUT`.
+pub struct CommandSink {
+    command: String,
+    timeout: Duration,
+}
+
+impl CommandSink {
+    pub fn new(command: String) -> Self {
+        Self {
+            command,
+            timeout: COMMAND_TIMEOUT,
+        }
+    }
+
+    #[cfg(test)]
+    fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The environment variables describing the notification, without the prefix
+    fn get_env_vars(notification: &Notification) -> Vec<(&'static str, String)> {
+        let mut env_vars = vec![
+            ("KIND", notification.kind.to_string()),
+            ("SUMMARY", notification.summary.clone()),
+            ("BODY", notification.body.clone()),
+        ];
+
+        if let Some(series) = &notification.series {
+            env_vars.push(("SERIES_ID", series.id.to_string()));
+            env_vars.push(("SERIES_NAME", series.name.clone()));
+        }
+
+        if let Some(episode) = &notification.episode {
+            env_vars.push(("SEASON", episode.season.to_string()));
+            if let Some(number) = episode.number {
+                env_vars.push(("EPISODE", number.to_string()));
+            }
+            env_vars.push(("EPISODE_NAME", episode.name.clone()));
+            if let Some(release_time) = &episode.release_time {
+                env_vars.push(("RELEASE_TIME", release_time.to_rfc3339()));
+            }
+            env_vars.push(("MINUTES_TO_RELEASE", episode.minutes_to_release.to_string()));
+        }
+
+        env_vars
+    }
+}
+
+impl NotificationSink for CommandSink {
+    fn name(&self) -> &str {
+        "command"
+    }
+
+    fn send<'a>(&'a self, notification: &'a Notification) -> SendFuture<'a> {
+        Box::pin(async move {
+            #[cfg(not(target_os = "windows"))]
+            let mut command = {
+                let mut command = tokio::process::Command::new("sh");
+                command.arg("-c").arg(&self.command);
+                command
+            };
+
+            #[cfg(target_os = "windows")]
+            let mut command = {
+                let mut command = tokio::process::Command::new("cmd");
+                command.arg("/C").arg(&self.command);
+                command
+            };
+
+            for (name, value) in Self::get_env_vars(notification) {
+                command.env(format!("{}{}", ENV_VAR_PREFIX, name), value);
+            }
+
+            // Dropping the command when it times out kills it
+            let status = tokio::time::timeout(self.timeout, command.kill_on_drop(true).status())
+                .await
+                .with_context(|| format!("'{}' timed out after {:?}", self.command, self.timeout))?
+                .with_context(|| format!("failed to run '{}'", self.command))?;
+
+            if !status.success() {
+                bail!("'{}' exited with {}", self.command, status);
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::core::notifications::{EpisodeDetails, NotificationKind, SeriesDetails};
+
+    fn test_notification() -> Notification {
+        Notification {
+            kind: NotificationKind::EpisodeRelease,
+            summary: "\"Show\" episode release".to_owned(),
+            body: "S01E02: Pilot, will be released in 1 hour".to_owned(),
+            series: Some(SeriesDetails {
+                id: 1,
+                name: "Show".to_owned(),
+            }),
+            episode: Some(EpisodeDetails {
+                season: 1,
+                number: Some(2),
+                name: "Pilot".to_owned(),
+                release_time: None,
+                minutes_to_release: 60,
+            }),
+        }
+    }
+
+    /// A local stand-in for http servers, accepting a single request and responding with
+    /// the given status
+    ///
+    /// Returns the url of the stand-in and a handle resolving to the request head and body.
+    async fn http_stand_in(status: u16) -> (String, tokio::task::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let mut request = vec![];
+            let mut buffer = [0; 1024];
+            let head_end = loop {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                if let Some(position) = request.windows(4).position(|bytes| bytes == b"\r\n\r\n") {
+                    break position + 4;
+                }
+            };
+
+            let head = String::from_utf8_lossy(&request[..head_end]).to_string();
+            let content_length: usize = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse().ok())?
+                })
+                .unwrap_or(0);
+
+            while request.len() < head_end + content_length {
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let body = String::from_utf8_lossy(&request[head_end..]).to_string();
+
+            stream
+                .write_all(
+                    format!("HTTP/1.1 {} Stand-in\r\ncontent-length: 0\r\n\r\n", status).as_bytes(),
+                )
+                .await
+                .unwrap();
+
+            (head, body)
+        });
+
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn webhook_sink_test() {
+        let (url, handle) = http_stand_in(200).await;
+
+        WebhookSink::new(format!("{}/hook", url))
+            .send(&test_notification())
+            .await
+            .unwrap();
+
+        let (head, body) = handle.await.unwrap();
+        assert!(head.starts_with("POST /hook "));
+
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["kind"], "episode-release");
+        assert_eq!(payload["summary"], "\"Show\" episode release");
+        assert_eq!(payload["series"]["name"], "Show");
+        assert_eq!(payload["episode"]["number"], 2);
+    }
+
+    #[tokio::test]
+    async fn ntfy_sink_test() {
+        let (url, handle) = http_stand_in(200).await;
+
+        NtfySink::new(url, "releases".to_owned(), Some("token".to_owned()))
+            .send(&test_notification())
+            .await
+            .unwrap();
+
+        let (head, body) = handle.await.unwrap();
+        let head = head.to_lowercase();
+        assert!(head.starts_with("post /releases "));
+        assert!(head.contains("title: \"show\" episode release"));
+        assert!(head.contains("authorization: bearer token"));
+        assert_eq!(body, "S01E02: Pilot, will be released in 1 hour");
+    }
+
+    #[tokio::test]
+    async fn ntfy_title_encoding_test() {
+        assert_eq!(encode_header_value("\"Show\" episode"), "\"Show\" episode");
+        assert_eq!(encode_header_value("Two\nlines"), "Two lines");
+        assert_eq!(encode_header_value("Café"), "=?UTF-8?B?Q2Fmw6k=?=");
+
+        let (url, handle) = http_stand_in(200).await;
+        let notification = Notification {
+            summary: "\"Café\"\nepisode release".to_owned(),
+            ..test_notification()
+        };
+
+        NtfySink::new(url, "releases".to_owned(), None)
+            .send(&notification)
+            .await
+            .unwrap();
+
+        let (head, _) = handle.await.unwrap();
+        let title = head
+            .lines()
+            .find_map(|line| line.strip_prefix("title: "))
+            .unwrap();
+        let encoded_title = title
+            .strip_prefix("=?UTF-8?B?")
+            .and_then(|title| title.strip_suffix("?="))
+            .unwrap();
+        let decoded_title = base64::engine::general_purpose::STANDARD
+            .decode(encoded_title)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(decoded_title).unwrap(),
+            "\"Café\" episode release"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_to_sinks_test() {
+        let (url, handle) = http_stand_in(200).await;
+        let (failing_url, failing_handle) = http_stand_in(500).await;
+
+        let sinks: Vec<Box<dyn NotificationSink>> = vec![
+            Box::new(WebhookSink::new(failing_url)),
+            Box::new(NtfySink::new(url, "releases".to_owned(), None)),
+        ];
+
+        assert_eq!(send_to_sinks(&sinks, &test_notification()).await, ["ntfy"]);
+        handle.await.unwrap();
+        failing_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn gotify_sink_test() {
+        let (url, handle) = http_stand_in(200).await;
+
+        GotifySink::new(url, "app-token".to_owned(), 8)
+            .send(&test_notification())
+            .await
+            .unwrap();
+
+        let (head, body) = handle.await.unwrap();
+        assert!(head.starts_with("POST /message "));
+        assert!(head.to_lowercase().contains("x-gotify-key: app-token"));
+
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["title"], "\"Show\" episode release");
+        assert_eq!(payload["priority"], 8);
+    }
+
+    #[tokio::test]
+    async fn failed_response_test() {
+        let (url, handle) = http_stand_in(500).await;
+
+        assert!(WebhookSink::new(url)
+            .send(&test_notification())
+            .await
+            .is_err());
+        handle.await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_sink_test() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let output_path = output_dir.path().join("output");
+
+        CommandSink::new(format!(
+            "printf '%s %s' \"$SERIES_TROXIDE_SERIES_NAME\" \"$SERIES_TROXIDE_EPISODE\" > '{}'",
+            output_path.display()
+        ))
+        .send(&test_notification())
+        .await
+        .unwrap();
+
+        let output = std::fs::read_to_string(&output_path).unwrap();
+        assert_eq!(output, "Show 2");
+
+        assert!(CommandSink::new("exit 1".to_owned())
+            .send(&test_notification())
+            .await
+            .is_err());
+
+        let started = std::time::Instant::now();
+        assert!(CommandSink::new("sleep 10".to_owned())
+            .with_timeout(Duration::from_millis(100))
+            .send(&test_notification())
+            .await
+            .is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
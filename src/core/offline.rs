@@ -0,0 +1,281 @@
+//! # Offline mode
+//!
+//! Caching falls back online whenever some data is not cached. Without connectivity, those
+//! fallbacks used to retry endlessly or fail after a long wait. When offline, all the reads
+//! are cache-only and anything that is not cached fails right away.
+//!
+//! The program is considered offline when either
+//! - offline mode is turned on in the settings.
+//! - a request fails to connect. Requests are then skipped, apart from one probing request
+//!   every `PROBE_INTERVAL` which brings the program back online when it succeeds.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::core::settings_config::SETTINGS;
+
+/// How long to wait after a failed connection before letting a request probe the network again
+const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+lazy_static! {
+    pub static ref OFFLINE: Offline = Offline::new();
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    #[default]
+    Online,
+    /// Offline mode is turned on in the settings
+    OfflineMode,
+    /// Requests are failing to connect
+    Disconnected,
+}
+
+impl ConnectionStatus {
+    pub fn is_offline(&self) -> bool {
+        *self != Self::Online
+    }
+}
+
+/// What the result of a request tells about connectivity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequestOutcome {
+    /// The server was reached, whatever the response was
+    Reached,
+    /// The request failed to connect or timed out
+    Unreachable,
+    /// The request failed in a way that tells nothing about connectivity
+    Inconclusive,
+}
+
+impl RequestOutcome {
+    fn of<T>(result: &Result<T, reqwest::Error>) -> Self {
+        match result {
+            Ok(_) => Self::Reached,
+            Err(err) if err.is_connect() || err.is_timeout() => Self::Unreachable,
+            Err(_) => Self::Inconclusive,
+        }
+    }
+}
+
+/// A change in connectivity caused by a request outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectivityChange {
+    Lost,
+    Restored,
+}
+
+/// The state machine of automatic disconnection detection
+///
+/// Goes from online to disconnected when a request is unreachable, lets a single probing request
+/// through every `PROBE_INTERVAL` while disconnected, and goes back online when a request reaches
+/// the server. The settings and the clock are passed in so that it's independent of them.
+#[derive(Debug, Clone, Copy, Default)]
+struct Connectivity {
+    /// When the last failed connection happened, or the last probe was let through
+    disconnected_at: Option<Instant>,
+}
+
+impl Connectivity {
+    fn status(&self, offline_mode: bool) -> ConnectionStatus {
+        if offline_mode {
+            ConnectionStatus::OfflineMode
+        } else if self.disconnected_at.is_some() {
+            ConnectionStatus::Disconnected
+        } else {
+            ConnectionStatus::Online
+        }
+    }
+
+    /// Whether a request made now should be skipped
+    ///
+    /// The first request after the `PROBE_INTERVAL` is let through as the probe and the interval
+    /// starts over, so the requests made while it's in flight are still skipped.
+    fn should_skip_requests(&mut self, offline_mode: bool, now: Instant) -> bool {
+        if offline_mode {
+            return true;
+        }
+
+        match self.disconnected_at {
+            Some(disconnected_at)
+                if now.saturating_duration_since(disconnected_at) < PROBE_INTERVAL =>
+            {
+                true
+            }
+            Some(_) => {
+                self.disconnected_at = Some(now);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Records the request outcome, a failed probe waits for another whole `PROBE_INTERVAL`
+    fn report(&mut self, outcome: RequestOutcome, now: Instant) -> Option<ConnectivityChange> {
+        match outcome {
+            RequestOutcome::Reached => self
+                .disconnected_at
+                .take()
+                .map(|_| ConnectivityChange::Restored),
+            RequestOutcome::Unreachable => self
+                .disconnected_at
+                .replace(now)
+                .is_none()
+                .then_some(ConnectivityChange::Lost),
+            RequestOutcome::Inconclusive => None,
+        }
+    }
+}
+
+pub struct Offline {
+    connectivity: Mutex<Connectivity>,
+    status: watch::Sender<ConnectionStatus>,
+}
+
+impl Offline {
+    fn new() -> Self {
+        let (status, _) = watch::channel(ConnectionStatus::default());
+        let offline = Self {
+            connectivity: Mutex::new(Connectivity::default()),
+            status,
+        };
+        offline.refresh_status();
+        offline
+    }
+
+    /// The current connection status
+    pub fn status(&self) -> ConnectionStatus {
+        self.get_connectivity().status(is_offline_mode_set())
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.status().is_offline()
+    }
+
+    /// Whether a request should be skipped and the cache(if any) be relied on instead
+    pub fn should_skip_requests(&self) -> bool {
+        self.connectivity
+            .lock()
+            .expect("failed to lock connectivity")
+            .should_skip_requests(is_offline_mode_set(), Instant::now())
+    }
+
+    /// Records the outcome of a request so that connectivity loss is detected automatically
+    ///
+    /// Only errors that come from failing to reach the server count as being disconnected.
+    pub fn report_request_result<T>(&self, result: &Result<T, reqwest::Error>) {
+        let change = self
+            .connectivity
+            .lock()
+            .expect("failed to lock connectivity")
+            .report(RequestOutcome::of(result), Instant::now());
+
+        match change {
+            Some(ConnectivityChange::Restored) => info!("connection restored"),
+            Some(ConnectivityChange::Lost) => {
+                if let Err(err) = result {
+                    warn!("connection lost, switching to cached data: {}", err);
+                }
+            }
+            None => {}
+        }
+        self.refresh_status();
+    }
+
+    /// Updates the status watchers, should be called whenever offline mode is toggled
+    pub fn refresh_status(&self) {
+        let status = self.status();
+        self.status.send_if_modified(|current_status| {
+            let is_modified = *current_status != status;
+            *current_status = status;
+            is_modified
+        });
+    }
+
+    /// Returns a receiver that gets notified whenever the connection status changes
+    pub fn subscribe(&self) -> watch::Receiver<ConnectionStatus> {
+        self.status.subscribe()
+    }
+
+    fn get_connectivity(&self) -> Connectivity {
+        *self
+            .connectivity
+            .lock()
+            .expect("failed to lock connectivity")
+    }
+}
+
+fn is_offline_mode_set() -> bool {
+    SETTINGS
+        .read()
+        .expect("failed to read settings")
+        .get_current_settings()
+        .caching
+        .offline_mode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disconnection_and_probing_test() {
+        let start = Instant::now();
+        let mut connectivity = Connectivity::default();
+        assert_eq!(connectivity.status(false), ConnectionStatus::Online);
+        assert!(!connectivity.should_skip_requests(false, start));
+
+        assert_eq!(
+            connectivity.report(RequestOutcome::Inconclusive, start),
+            None
+        );
+        assert_eq!(connectivity.status(false), ConnectionStatus::Online);
+
+        assert_eq!(
+            connectivity.report(RequestOutcome::Unreachable, start),
+            Some(ConnectivityChange::Lost)
+        );
+        assert_eq!(connectivity.status(false), ConnectionStatus::Disconnected);
+        assert!(connectivity.should_skip_requests(false, start + PROBE_INTERVAL / 2));
+
+        // A single probe is let through after the interval, failing it waits for another interval
+        let probe_time = start + PROBE_INTERVAL;
+        assert!(!connectivity.should_skip_requests(false, probe_time));
+        assert!(connectivity.should_skip_requests(false, probe_time));
+        assert!(connectivity.should_skip_requests(false, probe_time + PROBE_INTERVAL / 2));
+        assert_eq!(
+            connectivity.report(RequestOutcome::Unreachable, probe_time),
+            None
+        );
+        assert!(connectivity.should_skip_requests(false, probe_time + PROBE_INTERVAL / 2));
+
+        let probe_time = probe_time + PROBE_INTERVAL;
+        assert!(!connectivity.should_skip_requests(false, probe_time));
+        assert_eq!(
+            connectivity.report(RequestOutcome::Reached, probe_time),
+            Some(ConnectivityChange::Restored)
+        );
+        assert_eq!(connectivity.status(false), ConnectionStatus::Online);
+        assert!(!connectivity.should_skip_requests(false, probe_time));
+        assert_eq!(
+            connectivity.report(RequestOutcome::Reached, probe_time),
+            None
+        );
+    }
+
+    #[test]
+    fn offline_mode_test() {
+        let now = Instant::now();
+        let mut connectivity = Connectivity::default();
+        assert_eq!(connectivity.status(true), ConnectionStatus::OfflineMode);
+        assert!(connectivity.should_skip_requests(true, now));
+
+        connectivity.report(RequestOutcome::Unreachable, now);
+        assert_eq!(connectivity.status(true), ConnectionStatus::OfflineMode);
+        assert!(connectivity.should_skip_requests(true, now + PROBE_INTERVAL));
+    }
+}
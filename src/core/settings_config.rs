@@ -65,6 +65,10 @@ pub struct Config {
     pub appearance: AppearanceSettings,
     pub locale: LocaleSettings,
     pub notifications: NotificationSettings,
+    #[serde(default)]
+    pub caching: CachingSettings,
+    #[serde(default)]
+    pub calendar_feed: CalendarFeedSettings,
     pub custom_paths: Option<CustomPaths>,
 }
 
@@ -77,28 +81,286 @@ pub struct AppearanceSettings {
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct LocaleSettings {
     pub country_code: String,
+    /// The IANA time zone release times are displayed in, the system time zone when `None`
+    #[serde(default)]
+    pub time_zone: Option<chrono_tz::Tz>,
+    #[serde(default)]
+    pub clock_format: ClockFormat,
+    #[serde(default)]
+    pub date_format: DateFormat,
 }
 
 impl Default for LocaleSettings {
     fn default() -> Self {
         Self {
             country_code: "US".to_owned(),
+            time_zone: None,
+            clock_format: ClockFormat::default(),
+            date_format: DateFormat::default(),
         }
     }
 }
 
+#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum ClockFormat {
+    #[default]
+    TwelveHour,
+    TwentyFourHour,
+}
+
+pub const ALL_CLOCK_FORMATS: [ClockFormat; 2] =
+    [ClockFormat::TwelveHour, ClockFormat::TwentyFourHour];
+
+impl std::fmt::Display for ClockFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ClockFormat::TwelveHour => "12-hour",
+            ClockFormat::TwentyFourHour => "24-hour",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum DateFormat {
+    /// 2024-01-31
+    #[default]
+    Iso,
+    /// 31/01/2024
+    DayMonthYear,
+    /// 01/31/2024
+    MonthDayYear,
+    /// Jan 31, 2024
+    Long,
+}
+
+pub const ALL_DATE_FORMATS: [DateFormat; 4] = [
+    DateFormat::Iso,
+    DateFormat::DayMonthYear,
+    DateFormat::MonthDayYear,
+    DateFormat::Long,
+];
+
+impl DateFormat {
+    /// The `chrono` format string of the date format
+    pub fn format_str(&self) -> &'static str {
+        match self {
+            DateFormat::Iso => "%Y-%m-%d",
+            DateFormat::DayMonthYear => "%d/%m/%Y",
+            DateFormat::MonthDayYear => "%m/%d/%Y",
+            DateFormat::Long => "%b %-d, %Y",
+        }
+    }
+}
+
+impl std::fmt::Display for DateFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            DateFormat::Iso => "2024-01-31",
+            DateFormat::DayMonthYear => "31/01/2024",
+            DateFormat::MonthDayYear => "01/31/2024",
+            DateFormat::Long => "Jan 31, 2024",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct NotificationSettings {
-    // the time is in minutes
-    pub time_to_notify: u32,
+    // the times are in minutes, each one being a separate reminder
+    #[serde(
+        alias = "time_to_notify",
+        deserialize_with = "deserialize_times_to_notify"
+    )]
+    pub times_to_notify: Vec<u32>,
     pub notify_when_outdated: bool,
+    /// Whether to notify when a tracked series ends, gets a new season or a premiere date
+    ///
+    /// Changes are only detected for tracked series, when their cache gets updated.
+    #[serde(default = "default_notify_series_changes")]
+    pub notify_series_changes: bool,
+    /// Whether to show notifications on the desktop, can be turned off when only backends are used
+    #[serde(default = "default_desktop_notifications")]
+    pub desktop_notifications: bool,
+    #[serde(default)]
+    pub daily_digest: DailyDigestSettings,
+    #[serde(default)]
+    pub quiet_hours: QuietHoursSettings,
+    /// Additional places notifications are sent to
+    #[serde(default)]
+    pub backends: Vec<NotificationBackend>,
 }
 
 impl Default for NotificationSettings {
     fn default() -> Self {
         Self {
-            time_to_notify: 60,
+            times_to_notify: vec![60],
             notify_when_outdated: true,
+            notify_series_changes: default_notify_series_changes(),
+            desktop_notifications: default_desktop_notifications(),
+            daily_digest: DailyDigestSettings::default(),
+            quiet_hours: QuietHoursSettings::default(),
+            backends: vec![],
+        }
+    }
+}
+
+fn default_notify_series_changes() -> bool {
+    true
+}
+
+fn default_desktop_notifications() -> bool {
+    true
+}
+
+/// A period of the day in which notifications are held back and sent together once it's over
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct QuietHoursSettings {
+    pub enabled: bool,
+    /// The time of the day quiet hours start at, in the display time zone
+    pub start: chrono::NaiveTime,
+    /// The time of the day quiet hours end at, can be before the start when they span midnight
+    pub end: chrono::NaiveTime,
+}
+
+impl QuietHoursSettings {
+    /// Whether the given time of the day is in the quiet hours
+    pub fn is_quiet(&self, time: chrono::NaiveTime) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl Default for QuietHoursSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: chrono::NaiveTime::from_hms_opt(22, 0, 0).expect("22:00 should be a valid time"),
+            end: chrono::NaiveTime::from_hms_opt(7, 0, 0).expect("7:00 should be a valid time"),
+        }
+    }
+}
+
+/// A backend notifications are sent to besides the desktop
+///
+/// Configured in the config file as `[[notifications.backends]]` tables with a `kind` key.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum NotificationBackend {
+    /// Posts the notification as a json payload to the url
+    Webhook { url: String },
+    /// Publishes the notification to the topic of a ntfy server
+    Ntfy {
+        url: String,
+        topic: String,
+        /// The access token for protected topics
+        #[serde(default)]
+        token: Option<String>,
+    },
+    /// Posts the notification as a message to a gotify server
+    Gotify {
+        url: String,
+        /// The application token
+        token: String,
+        #[serde(default = "default_gotify_priority")]
+        priority: u8,
+    },
+    /// Runs the shell command with the notification details in `SERIES_TROXIDE_*` environment variables
+    Command { command: String },
+}
+
+fn default_gotify_priority() -> u8 {
+    5
+}
+
+/// A single notification sent every day listing the releases of the day and the unwatched episodes
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct DailyDigestSettings {
+    pub enabled: bool,
+    /// The time of the day the digest is sent at, in the display time zone
+    pub time: chrono::NaiveTime,
+    /// Whether the digest is sent instead of the reminders of the individual episode releases
+    pub replaces_reminders: bool,
+}
+
+impl Default for DailyDigestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            time: chrono::NaiveTime::from_hms_opt(9, 0, 0).expect("9:00 should be a valid time"),
+            replaces_reminders: false,
+        }
+    }
+}
+
+/// Older configs have a single `time_to_notify`, so both a single time and a list of times are accepted
+fn deserialize_times_to_notify<'de, D>(deserializer: D) -> Result<Vec<u32>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum TimesToNotify {
+        Single(u32),
+        Multiple(Vec<u32>),
+    }
+
+    Ok(match TimesToNotify::deserialize(deserializer)? {
+        TimesToNotify::Single(time_to_notify) => vec![time_to_notify],
+        TimesToNotify::Multiple(times_to_notify) => times_to_notify,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct CachingSettings {
+    /// The maximum amount of series fetches that can run at the same time
+    pub max_concurrent_fetches: usize,
+    /// The maximum size of the images cache in megabytes
+    #[serde(default = "default_max_image_cache_size")]
+    pub max_image_cache_size: u64,
+    /// Whether to only read from the cache without ever going online
+    #[serde(default)]
+    pub offline_mode: bool,
+}
+
+/// The largest images cache size that can be set in megabytes, i.e. 1 TB
+pub const MAX_IMAGE_CACHE_SIZE_LIMIT: u64 = 1024 * 1024;
+
+fn default_max_image_cache_size() -> u64 {
+    500
+}
+
+impl Default for CachingSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent_fetches: 10,
+            max_image_cache_size: default_max_image_cache_size(),
+            offline_mode: false,
+        }
+    }
+}
+
+/// The local HTTP server serving the upcoming releases as an iCalendar feed
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct CalendarFeedSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for CalendarFeedSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8642,
         }
     }
 }
@@ -283,3 +545,153 @@ pub mod locale_settings {
             .map(|country_code| country_code.name)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CalendarFeedSettings, ClockFormat, DateFormat, LocaleSettings, NotificationBackend,
+        NotificationSettings, QuietHoursSettings,
+    };
+
+    #[test]
+    fn single_time_to_notify_test() {
+        let notification_settings: NotificationSettings =
+            toml::from_str("time_to_notify = 30\nnotify_when_outdated = true").unwrap();
+        assert_eq!(notification_settings.times_to_notify, vec![30]);
+    }
+
+    #[test]
+    fn multiple_times_to_notify_test() {
+        let notification_settings: NotificationSettings =
+            toml::from_str("times_to_notify = [1440, 60]\nnotify_when_outdated = true").unwrap();
+        assert_eq!(notification_settings.times_to_notify, vec![1440, 60]);
+    }
+
+    #[test]
+    fn daily_digest_test() {
+        let notification_settings: NotificationSettings = toml::from_str(
+            "times_to_notify = [60]\nnotify_when_outdated = true\n[daily_digest]\nenabled = true\ntime = \"20:30:00\"\nreplaces_reminders = false",
+        )
+        .unwrap();
+        assert!(notification_settings.daily_digest.enabled);
+        assert_eq!(
+            notification_settings.daily_digest.time,
+            chrono::NaiveTime::from_hms_opt(20, 30, 0).unwrap()
+        );
+
+        let serialized_settings = toml::to_string_pretty(&notification_settings).unwrap();
+        assert_eq!(
+            toml::from_str::<NotificationSettings>(&serialized_settings).unwrap(),
+            notification_settings
+        );
+    }
+
+    #[test]
+    fn notification_backends_test() {
+        let notification_settings: NotificationSettings = toml::from_str(
+            r#"
+            times_to_notify = [60]
+            notify_when_outdated = true
+
+            [[backends]]
+            kind = "webhook"
+            url = "http://localhost:8080/hook"
+
+            [[backends]]
+            kind = "gotify"
+            url = "http://localhost:8081"
+            token = "secret"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            notification_settings.backends,
+            vec![
+                NotificationBackend::Webhook {
+                    url: "http://localhost:8080/hook".to_owned()
+                },
+                NotificationBackend::Gotify {
+                    url: "http://localhost:8081".to_owned(),
+                    token: "secret".to_owned(),
+                    priority: 5
+                }
+            ]
+        );
+
+        let serialized_settings = toml::to_string_pretty(&notification_settings).unwrap();
+        assert_eq!(
+            toml::from_str::<NotificationSettings>(&serialized_settings).unwrap(),
+            notification_settings
+        );
+    }
+
+    #[test]
+    fn quiet_hours_test() {
+        let time = |hour| chrono::NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+
+        let overnight_quiet_hours = QuietHoursSettings {
+            enabled: true,
+            start: time(22),
+            end: time(7),
+        };
+        assert!(overnight_quiet_hours.is_quiet(time(23)));
+        assert!(overnight_quiet_hours.is_quiet(time(3)));
+        assert!(!overnight_quiet_hours.is_quiet(time(7)));
+        assert!(!overnight_quiet_hours.is_quiet(time(12)));
+
+        let daytime_quiet_hours = QuietHoursSettings {
+            enabled: true,
+            start: time(9),
+            end: time(17),
+        };
+        assert!(daytime_quiet_hours.is_quiet(time(9)));
+        assert!(!daytime_quiet_hours.is_quiet(time(20)));
+
+        let disabled_quiet_hours = QuietHoursSettings {
+            enabled: false,
+            ..overnight_quiet_hours
+        };
+        assert!(!disabled_quiet_hours.is_quiet(time(23)));
+    }
+
+    #[test]
+    fn calendar_feed_test() {
+        let calendar_feed_settings: CalendarFeedSettings =
+            toml::from_str("enabled = true\nport = 9000").unwrap();
+        assert_eq!(
+            calendar_feed_settings,
+            CalendarFeedSettings {
+                enabled: true,
+                port: 9000
+            }
+        );
+
+        // The feed is opt-in
+        assert!(!CalendarFeedSettings::default().enabled);
+    }
+
+    #[test]
+    fn locale_date_time_test() {
+        // Configs from before the date and time settings still load with the previous display
+        let locale_settings: LocaleSettings = toml::from_str("country_code = \"TZ\"").unwrap();
+        assert_eq!(locale_settings.time_zone, None);
+        assert_eq!(locale_settings.clock_format, ClockFormat::TwelveHour);
+        assert_eq!(locale_settings.date_format, DateFormat::Iso);
+
+        let locale_settings: LocaleSettings = toml::from_str(
+            "country_code = \"TZ\"\ntime_zone = \"Africa/Dar_es_Salaam\"\nclock_format = \"TwentyFourHour\"\ndate_format = \"DayMonthYear\"",
+        )
+        .unwrap();
+        assert_eq!(
+            locale_settings.time_zone,
+            Some(chrono_tz::Africa::Dar_es_Salaam)
+        );
+
+        let serialized_settings = toml::to_string_pretty(&locale_settings).unwrap();
+        assert_eq!(
+            toml::from_str::<LocaleSettings>(&serialized_settings).unwrap(),
+            locale_settings
+        );
+    }
+}
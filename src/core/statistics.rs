@@ -0,0 +1,637 @@
+//! # Watch statistics
+//!
+//! Breakdowns of the watched series by their metadata, together with how far along they are being
+//! watched and how the watching went over time, as displayed in the Statistics tab.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+use crate::core::api::tv_maze::series_information::{Genre, SeriesMainInformation};
+use crate::core::caching::episode_list::EpisodeList;
+use crate::core::caching::fetch_limiter::FETCH_LIMITER;
+use crate::core::database;
+use crate::core::date_time_display::DateTimeDisplay;
+use crate::core::settings_config::locale_settings;
+
+const UNKNOWN: &str = "Unknown";
+
+/// A series in the database together with what has been watched of it
+#[derive(Debug, Clone)]
+pub struct WatchedSeries {
+    pub series_info: SeriesMainInformation,
+    pub is_tracked: bool,
+    /// The total watch time in minutes
+    pub watch_time: Option<u32>,
+    pub watched_episodes: usize,
+    /// The episodes released so far, `None` when the episode list is unavailable
+    pub watchable_episodes: Option<usize>,
+    /// When the watched episodes were watched, leaving out the ones without a date to go by
+    pub episode_watches: Vec<EpisodeWatch>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpisodeWatch {
+    /// The day the episode was marked watched, or it's air date when that wasn't recorded
+    pub date: NaiveDate,
+    /// The runtime of the episode in minutes
+    pub runtime: u32,
+}
+
+/// Gets all the series in the database with what has been watched of them
+///
+/// Episodes are dated by the day they were watched on in the display time zone.
+pub async fn get_watched_series() -> Vec<WatchedSeries> {
+    let date_time_display = DateTimeDisplay::from_settings();
+    let handles: Vec<_> = database::DB
+        .get_series_collection()
+        .into_iter()
+        .map(|series| {
+            FETCH_LIMITER.spawn(async move {
+                let (series_info, watch_time) = match series.get_total_watchtime().await {
+                    Ok(total_watchtime) => total_watchtime,
+                    Err(err) => {
+                        tracing::error!(
+                            "failed to get the watch time of series with id {}: {}",
+                            series.id(),
+                            err
+                        );
+                        return None;
+                    }
+                };
+                let episode_list = EpisodeList::new(series.id()).await.ok();
+
+                let watch_times = database::DB.get_episode_watch_times(series.id());
+                let episode_watches = series
+                    .get_watched_episodes()
+                    .into_iter()
+                    .filter_map(|(season_number, episode_number)| {
+                        let episode = episode_list.as_ref().and_then(|episode_list| {
+                            episode_list.get_episode(season_number, episode_number)
+                        });
+
+                        let date = match watch_times.get(&(season_number, episode_number)) {
+                            Some(watch_time) => date_time_display.zoned(watch_time).date_naive(),
+                            None => episode
+                                .and_then(|episode| episode.airdate.as_deref())
+                                .and_then(|airdate| {
+                                    NaiveDate::parse_from_str(airdate, "%Y-%m-%d").ok()
+                                })?,
+                        };
+                        let runtime = episode
+                            .and_then(|episode| episode.runtime)
+                            .or(series_info.average_runtime)
+                            .unwrap_or_default();
+
+                        Some(EpisodeWatch { date, runtime })
+                    })
+                    .collect();
+
+                Some(WatchedSeries {
+                    series_info,
+                    is_tracked: series.is_tracked(),
+                    watch_time,
+                    watched_episodes: series.get_total_episodes(),
+                    watchable_episodes: episode_list
+                        .map(|episode_list| episode_list.get_total_watchable_episodes()),
+                    episode_watches,
+                })
+            })
+        })
+        .collect();
+
+    let mut watched_series = Vec::with_capacity(handles.len());
+    for handle in handles {
+        // Series whose information failed to load are left out
+        if let Some(series) = handle.await.expect("failed to await all watched series") {
+            watched_series.push(series);
+        }
+    }
+    watched_series
+}
+
+/// The genres of the series with how many series have them, the most common first
+pub fn genre_share(watched_series: &[WatchedSeries]) -> Vec<(Genre, usize)> {
+    let mut genre_count: HashMap<Genre, usize> = HashMap::new();
+
+    watched_series
+        .iter()
+        .flat_map(|series| series.series_info.get_genres())
+        .for_each(|genre| *genre_count.entry(genre).or_default() += 1);
+
+    let mut genre_count: Vec<(Genre, usize)> = genre_count.into_iter().collect();
+    genre_count.sort_unstable_by(|(genre_a, count_a), (genre_b, count_b)| {
+        count_b
+            .cmp(count_a)
+            .then_with(|| genre_a.to_string().cmp(&genre_b.to_string()))
+    });
+    genre_count
+}
+
+/// The series metadata the watched series can be broken down by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakdownKind {
+    /// The network or web channel
+    Network,
+    Country,
+    Language,
+    ShowType,
+    /// The decade of the premiere
+    Decade,
+}
+
+pub const ALL_BREAKDOWN_KINDS: [BreakdownKind; 5] = [
+    BreakdownKind::Network,
+    BreakdownKind::Country,
+    BreakdownKind::Language,
+    BreakdownKind::ShowType,
+    BreakdownKind::Decade,
+];
+
+impl BreakdownKind {
+    fn group_name(&self, series_info: &SeriesMainInformation) -> Option<String> {
+        match self {
+            BreakdownKind::Network => series_info
+                .network
+                .as_ref()
+                .map(|network| network.name.clone())
+                .or_else(|| {
+                    series_info
+                        .web_channel
+                        .as_ref()
+                        .map(|web_channel| web_channel.name.clone())
+                }),
+            BreakdownKind::Country => series_info
+                .get_country_code()
+                .and_then(locale_settings::get_country_name_from_country_code)
+                .map(str::to_owned),
+            BreakdownKind::Language => series_info.language.clone(),
+            BreakdownKind::ShowType => series_info.kind.clone(),
+            BreakdownKind::Decade => series_info
+                .premiered
+                .as_deref()
+                .and_then(|premiered| NaiveDate::parse_from_str(premiered, "%Y-%m-%d").ok())
+                .map(|premiered| format!("{}s", premiered.year() / 10 * 10)),
+        }
+    }
+}
+
+impl std::fmt::Display for BreakdownKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            BreakdownKind::Network => "Network",
+            BreakdownKind::Country => "Country",
+            BreakdownKind::Language => "Language",
+            BreakdownKind::ShowType => "Show type",
+            BreakdownKind::Decade => "Decade",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakdownSorting {
+    Series,
+    Episodes,
+    WatchTime,
+    Name,
+}
+
+pub const ALL_BREAKDOWN_SORTINGS: [BreakdownSorting; 4] = [
+    BreakdownSorting::Series,
+    BreakdownSorting::Episodes,
+    BreakdownSorting::WatchTime,
+    BreakdownSorting::Name,
+];
+
+impl std::fmt::Display for BreakdownSorting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            BreakdownSorting::Series => "Most series",
+            BreakdownSorting::Episodes => "Most episodes",
+            BreakdownSorting::WatchTime => "Most watch time",
+            BreakdownSorting::Name => "Name",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+/// A group of watched series sharing the same metadata
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakdownEntry {
+    pub name: String,
+    pub series: usize,
+    pub watched_episodes: usize,
+    /// The total watch time in minutes
+    pub watch_time: u32,
+}
+
+/// Groups the watched series by the given kind of metadata
+///
+/// Series without any watched episodes are left out and the ones missing the metadata are
+/// grouped under `Unknown`.
+pub fn breakdown(
+    watched_series: &[WatchedSeries],
+    kind: BreakdownKind,
+    sorting: BreakdownSorting,
+) -> Vec<BreakdownEntry> {
+    let mut groups: HashMap<String, BreakdownEntry> = HashMap::new();
+
+    for series in watched_series
+        .iter()
+        .filter(|series| series.watched_episodes != 0)
+    {
+        let name = kind
+            .group_name(&series.series_info)
+            .unwrap_or_else(|| UNKNOWN.to_owned());
+
+        let entry = groups
+            .entry(name.clone())
+            .or_insert_with(|| BreakdownEntry {
+                name,
+                series: 0,
+                watched_episodes: 0,
+                watch_time: 0,
+            });
+        entry.series += 1;
+        entry.watched_episodes += series.watched_episodes;
+        entry.watch_time += series.watch_time.unwrap_or_default();
+    }
+
+    let mut entries: Vec<BreakdownEntry> = groups.into_values().collect();
+
+    // Sorting by name first so that ties of the other sortings stay in a stable order
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    match sorting {
+        BreakdownSorting::Series => entries.sort_by_key(|entry| std::cmp::Reverse(entry.series)),
+        BreakdownSorting::Episodes => {
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.watched_episodes))
+        }
+        BreakdownSorting::WatchTime => {
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.watch_time))
+        }
+        BreakdownSorting::Name => {}
+    }
+
+    entries
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressSorting {
+    MostCompleted,
+    LeastCompleted,
+    Name,
+}
+
+pub const ALL_PROGRESS_SORTINGS: [ProgressSorting; 3] = [
+    ProgressSorting::MostCompleted,
+    ProgressSorting::LeastCompleted,
+    ProgressSorting::Name,
+];
+
+impl std::fmt::Display for ProgressSorting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ProgressSorting::MostCompleted => "Most completed",
+            ProgressSorting::LeastCompleted => "Least completed",
+            ProgressSorting::Name => "Name",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+/// How much of the released episodes of a series have been watched
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesProgress {
+    pub series_id: u32,
+    pub name: String,
+    pub watched_episodes: usize,
+    pub watchable_episodes: usize,
+}
+
+impl SeriesProgress {
+    /// The fraction of the released episodes that have been watched, from 0 to 1
+    pub fn completion(&self) -> f32 {
+        if self.watchable_episodes == 0 {
+            return 1.0;
+        }
+        (self.watched_episodes as f32 / self.watchable_episodes as f32).min(1.0)
+    }
+
+    /// The released episodes that haven't been watched yet
+    pub fn unwatched_episodes(&self) -> usize {
+        self.watchable_episodes
+            .saturating_sub(self.watched_episodes)
+    }
+}
+
+/// The completion of the watched series whose episode lists are available
+pub fn series_progress(
+    watched_series: &[WatchedSeries],
+    sorting: ProgressSorting,
+) -> Vec<SeriesProgress> {
+    let mut progress: Vec<SeriesProgress> = watched_series
+        .iter()
+        .filter(|series| series.watched_episodes != 0)
+        .filter_map(|series| {
+            Some(SeriesProgress {
+                series_id: series.series_info.id,
+                name: series.series_info.name.clone(),
+                watched_episodes: series.watched_episodes,
+                watchable_episodes: series.watchable_episodes?,
+            })
+        })
+        .collect();
+
+    progress.sort_by(|a, b| a.name.cmp(&b.name));
+    match sorting {
+        ProgressSorting::MostCompleted => {
+            progress.sort_by(|a, b| b.completion().total_cmp(&a.completion()))
+        }
+        ProgressSorting::LeastCompleted => {
+            progress.sort_by(|a, b| a.completion().total_cmp(&b.completion()))
+        }
+        ProgressSorting::Name => {}
+    }
+
+    progress
+}
+
+/// The tracked series with the most released episodes left to watch, the furthest behind first
+pub fn furthest_behind(watched_series: &[WatchedSeries]) -> Vec<SeriesProgress> {
+    let tracked_series: Vec<WatchedSeries> = watched_series
+        .iter()
+        .filter(|series| series.is_tracked)
+        .cloned()
+        .collect();
+
+    let mut progress: Vec<SeriesProgress> = series_progress(&tracked_series, ProgressSorting::Name)
+        .into_iter()
+        .filter(|progress| progress.unwatched_episodes() != 0)
+        .collect();
+
+    progress.sort_by_key(|progress| std::cmp::Reverse(progress.unwatched_episodes()));
+    progress
+}
+
+/// The average TVmaze rating of the watched series that have one
+pub fn average_rating(watched_series: &[WatchedSeries]) -> Option<f32> {
+    let ratings: Vec<f32> = watched_series
+        .iter()
+        .filter(|series| series.watched_episodes != 0)
+        .filter_map(|series| series.series_info.rating.average)
+        .collect();
+
+    if ratings.is_empty() {
+        None
+    } else {
+        Some(ratings.iter().sum::<f32>() / ratings.len() as f32)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartPeriod {
+    Week,
+    Month,
+}
+
+pub const ALL_CHART_PERIODS: [ChartPeriod; 2] = [ChartPeriod::Week, ChartPeriod::Month];
+
+impl ChartPeriod {
+    /// How many of the most recent periods are charted
+    fn span(&self) -> usize {
+        match self {
+            ChartPeriod::Week => 26,
+            ChartPeriod::Month => 24,
+        }
+    }
+
+    /// The first day of the period the date is in, weeks starting on Monday
+    fn start_of(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            ChartPeriod::Week => {
+                date - Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+            ChartPeriod::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    fn previous(&self, period_start: NaiveDate) -> NaiveDate {
+        match self {
+            ChartPeriod::Week => period_start - Duration::weeks(1),
+            ChartPeriod::Month => period_start
+                .checked_sub_months(Months::new(1))
+                .unwrap_or(period_start),
+        }
+    }
+}
+
+impl std::fmt::Display for ChartPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            ChartPeriod::Week => "Weekly",
+            ChartPeriod::Month => "Monthly",
+        };
+
+        write!(f, "{}", str)
+    }
+}
+
+/// The watching done in a period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodWatches {
+    /// The first day of the period
+    pub start: NaiveDate,
+    pub episodes: usize,
+    /// All the minutes watched up to the end of the period
+    pub cumulative_minutes: u32,
+}
+
+/// The watching done in each of the most recent periods up to the period of the given day
+pub fn watch_history(
+    watched_series: &[WatchedSeries],
+    period: ChartPeriod,
+    today: NaiveDate,
+) -> Vec<PeriodWatches> {
+    let mut period_starts = vec![period.start_of(today)];
+    for _ in 1..period.span() {
+        let previous_start = period.previous(*period_starts.last().unwrap());
+        period_starts.push(previous_start);
+    }
+    period_starts.reverse();
+
+    let episode_watches: Vec<&EpisodeWatch> = watched_series
+        .iter()
+        .flat_map(|series| series.episode_watches.iter())
+        .filter(|episode_watch| episode_watch.date <= today)
+        .collect();
+
+    // Everything watched before the charted periods still counts towards the cumulative time
+    let mut cumulative_minutes: u32 = episode_watches
+        .iter()
+        .filter(|episode_watch| episode_watch.date < period_starts[0])
+        .map(|episode_watch| episode_watch.runtime)
+        .sum();
+
+    period_starts
+        .iter()
+        .enumerate()
+        .map(|(index, start)| {
+            let period_watches = episode_watches.iter().filter(|episode_watch| {
+                episode_watch.date >= *start
+                    && period_starts
+                        .get(index + 1)
+                        .is_none_or(|next_start| episode_watch.date < *next_start)
+            });
+
+            let mut episodes = 0;
+            for episode_watch in period_watches {
+                episodes += 1;
+                cumulative_minutes += episode_watch.runtime;
+            }
+
+            PeriodWatches {
+                start: *start,
+                episodes,
+                cumulative_minutes,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::api::tv_maze::series_information::{Country, Network};
+    use crate::core::api::tv_maze::test_fixtures;
+
+    fn watched_series(
+        id: u32,
+        network: &str,
+        premiered: &str,
+        is_tracked: bool,
+        watched_episodes: usize,
+        watchable_episodes: usize,
+    ) -> WatchedSeries {
+        let mut series_info = test_fixtures::series_info(id);
+        series_info.premiered = Some(premiered.to_owned());
+        series_info.rating.average = Some(id as f32);
+        series_info.network = Some(Network {
+            name: network.to_owned(),
+            country: Country {
+                name: Some("United States".to_owned()),
+                code: Some("US".to_owned()),
+            },
+            official_site_url: None,
+        });
+
+        WatchedSeries {
+            series_info,
+            is_tracked,
+            watch_time: Some(watched_episodes as u32 * 60),
+            watched_episodes,
+            watchable_episodes: Some(watchable_episodes),
+            episode_watches: vec![],
+        }
+    }
+
+    #[test]
+    fn breakdown_test() {
+        let watched_series = [
+            watched_series(1, "HBO", "1999-01-10", true, 10, 10),
+            watched_series(2, "AMC", "2008-01-20", true, 30, 62),
+            watched_series(3, "HBO", "2011-04-17", false, 5, 73),
+        ];
+
+        assert_eq!(
+            breakdown(
+                &watched_series,
+                BreakdownKind::Network,
+                BreakdownSorting::Series
+            ),
+            vec![
+                BreakdownEntry {
+                    name: "HBO".to_owned(),
+                    series: 2,
+                    watched_episodes: 15,
+                    watch_time: 900,
+                },
+                BreakdownEntry {
+                    name: "AMC".to_owned(),
+                    series: 1,
+                    watched_episodes: 30,
+                    watch_time: 1800,
+                },
+            ]
+        );
+
+        let decades: Vec<String> = breakdown(
+            &watched_series,
+            BreakdownKind::Decade,
+            BreakdownSorting::Name,
+        )
+        .into_iter()
+        .map(|entry| entry.name)
+        .collect();
+        assert_eq!(decades, vec!["1990s", "2000s", "2010s"]);
+
+        assert_eq!(average_rating(&watched_series), Some(2.0));
+    }
+
+    #[test]
+    fn progress_test() {
+        let watched_series = [
+            watched_series(1, "HBO", "1999-01-10", true, 10, 10),
+            watched_series(2, "AMC", "2008-01-20", true, 30, 62),
+            watched_series(3, "HBO", "2011-04-17", false, 5, 73),
+        ];
+
+        let least_completed: Vec<u32> =
+            series_progress(&watched_series, ProgressSorting::LeastCompleted)
+                .iter()
+                .map(|progress| progress.series_id)
+                .collect();
+        assert_eq!(least_completed, vec![3, 2, 1]);
+
+        // Untracked and completed series aren't behind
+        let furthest_behind = furthest_behind(&watched_series);
+        assert_eq!(furthest_behind.len(), 1);
+        assert_eq!(furthest_behind[0].unwatched_episodes(), 32);
+    }
+
+    #[test]
+    fn watch_history_test() {
+        let mut series = watched_series(1, "HBO", "1999-01-10", true, 3, 10);
+        let date = |day| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+        series.episode_watches = vec![
+            EpisodeWatch {
+                date: NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                runtime: 60,
+            },
+            EpisodeWatch {
+                date: date(4),
+                runtime: 30,
+            },
+            EpisodeWatch {
+                date: date(10),
+                runtime: 45,
+            },
+        ];
+
+        // Sunday the 10th is in the same week as Monday the 4th
+        let history = watch_history(&[series], ChartPeriod::Week, date(10));
+        assert_eq!(history.len(), 26);
+        assert_eq!(
+            history.last(),
+            Some(&PeriodWatches {
+                start: date(4),
+                episodes: 2,
+                cumulative_minutes: 135,
+            })
+        );
+        assert_eq!(history[0].cumulative_minutes, 60);
+    }
+}
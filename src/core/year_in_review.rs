@@ -0,0 +1,575 @@
+//! # Year in review
+//!
+//! A yearly summary of the watched series made from the same data as the Statistics tab, exported
+//! as a standalone SVG and PNG image together with Markdown and HTML reports for sharing.
+//!
+//! Episodes count towards the year they were marked watched, or the year they aired when that
+//! wasn't recorded.
+
+use std::path::{self, PathBuf};
+
+use anyhow::Context;
+use base64::Engine;
+use bytes::Bytes;
+use chrono::Datelike;
+
+use crate::core::api::tv_maze::series_information::{Genre, SeriesMainInformation};
+use crate::core::caching;
+use crate::core::statistics::{self, WatchedSeries};
+use crate::gui::assets::fonts;
+
+const TOP_SHOWS: usize = 5;
+const TOP_GENRES: usize = 5;
+
+const IMAGE_WIDTH: u32 = 800;
+const POSTER_WIDTH: u32 = 60;
+const POSTER_HEIGHT: u32 = 85;
+
+/// The accent and background colors of the dark theme
+const ACCENT_COLOR: &str = "#8f6593";
+const BACKGROUND_COLOR: &str = "#282828";
+const TEXT_COLOR: &str = "#ffffff";
+
+#[derive(Debug, Clone)]
+pub struct TopShow {
+    pub series_info: SeriesMainInformation,
+    pub episodes: usize,
+    /// The watch time during the year in minutes
+    pub minutes: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct YearInReview {
+    pub year: i32,
+    pub episodes: usize,
+    /// The watch time during the year in minutes
+    pub minutes: u32,
+    /// The shows with the most watch time during the year, the most watched first
+    pub top_shows: Vec<TopShow>,
+    /// The genres of the shows watched during the year with how many of them have it
+    pub top_genres: Vec<(Genre, usize)>,
+    /// The shows whose first watched episode was watched during the year
+    pub shows_started: usize,
+    /// The ended shows with all their episodes watched, the last of them during the year
+    pub shows_completed: usize,
+}
+
+impl YearInReview {
+    pub fn new(watched_series: &[WatchedSeries], year: i32) -> Self {
+        let mut episodes = 0;
+        let mut minutes = 0;
+        let mut top_shows = vec![];
+        let mut shows_started = 0;
+        let mut shows_completed = 0;
+
+        for series in watched_series {
+            let year_watches: Vec<_> = series
+                .episode_watches
+                .iter()
+                .filter(|episode_watch| episode_watch.date.year() == year)
+                .collect();
+
+            if year_watches.is_empty() {
+                continue;
+            }
+
+            let series_minutes = year_watches
+                .iter()
+                .map(|episode_watch| episode_watch.runtime)
+                .sum();
+            episodes += year_watches.len();
+            minutes += series_minutes;
+
+            let first_watch = series
+                .episode_watches
+                .iter()
+                .map(|episode_watch| episode_watch.date)
+                .min();
+            if first_watch.is_some_and(|first_watch| first_watch.year() == year) {
+                shows_started += 1;
+            }
+
+            let last_watch = series
+                .episode_watches
+                .iter()
+                .map(|episode_watch| episode_watch.date)
+                .max();
+            let is_fully_watched = series
+                .watchable_episodes
+                .is_some_and(|watchable_episodes| series.watched_episodes >= watchable_episodes);
+            if series.series_info.has_ended()
+                && is_fully_watched
+                && last_watch.is_some_and(|last_watch| last_watch.year() == year)
+            {
+                shows_completed += 1;
+            }
+
+            top_shows.push(TopShow {
+                series_info: series.series_info.clone(),
+                episodes: year_watches.len(),
+                minutes: series_minutes,
+            });
+        }
+
+        let year_series: Vec<WatchedSeries> = watched_series
+            .iter()
+            .filter(|series| {
+                top_shows
+                    .iter()
+                    .any(|top_show| top_show.series_info.id == series.series_info.id)
+            })
+            .cloned()
+            .collect();
+        let mut top_genres = statistics::genre_share(&year_series);
+        top_genres.truncate(TOP_GENRES);
+
+        top_shows.sort_by(|a, b| {
+            b.minutes
+                .cmp(&a.minutes)
+                .then_with(|| a.series_info.name.cmp(&b.series_info.name))
+        });
+        top_shows.truncate(TOP_SHOWS);
+
+        Self {
+            year,
+            episodes,
+            minutes,
+            top_shows,
+            top_genres,
+            shows_started,
+            shows_completed,
+        }
+    }
+
+    pub fn hours(&self) -> u32 {
+        self.minutes / 60
+    }
+
+    fn title(&self) -> String {
+        format!("My {} in series", self.year)
+    }
+
+    /// The summary as a standalone SVG image, the posters being those of the top shows in order
+    pub fn svg_str(&self, posters: &[Option<Bytes>]) -> String {
+        let accent_color = ACCENT_COLOR;
+        let background_color = BACKGROUND_COLOR;
+        let text_color = TEXT_COLOR;
+
+        let top_shows_top = 230;
+        let show_row_height = POSTER_HEIGHT + 15;
+        let genres_top = top_shows_top + 40 + show_row_height * self.top_shows.len() as u32;
+        let height = genres_top + 40 + 30 * self.top_genres.len() as u32 + 40;
+
+        let mut svg = vec![
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{}" height="{}" viewBox="0 0 {} {}" font-family="Noto Sans, sans-serif">"#,
+                IMAGE_WIDTH, height, IMAGE_WIDTH, height
+            ),
+            format!(
+                r#"<rect width="100%" height="100%" rx="20" fill="{}"/>"#,
+                background_color
+            ),
+            format!(
+                r#"<text x="40" y="70" font-size="36" font-weight="bold" fill="{}">{}</text>"#,
+                accent_color,
+                escape_xml(&self.title())
+            ),
+        ];
+
+        let numbers = [
+            (self.hours(), "hours"),
+            (self.episodes as u32, "episodes"),
+            (self.shows_started as u32, "shows started"),
+            (self.shows_completed as u32, "shows completed"),
+        ];
+        for (index, (number, description)) in numbers.iter().enumerate() {
+            let x = 40 + index as u32 * 185;
+            svg.push(format!(
+                r#"<text x="{}" y="150" font-size="40" font-weight="bold" fill="{}">{}</text>"#,
+                x, accent_color, number
+            ));
+            svg.push(format!(
+                r#"<text x="{}" y="180" font-size="16" fill="{}">{}</text>"#,
+                x, text_color, description
+            ));
+        }
+
+        svg.push(format!(
+            r#"<text x="40" y="{}" font-size="24" font-weight="bold" fill="{}">Top shows</text>"#,
+            top_shows_top, text_color
+        ));
+        for (index, top_show) in self.top_shows.iter().enumerate() {
+            let row_top = top_shows_top + 20 + show_row_height * index as u32;
+
+            match posters.get(index).and_then(Option::as_ref) {
+                Some(poster) => svg.push(format!(
+                    r#"<image x="40" y="{}" width="{}" height="{}" preserveAspectRatio="xMidYMid slice" xlink:href="{}"/>"#,
+                    row_top,
+                    POSTER_WIDTH,
+                    POSTER_HEIGHT,
+                    image_data_uri(poster)
+                )),
+                None => svg.push(format!(
+                    r#"<rect x="40" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    row_top, POSTER_WIDTH, POSTER_HEIGHT, accent_color
+                )),
+            }
+
+            svg.push(format!(
+                r#"<text x="{}" y="{}" font-size="20" fill="{}">{}. {}</text>"#,
+                60 + POSTER_WIDTH,
+                row_top + 35,
+                text_color,
+                index + 1,
+                escape_xml(&top_show.series_info.name)
+            ));
+            svg.push(format!(
+                r#"<text x="{}" y="{}" font-size="16" fill="{}">{}</text>"#,
+                60 + POSTER_WIDTH,
+                row_top + 60,
+                accent_color,
+                top_show_details(top_show)
+            ));
+        }
+
+        svg.push(format!(
+            r#"<text x="40" y="{}" font-size="24" font-weight="bold" fill="{}">Top genres</text>"#,
+            genres_top, text_color
+        ));
+        for (index, (genre, count)) in self.top_genres.iter().enumerate() {
+            svg.push(format!(
+                r#"<text x="40" y="{}" font-size="18" fill="{}">{}. {} <tspan fill="{}">({} shows)</tspan></text>"#,
+                genres_top + 35 + 30 * index as u32,
+                text_color,
+                index + 1,
+                genre,
+                accent_color,
+                count
+            ));
+        }
+
+        svg.push("</svg>".to_owned());
+        svg.join("\n")
+    }
+
+    /// The summary as a Markdown report, showing the image at the given path relative to it
+    pub fn markdown_str(&self, image_path: &str) -> String {
+        let mut markdown = vec![
+            format!("# {}", self.title()),
+            String::new(),
+            format!("![{}]({})", self.title(), image_path),
+            String::new(),
+            format!("- **{}** hours watched", self.hours()),
+            format!("- **{}** episodes watched", self.episodes),
+            format!("- **{}** shows started", self.shows_started),
+            format!("- **{}** shows completed", self.shows_completed),
+            String::new(),
+            "## Top shows".to_owned(),
+            String::new(),
+        ];
+
+        markdown.extend(self.top_shows.iter().enumerate().map(|(index, top_show)| {
+            format!(
+                "{}. **{}**, {}",
+                index + 1,
+                top_show.series_info.name,
+                top_show_details(top_show)
+            )
+        }));
+
+        markdown.extend([String::new(), "## Top genres".to_owned(), String::new()]);
+        markdown.extend(
+            self.top_genres
+                .iter()
+                .enumerate()
+                .map(|(index, (genre, count))| {
+                    format!("{}. {} ({} shows)", index + 1, genre, count)
+                }),
+        );
+
+        markdown.push(String::new());
+        markdown.join("\n")
+    }
+
+    /// The summary as a standalone HTML report, the posters being those of the top shows in order
+    pub fn html_str(&self, posters: &[Option<Bytes>]) -> String {
+        let accent_color = ACCENT_COLOR;
+        let background_color = BACKGROUND_COLOR;
+
+        let numbers = [
+            (self.hours(), "hours"),
+            (self.episodes as u32, "episodes"),
+            (self.shows_started as u32, "shows started"),
+            (self.shows_completed as u32, "shows completed"),
+        ]
+        .iter()
+        .map(|(number, description)| {
+            format!(
+                r#"<div class="number"><strong>{}</strong><span>{}</span></div>"#,
+                number, description
+            )
+        })
+        .collect::<String>();
+
+        let top_shows = self
+            .top_shows
+            .iter()
+            .enumerate()
+            .map(|(index, top_show)| {
+                let poster = posters
+                    .get(index)
+                    .and_then(Option::as_ref)
+                    .map(|poster| format!(r#"<img src="{}" alt="">"#, image_data_uri(poster)))
+                    .unwrap_or_default();
+                format!(
+                    r#"<li>{}<div><strong>{}</strong><br><span class="accent">{}</span></div></li>"#,
+                    poster,
+                    escape_xml(&top_show.series_info.name),
+                    top_show_details(top_show)
+                )
+            })
+            .collect::<String>();
+
+        let top_genres = self
+            .top_genres
+            .iter()
+            .map(|(genre, count)| {
+                format!(
+                    r#"<li>{} <span class="accent">({} shows)</span></li>"#,
+                    genre, count
+                )
+            })
+            .collect::<String>();
+
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ background: {background}; color: {text}; font-family: sans-serif; max-width: 800px; margin: 40px auto; padding: 0 20px; }}
+h1, .accent, .number strong {{ color: {accent}; }}
+.numbers {{ display: flex; gap: 40px; }}
+.number strong {{ display: block; font-size: 40px; }}
+ol {{ padding-left: 20px; }}
+li {{ margin-bottom: 15px; }}
+li img {{ width: {poster_width}px; height: {poster_height}px; object-fit: cover; vertical-align: middle; margin-right: 15px; }}
+li div {{ display: inline-block; vertical-align: middle; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+<div class="numbers">{numbers}</div>
+<h2>Top shows</h2>
+<ol>{top_shows}</ol>
+<h2>Top genres</h2>
+<ol>{top_genres}</ol>
+</body>
+</html>
+"#,
+            title = escape_xml(&self.title()),
+            background = background_color,
+            accent = accent_color,
+            text = TEXT_COLOR,
+            poster_width = POSTER_WIDTH,
+            poster_height = POSTER_HEIGHT,
+            numbers = numbers,
+            top_shows = top_shows,
+            top_genres = top_genres,
+        )
+    }
+}
+
+/// The years that have watched episodes, the most recent first
+pub fn get_years(watched_series: &[WatchedSeries]) -> Vec<i32> {
+    let mut years: Vec<i32> = watched_series
+        .iter()
+        .flat_map(|series| series.episode_watches.iter())
+        .map(|episode_watch| episode_watch.date.year())
+        .collect();
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years.dedup();
+    years
+}
+
+/// Exports the year in review of the given year into the given directory
+///
+/// Returns the paths of the exported files.
+pub async fn export(
+    watched_series: Vec<WatchedSeries>,
+    year: i32,
+    directory: impl AsRef<path::Path>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let year_in_review = YearInReview::new(&watched_series, year);
+
+    let mut posters = Vec::with_capacity(year_in_review.top_shows.len());
+    for top_show in year_in_review.top_shows.iter() {
+        let poster = match &top_show.series_info.image {
+            Some(image) => {
+                caching::load_image(
+                    image.medium_image_url.clone(),
+                    caching::ImageResolution::Medium(caching::ImageKind::PosterThumb),
+                )
+                .await
+            }
+            None => None,
+        };
+        posters.push(poster);
+    }
+
+    let svg = year_in_review.svg_str(&posters);
+    let png = tokio::task::spawn_blocking({
+        let svg = svg.clone();
+        move || render_png(&svg)
+    })
+    .await??;
+
+    let file_stem = format!("series-troxide-{}-in-review", year);
+    let files = [
+        (format!("{}.svg", file_stem), svg.into_bytes()),
+        (format!("{}.png", file_stem), png),
+        (
+            format!("{}.md", file_stem),
+            year_in_review
+                .markdown_str(&format!("{}.png", file_stem))
+                .into_bytes(),
+        ),
+        (
+            format!("{}.html", file_stem),
+            year_in_review.html_str(&posters).into_bytes(),
+        ),
+    ];
+
+    tokio::fs::create_dir_all(directory.as_ref())
+        .await
+        .with_context(|| format!("failed to create {}", directory.as_ref().display()))?;
+
+    let mut paths = Vec::with_capacity(files.len());
+    for (file_name, contents) in files {
+        let path = directory.as_ref().join(file_name);
+        tokio::fs::write(&path, contents)
+            .await
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// Exports the year in review of all the watched series
+///
+/// Uses the most recent year with watched episodes when no year is given.
+pub fn blocking_export(
+    year: Option<i32>,
+    directory: impl AsRef<path::Path>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    tokio::runtime::Runtime::new()?.block_on(async {
+        let watched_series = statistics::get_watched_series().await;
+        let year = year
+            .or_else(|| get_years(&watched_series).first().copied())
+            .unwrap_or_else(|| chrono::Local::now().year());
+        export(watched_series, year, directory).await
+    })
+}
+
+fn render_png(svg: &str) -> anyhow::Result<Vec<u8>> {
+    let mut options = resvg::usvg::Options::default();
+    let fontdb = options.fontdb_mut();
+    fontdb.load_system_fonts();
+    // The bundled font makes the image look the same whatever fonts the system has
+    fontdb.load_font_data(fonts::NOTOSANS_REGULAR_STATIC.to_vec());
+    fontdb.set_sans_serif_family("Noto Sans");
+
+    let tree = resvg::usvg::Tree::from_str(svg, &options).context("failed to parse the svg")?;
+    let size = tree.size().to_int_size();
+
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(size.width(), size.height())
+        .context("failed to create the png canvas")?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::default(),
+        &mut pixmap.as_mut(),
+    );
+
+    pixmap.encode_png().context("failed to encode the png")
+}
+
+fn top_show_details(top_show: &TopShow) -> String {
+    format!(
+        "{} episodes, {} hours",
+        top_show.episodes,
+        top_show.minutes / 60
+    )
+}
+
+fn image_data_uri(image_bytes: &[u8]) -> String {
+    let mime_type = image::guess_format(image_bytes)
+        .map(|format| format.to_mime_type())
+        .unwrap_or("image/jpeg");
+
+    format!(
+        "data:{};base64,{}",
+        mime_type,
+        base64::engine::general_purpose::STANDARD.encode(image_bytes)
+    )
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::api::tv_maze::test_fixtures;
+    use crate::core::statistics::EpisodeWatch;
+    use chrono::NaiveDate;
+
+    fn watched_series(id: u32, status: &str, watch_years: &[i32]) -> WatchedSeries {
+        let mut series_info = test_fixtures::series_info(id);
+        series_info.name = format!("Series & {}", id);
+        series_info.genres = vec!["Drama".to_owned()];
+        series_info.status = status.to_owned();
+
+        WatchedSeries {
+            series_info,
+            is_tracked: true,
+            watch_time: Some(watch_years.len() as u32 * 60),
+            watched_episodes: watch_years.len(),
+            watchable_episodes: Some(watch_years.len()),
+            episode_watches: watch_years
+                .iter()
+                .map(|year| EpisodeWatch {
+                    date: NaiveDate::from_ymd_opt(*year, 6, 1).unwrap(),
+                    runtime: 60,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn year_in_review_test() {
+        let watched_series = [
+            watched_series(1, "Ended", &[2023, 2024, 2024]),
+            watched_series(2, "Running", &[2024]),
+            watched_series(3, "Ended", &[2023]),
+        ];
+
+        let year_in_review = YearInReview::new(&watched_series, 2024);
+        assert_eq!(year_in_review.episodes, 3);
+        assert_eq!(year_in_review.hours(), 3);
+        assert_eq!(year_in_review.shows_started, 1);
+        assert_eq!(year_in_review.shows_completed, 1);
+        assert_eq!(year_in_review.top_shows[0].series_info.id, 1);
+        assert_eq!(year_in_review.top_genres, vec![(Genre::Drama, 2)]);
+
+        let svg = year_in_review.svg_str(&[]);
+        assert!(svg.contains("Series &amp; 1"));
+        assert!(render_png(&svg).unwrap().starts_with(b"\x89PNG"));
+        assert_eq!(get_years(&watched_series), vec![2024, 2023]);
+    }
+}
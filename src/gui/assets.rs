@@ -2,6 +2,7 @@ pub mod icons {
     pub static BINOCULARS_FILL: &[u8] = include_bytes!("../../assets/icons/binoculars-fill.svg");
     pub static CARD_CHECKLIST: &[u8] = include_bytes!("../../assets/icons/card-checklist.svg");
     pub static FILM: &[u8] = include_bytes!("../../assets/icons/film.svg");
+    pub static CALENDAR3: &[u8] = include_bytes!("../../assets/icons/calendar3.svg");
     pub static GRAPH_UP_ARROW: &[u8] = include_bytes!("../../assets/icons/graph-up-arrow.svg");
     pub static GEAR_WIDE_CONNECTED: &[u8] =
         include_bytes!("../../assets/icons/gear-wide-connected.svg");
@@ -35,6 +35,28 @@ pub fn genres_with_pipes(genres: &[String]) -> String {
     genres_string
 }
 
+/// Generates a human readable string of the given size in bytes
+///
+/// 1536 bytes will generate 1.5 KB
+pub fn readable_size(size_in_bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if size_in_bytes < 1024 {
+        return format!("{} B", size_in_bytes);
+    }
+
+    let mut size = size_in_bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next_unit;
+    }
+    format!("{:.1} {}", size, unit)
+}
+
 pub mod time {
     //! Time related helpers
     use chrono::Duration;
@@ -251,3 +273,16 @@ pub mod empty_image {
         svg(icon_handle)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::readable_size;
+
+    #[test]
+    fn readable_size_test() {
+        assert_eq!(readable_size(512), "512 B");
+        assert_eq!(readable_size(1536), "1.5 KB");
+        assert_eq!(readable_size(500 * 1024 * 1024), "500.0 MB");
+        assert_eq!(readable_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
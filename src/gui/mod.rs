@@ -1,3 +1,4 @@
+use crate::core::caching::fetch_limiter::FetchProgress;
 use crate::core::settings_config::{self, SETTINGS};
 use iced::window::Id;
 use iced::Task;
@@ -6,7 +7,7 @@ use std::sync::{mpsc, Arc};
 
 use series_page::{Message as SeriesPageControllerMessage, SeriesPageController};
 use tabs::{Message as TabsControllerMessage, TabId, TabsController};
-use troxide_widget::title_bar::{Message as TitleBarMessage, TitleBar};
+use troxide_widget::title_bar::{ConnectionState, Message as TitleBarMessage, TitleBar};
 
 pub mod assets;
 pub mod helpers;
@@ -22,6 +23,8 @@ pub enum Message {
     SeriesPageController(SeriesPageControllerMessage),
     TabsController(TabsControllerMessage),
     FontLoaded(Result<(), iced::font::Error>),
+    FetchProgress(FetchProgress),
+    ConnectionState(ConnectionState),
 }
 
 pub struct TroxideGui<'a> {
@@ -29,6 +32,8 @@ pub struct TroxideGui<'a> {
     title_bar: TitleBar,
     tabs_controller: TabsController<'a>,
     series_page_controller: SeriesPageController<'a>,
+    fetch_progress: FetchProgress,
+    connection_state: ConnectionState,
 }
 
 impl TroxideGui<'_> {
@@ -51,6 +56,8 @@ impl TroxideGui<'_> {
                 title_bar: TitleBar::new(),
                 tabs_controller,
                 series_page_controller: SeriesPageController::new(sender, receiver),
+                fetch_progress: FetchProgress::default(),
+                connection_state: ConnectionState::default(),
             },
             Task::batch([
                 noto_font_command.map(Message::FontLoaded),
@@ -102,9 +109,15 @@ impl TroxideGui<'_> {
     }
 
     pub fn subscription(&self) -> iced::Subscription<Message> {
-        self.tabs_controller
-            .subscription()
-            .map(Message::TabsController)
+        iced::Subscription::batch([
+            self.tabs_controller
+                .subscription()
+                .map(Message::TabsController),
+            iced::Subscription::run(troxide_widget::title_bar::fetch_progress_watcher)
+                .map(Message::FetchProgress),
+            iced::Subscription::run(troxide_widget::title_bar::connection_state_watcher)
+                .map(Message::ConnectionState),
+        ])
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
@@ -127,6 +140,14 @@ impl TroxideGui<'_> {
                 }
                 Task::none()
             }
+            Message::FetchProgress(fetch_progress) => {
+                self.fetch_progress = fetch_progress;
+                Task::none()
+            }
+            Message::ConnectionState(connection_state) => {
+                self.connection_state = connection_state;
+                Task::none()
+            }
             Message::TitleBar(message) => {
                 self.title_bar.update(message.clone());
                 match message {
@@ -159,7 +180,7 @@ impl TroxideGui<'_> {
         }
     }
 
-    pub fn view(&self) -> iced::Element<Message> {
+    pub fn view(&self) -> iced::Element<'_, Message> {
         let view = if let Some(series_page_view) = self.series_page_controller.view() {
             series_page_view.map(Message::SeriesPageController)
         } else {
@@ -170,7 +191,9 @@ impl TroxideGui<'_> {
             self.title_bar
                 .view(
                     &self.tabs_controller.get_labels(),
-                    self.series_page_controller.has_a_series_page()
+                    self.series_page_controller.has_a_series_page(),
+                    self.fetch_progress,
+                    self.connection_state,
                 )
                 .map(Message::TitleBar),
             view
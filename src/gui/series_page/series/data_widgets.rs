@@ -3,7 +3,10 @@ use bytes::Bytes;
 use super::Message;
 use crate::core::api::tv_maze::episodes_information::Episode;
 use crate::core::api::tv_maze::series_information::{SeriesMainInformation, ShowStatus};
-use crate::core::database;
+use crate::core::caching::offline_availability::OfflineAvailability;
+use crate::core::database::{self, NotificationRule};
+use crate::core::date_time_display::DateTimeDisplay;
+use crate::core::offline::OFFLINE;
 use crate::gui::assets::icons::{
     CLOCK_FILL, PATCH_PLUS, PATCH_PLUS_FILL, STAR, STAR_FILL, STAR_HALF,
 };
@@ -11,16 +14,19 @@ use crate::gui::helpers::{self, season_episode_str_gen};
 use crate::gui::styles;
 
 use iced::widget::{
-    button, column, container, horizontal_rule, horizontal_space, row, svg, text, Button, Space,
+    button, column, container, horizontal_rule, horizontal_space, row, svg, text, toggler, Button,
+    Space,
 };
 use iced::{Alignment, Element, Length};
-use iced_aw::{Grid, GridRow};
+use iced_aw::{Grid, GridRow, NumberInput};
 
 /// Generates the Series Metadata
 pub fn series_metadata<'a>(
     series_information: &'a SeriesMainInformation,
     image_bytes: Option<Bytes>,
     next_episode_to_air: Option<&Episode>,
+    offline_availability: Option<&'a Result<OfflineAvailability, String>>,
+    making_available_offline: bool,
 ) -> Element<'a, Message> {
     let mut main_info = row!().padding(5).spacing(10);
 
@@ -54,8 +60,11 @@ pub fn series_metadata<'a>(
 
     let title_bar = row![
         series_name.width(Length::FillPortion(10)),
+        offline_availability_widget(offline_availability, making_available_offline),
         tracking_button(series_information.id)
-    ];
+    ]
+    .spacing(5)
+    .align_y(Alignment::Center);
 
     let next_episode_widget = next_episode_to_air_widget(next_episode_to_air);
 
@@ -67,6 +76,7 @@ pub fn series_metadata<'a>(
         rating_and_release_widget,
         horizontal_rule(1),
         data_grid,
+        notification_rule_widget(series_information.id),
         Space::with_height(10),
     ]
     .width(700)
@@ -113,6 +123,132 @@ pub fn background(
     }
 }
 
+pub fn offline_availability_widget(
+    offline_availability: Option<&Result<OfflineAvailability, String>>,
+    making_available_offline: bool,
+) -> Element<'static, Message> {
+    if making_available_offline {
+        return text("making available offline...").size(11).into();
+    }
+
+    match offline_availability {
+        Some(Ok(offline_availability)) => {
+            let mut availability_text = format!(
+                "Available offline, {}",
+                helpers::readable_size(offline_availability.disk_size)
+            );
+            if offline_availability.failed_images != 0 {
+                availability_text.push_str(&format!(
+                    ", {} images failed",
+                    offline_availability.failed_images
+                ));
+            }
+            text(availability_text)
+                .size(11)
+                .style(styles::text_styles::green_text_theme)
+                .into()
+        }
+        Some(Err(err)) => text(err.clone())
+            .size(11)
+            .style(styles::text_styles::red_text_theme)
+            .into(),
+        None => button(text("Make available offline").size(11))
+            .on_press_maybe((!OFFLINE.is_offline()).then_some(Message::MakeAvailableOffline))
+            .into(),
+    }
+}
+
+/// The notification overrides of the series, only displayed when the series is tracked
+pub fn notification_rule_widget(series_id: u32) -> Element<'static, Message> {
+    let is_tracked = database::DB
+        .get_series(series_id)
+        .map(|series| series.is_tracked())
+        .unwrap_or(false);
+
+    if !is_tracked {
+        return Space::new(0, 0).into();
+    }
+
+    let notification_rule = database::DB.get_notification_rule(series_id);
+
+    let mute_toggler = toggler(notification_rule.muted)
+        .label("Mute")
+        .on_toggle(move |muted| {
+            Message::NotificationRuleChanged(NotificationRule {
+                muted,
+                ..notification_rule
+            })
+        })
+        .text_size(11)
+        .width(Length::Shrink);
+
+    let premieres_and_finales_toggler = toggler(notification_rule.only_premieres_and_finales)
+        .label("Only premieres and finales")
+        .on_toggle(move |only_premieres_and_finales| {
+            Message::NotificationRuleChanged(NotificationRule {
+                only_premieres_and_finales,
+                ..notification_rule
+            })
+        })
+        .text_size(11)
+        .width(Length::Shrink);
+
+    // Starting the custom time from the first of the global ones
+    let global_time_to_notify = crate::core::settings_config::SETTINGS
+        .read()
+        .unwrap()
+        .get_current_settings()
+        .notifications
+        .times_to_notify
+        .first()
+        .copied()
+        .unwrap_or(60);
+
+    let custom_time_toggler = toggler(notification_rule.time_to_notify.is_some())
+        .label("Custom time to notify")
+        .on_toggle(move |is_custom| {
+            Message::NotificationRuleChanged(NotificationRule {
+                time_to_notify: is_custom.then_some(global_time_to_notify),
+                ..notification_rule
+            })
+        })
+        .text_size(11)
+        .width(Length::Shrink);
+
+    let custom_time_input: Element<'_, Message> =
+        if let Some(time_to_notify) = notification_rule.time_to_notify {
+            row![
+                NumberInput::new(time_to_notify, u32::MIN..u32::MAX, move |time_to_notify| {
+                    Message::NotificationRuleChanged(NotificationRule {
+                        time_to_notify: Some(time_to_notify),
+                        ..notification_rule
+                    })
+                })
+                .width(Length::Fixed(120.0)),
+                text("minutes before release").size(11),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center)
+            .into()
+        } else {
+            Space::new(0, 0).into()
+        };
+
+    column![
+        text("Notifications").size(11),
+        row![
+            mute_toggler,
+            premieres_and_finales_toggler,
+            custom_time_toggler,
+            custom_time_input,
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center),
+    ]
+    .spacing(5)
+    .into()
+}
+
 pub fn tracking_button(series_id: u32) -> Button<'static, Message> {
     if database::DB
         .get_series(series_id)
@@ -214,7 +350,7 @@ pub fn premiered_widget<'b>(
 ) -> Grid<'b, Message> {
     let title_text = text("Premiered");
     let body_text = if let Some(premier) = &series_info.premiered {
-        text(premier.clone())
+        text(DateTimeDisplay::from_settings().date_str(premier))
     } else {
         text("unavailable")
     };
@@ -229,7 +365,7 @@ pub fn ended_widget<'b>(
     if let ShowStatus::Ended = series_info.get_status() {
         let title_text = text("Ended");
         let body_text = if let Some(ended) = &series_info.ended {
-            text(ended.clone())
+            text(DateTimeDisplay::from_settings().date_str(ended))
         } else {
             text("unavailable")
         };
@@ -5,6 +5,7 @@ use image;
 
 use crate::core::api::tv_maze::series_information::SeriesMainInformation;
 use crate::core::api::tv_maze::Image;
+use crate::core::caching::offline_availability::{self, OfflineAvailability};
 use crate::core::{caching, database};
 use crate::gui::styles;
 use data_widgets::*;
@@ -32,6 +33,9 @@ pub enum Message {
     PageScrolled(Viewport),
     TrackSeries,
     UntrackSeries,
+    NotificationRuleChanged(database::NotificationRule),
+    MakeAvailableOffline,
+    MadeAvailableOffline(Result<OfflineAvailability, String>),
 }
 
 pub struct Series<'a> {
@@ -45,6 +49,8 @@ pub struct Series<'a> {
     series_suggestion_widget: SeriesSuggestion<'a>,
     scroll_offset: RelativeOffset,
     scroller_id: Id,
+    making_available_offline: bool,
+    offline_availability: Option<Result<OfflineAvailability, String>>,
 }
 
 impl Series<'_> {
@@ -76,6 +82,8 @@ impl Series<'_> {
             series_suggestion_widget,
             scroll_offset: RelativeOffset::default(),
             scroller_id: scroller_id.clone(),
+            making_available_offline: false,
+            offline_availability: None,
         };
 
         let scroller_command = scrollable::snap_to(scroller_id, RelativeOffset::START);
@@ -144,6 +152,20 @@ impl Series<'_> {
                     series.mark_untracked();
                 }
             }
+            Message::NotificationRuleChanged(notification_rule) => {
+                database::DB.set_notification_rule(self.series_id, notification_rule);
+            }
+            Message::MakeAvailableOffline => {
+                self.making_available_offline = true;
+                return Task::perform(
+                    offline_availability::make_series_available_offline(self.series_id),
+                    |result| Message::MadeAvailableOffline(result.map_err(|err| err.to_string())),
+                );
+            }
+            Message::MadeAvailableOffline(offline_availability) => {
+                self.making_available_offline = false;
+                self.offline_availability = Some(offline_availability);
+            }
             Message::SeriesBackgroundLoaded(background) => self.series_background = background,
             Message::SeriesSuggestion(message) => {
                 return self
@@ -174,6 +196,8 @@ impl Series<'_> {
             &self.series_information,
             self.series_image.clone(),
             self.seasons.get_next_episode_to_air(),
+            self.offline_availability.as_ref(),
+            self.making_available_offline,
         );
 
         let seasons_widget = self.seasons.view().map(Message::Seasons);
@@ -364,7 +364,10 @@ mod cast_poster {
         fn load_person_image(image: Option<Image>) -> Task<Message> {
             if let Some(image) = image {
                 Task::perform(
-                    caching::load_image(image.medium_image_url, caching::ImageResolution::Medium),
+                    caching::load_image(
+                        image.medium_image_url,
+                        caching::ImageResolution::Medium(caching::ImageKind::CastAvatar),
+                    ),
                     Message::PersonImageLoaded,
                 )
             } else {
@@ -375,7 +378,10 @@ mod cast_poster {
         fn load_character_image(image: Option<Image>) -> Task<Message> {
             if let Some(image) = image {
                 Task::perform(
-                    caching::load_image(image.medium_image_url, caching::ImageResolution::Medium),
+                    caching::load_image(
+                        image.medium_image_url,
+                        caching::ImageResolution::Medium(caching::ImageKind::CastAvatar),
+                    ),
                     Message::CharacterImageLoaded,
                 )
             } else {
@@ -294,7 +294,10 @@ mod crew_poster {
         fn load_person_image(image: Option<Image>) -> Task<Message> {
             if let Some(image) = image {
                 Task::perform(
-                    caching::load_image(image.medium_image_url, caching::ImageResolution::Medium),
+                    caching::load_image(
+                        image.medium_image_url,
+                        caching::ImageResolution::Medium(caching::ImageKind::CastAvatar),
+                    ),
                     Message::PersonImageLoaded,
                 )
             } else {
@@ -0,0 +1,558 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::mpsc;
+
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate};
+use iced::widget::scrollable::{RelativeOffset, Viewport};
+use iced::widget::{
+    button, column, container, horizontal_space, row, scrollable, text, toggler, Column, Row,
+};
+use iced::{Element, Length, Task};
+use iced_aw::{Spinner, Wrap};
+
+use super::Tab;
+use crate::core::api::tv_maze::episodes_information::Episode;
+use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+use crate::core::caching::episode_list::EpisodeList;
+use crate::core::caching::fetch_limiter::FETCH_LIMITER;
+use crate::core::caching::{series_list, tv_schedule};
+use crate::core::date_time_display::DateTimeDisplay;
+use crate::gui::assets::icons::CALENDAR3;
+use crate::gui::helpers::season_episode_str_gen;
+use crate::gui::styles;
+
+/// The most entries displayed in a day of the month grid, the rest are only counted
+const MAX_MONTH_DAY_ENTRIES: usize = 3;
+
+const WEEKDAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewMode {
+    Month,
+    Week,
+}
+
+/// An episode of a tracked series placed on it's air date
+#[derive(Debug, Clone)]
+pub struct CalendarEntry {
+    series_id: u32,
+    series_name: String,
+    season: u32,
+    episode_number: Option<u32>,
+    episode_name: String,
+    release_time: DateTime<Local>,
+}
+
+impl CalendarEntry {
+    fn new(series_info: &SeriesMainInformation, episode: &Episode) -> Option<Self> {
+        Some(Self {
+            series_id: series_info.id,
+            series_name: series_info.name.clone(),
+            season: episode.season,
+            episode_number: episode.number,
+            episode_name: episode.name.clone(),
+            release_time: episode.local_date_time().ok()?,
+        })
+    }
+
+    fn title(&self) -> String {
+        match self.episode_number {
+            Some(episode_number) => format!(
+                "{} {}",
+                self.series_name,
+                season_episode_str_gen(self.season, episode_number)
+            ),
+            None => format!("{} (special)", self.series_name),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    TrackedEpisodesLoaded(Vec<SeriesMainInformation>, Vec<CalendarEntry>),
+    ScheduleLoaded(NaiveDate, Vec<SeriesMainInformation>),
+    ViewModeSelected(ViewMode),
+    PreviousPressed,
+    NextPressed,
+    TodayPressed,
+    DayPressed(NaiveDate),
+    ScheduleOverlayToggled(bool),
+    SeriesPressed(u32),
+    PageScrolled(Viewport),
+}
+
+#[derive(Default)]
+enum LoadState {
+    #[default]
+    Loading,
+    Loaded,
+}
+
+pub struct CalendarTab {
+    load_state: LoadState,
+    view_mode: ViewMode,
+    /// A day of the month or week being viewed
+    focused_date: NaiveDate,
+    /// The episodes of the tracked series by their air dates, sorted by their release times
+    tracked_entries: BTreeMap<NaiveDate, Vec<CalendarEntry>>,
+    show_schedule: bool,
+    /// The series of the schedule by their air dates, for the dates loaded so far
+    schedule: HashMap<NaiveDate, Vec<SeriesMainInformation>>,
+    loading_schedule_dates: HashSet<NaiveDate>,
+    /// The series that can be opened from the calendar
+    series_infos: HashMap<u32, SeriesMainInformation>,
+    series_page_sender: mpsc::Sender<SeriesMainInformation>,
+    scrollable_offset: RelativeOffset,
+}
+
+impl CalendarTab {
+    pub fn new(
+        series_page_sender: mpsc::Sender<SeriesMainInformation>,
+        scrollable_offset: Option<RelativeOffset>,
+    ) -> (Self, Task<Message>) {
+        (
+            Self {
+                load_state: LoadState::default(),
+                view_mode: ViewMode::Month,
+                focused_date: DateTimeDisplay::from_settings().today(),
+                tracked_entries: BTreeMap::new(),
+                show_schedule: false,
+                schedule: HashMap::new(),
+                loading_schedule_dates: HashSet::new(),
+                series_infos: HashMap::new(),
+                series_page_sender,
+                scrollable_offset: scrollable_offset.unwrap_or(RelativeOffset::START),
+            },
+            Task::perform(load_tracked_episodes(), |(series_infos, entries)| {
+                Message::TrackedEpisodesLoaded(series_infos, entries)
+            }),
+        )
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::TrackedEpisodesLoaded(series_infos, entries) => {
+                self.load_state = LoadState::Loaded;
+
+                self.series_infos.extend(
+                    series_infos
+                        .into_iter()
+                        .map(|series_info| (series_info.id, series_info)),
+                );
+
+                let date_time_display = DateTimeDisplay::from_settings();
+
+                self.tracked_entries.clear();
+                for entry in entries {
+                    self.tracked_entries
+                        .entry(date_time_display.zoned(&entry.release_time).date_naive())
+                        .or_default()
+                        .push(entry);
+                }
+                self.tracked_entries
+                    .values_mut()
+                    .for_each(|entries| entries.sort_by_key(|entry| entry.release_time));
+
+                Task::none()
+            }
+            Message::ScheduleLoaded(date, series_infos) => {
+                self.loading_schedule_dates.remove(&date);
+
+                for series_info in series_infos.iter() {
+                    self.series_infos
+                        .entry(series_info.id)
+                        .or_insert_with(|| series_info.clone());
+                }
+                self.schedule.insert(date, series_infos);
+
+                Task::none()
+            }
+            Message::ViewModeSelected(view_mode) => {
+                self.view_mode = view_mode;
+                self.load_visible_schedule()
+            }
+            Message::PreviousPressed => {
+                self.focused_date = match self.view_mode {
+                    ViewMode::Month => first_of_month(self.focused_date)
+                        .checked_sub_months(Months::new(1))
+                        .unwrap_or(self.focused_date),
+                    ViewMode::Week => self.focused_date - Duration::weeks(1),
+                };
+                self.load_visible_schedule()
+            }
+            Message::NextPressed => {
+                self.focused_date = match self.view_mode {
+                    ViewMode::Month => first_of_month(self.focused_date)
+                        .checked_add_months(Months::new(1))
+                        .unwrap_or(self.focused_date),
+                    ViewMode::Week => self.focused_date + Duration::weeks(1),
+                };
+                self.load_visible_schedule()
+            }
+            Message::TodayPressed => {
+                self.focused_date = DateTimeDisplay::from_settings().today();
+                self.load_visible_schedule()
+            }
+            Message::DayPressed(date) => {
+                self.focused_date = date;
+                self.view_mode = ViewMode::Week;
+                self.load_visible_schedule()
+            }
+            Message::ScheduleOverlayToggled(show_schedule) => {
+                self.show_schedule = show_schedule;
+                self.load_visible_schedule()
+            }
+            Message::SeriesPressed(series_id) => {
+                if let Some(series_info) = self.series_infos.get(&series_id) {
+                    self.series_page_sender
+                        .send(series_info.clone())
+                        .expect("failed to send series page info");
+                }
+                Task::none()
+            }
+            Message::PageScrolled(view_port) => {
+                self.scrollable_offset = view_port.relative_offset();
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        if let LoadState::Loading = self.load_state {
+            return container(Spinner::new()).center(Length::Fill).into();
+        }
+
+        let calendar = match self.view_mode {
+            ViewMode::Month => self.month_view(),
+            ViewMode::Week => self.week_view(),
+        };
+
+        let content = scrollable(container(calendar).padding(10))
+            .direction(styles::scrollable_styles::vertical_direction())
+            .id(Self::scrollable_id())
+            .on_scroll(Message::PageScrolled);
+
+        column![self.header(), content]
+            .spacing(10)
+            .padding(5)
+            .into()
+    }
+
+    fn header(&self) -> Element<'_, Message> {
+        let title = match self.view_mode {
+            ViewMode::Month => self.focused_date.format("%B %Y").to_string(),
+            ViewMode::Week => {
+                let week_start = week_start(self.focused_date);
+                format!(
+                    "{} - {}",
+                    week_start.format("%b %-d"),
+                    (week_start + Duration::days(6)).format("%b %-d, %Y")
+                )
+            }
+        };
+
+        let view_mode_button = |label, view_mode| {
+            let view_mode_button = button(label).on_press(Message::ViewModeSelected(view_mode));
+            if self.view_mode == view_mode {
+                view_mode_button
+            } else {
+                view_mode_button
+                    .style(styles::button_styles::transparent_button_with_rounded_border_theme)
+            }
+        };
+
+        row![
+            button("<").on_press(Message::PreviousPressed),
+            button("Today").on_press(Message::TodayPressed),
+            button(">").on_press(Message::NextPressed),
+            text(title)
+                .size(21)
+                .style(styles::text_styles::accent_color_theme),
+            horizontal_space(),
+            toggler(self.show_schedule)
+                .label("Show schedule")
+                .on_toggle(Message::ScheduleOverlayToggled)
+                .spacing(10)
+                .width(Length::Shrink),
+            view_mode_button("Month", ViewMode::Month),
+            view_mode_button("Week", ViewMode::Week),
+        ]
+        .spacing(10)
+        .align_y(iced::Alignment::Center)
+        .into()
+    }
+
+    fn month_view(&self) -> Element<'_, Message> {
+        let weekday_names = Row::with_children(WEEKDAY_NAMES.iter().map(|weekday_name| {
+            container(text(*weekday_name).size(12))
+                .center_x(Length::Fill)
+                .into()
+        }))
+        .spacing(2);
+
+        let visible_dates = self.visible_dates();
+        let weeks = visible_dates.chunks(7).map(|week| {
+            Row::with_children(week.iter().map(|date| self.month_day(*date)))
+                .spacing(2)
+                .into()
+        });
+
+        column![weekday_names, Column::with_children(weeks).spacing(2)]
+            .spacing(5)
+            .into()
+    }
+
+    fn month_day(&self, date: NaiveDate) -> Element<'_, Message> {
+        let tracked_entries = self.get_tracked_entries(date);
+        let scheduled_series = self.get_scheduled_series(date);
+
+        let mut day_number = text(date.day()).size(12);
+        if date == DateTimeDisplay::from_settings().today() {
+            day_number = day_number.style(styles::text_styles::accent_color_theme);
+        }
+
+        let mut content = column![button(day_number)
+            .on_press(Message::DayPressed(date))
+            .padding(0)
+            .style(styles::button_styles::transparent_button_theme)]
+        .spacing(2);
+
+        for entry in tracked_entries.iter().take(MAX_MONTH_DAY_ENTRIES) {
+            content = content.push(entry_button(entry.title(), entry.series_id, true));
+        }
+
+        for series_info in scheduled_series
+            .iter()
+            .take(MAX_MONTH_DAY_ENTRIES.saturating_sub(tracked_entries.len()))
+        {
+            content = content.push(entry_button(
+                series_info.name.clone(),
+                series_info.id,
+                false,
+            ));
+        }
+
+        let total_entries = tracked_entries.len() + scheduled_series.len();
+        if total_entries > MAX_MONTH_DAY_ENTRIES {
+            content = content
+                .push(text(format!("+{} more", total_entries - MAX_MONTH_DAY_ENTRIES)).size(10));
+        }
+
+        let mut day = container(content)
+            .padding(3)
+            .width(Length::Fill)
+            .height(110)
+            .clip(true);
+
+        // Days of the neighbouring months filling the grid are left unstyled
+        if date.month() == self.focused_date.month() {
+            day = day.style(styles::container_styles::first_class_container_square_theme);
+        }
+
+        day.into()
+    }
+
+    fn week_view(&self) -> Element<'_, Message> {
+        Column::with_children(
+            self.visible_dates()
+                .into_iter()
+                .map(|date| self.week_day(date)),
+        )
+        .spacing(5)
+        .into()
+    }
+
+    fn week_day(&self, date: NaiveDate) -> Element<'_, Message> {
+        let tracked_entries = self.get_tracked_entries(date);
+        let scheduled_series = self.get_scheduled_series(date);
+
+        let mut heading = text(date.format("%A, %B %-d").to_string()).size(16);
+        if date == DateTimeDisplay::from_settings().today() {
+            heading = heading.style(styles::text_styles::accent_color_theme);
+        }
+
+        let mut content = column![heading].spacing(5);
+
+        if tracked_entries.is_empty() && scheduled_series.is_empty() {
+            content = content.push(text("Nothing airing").size(11));
+        }
+
+        for entry in tracked_entries {
+            content = content.push(
+                row![
+                    text(DateTimeDisplay::from_settings().time(&entry.release_time))
+                        .size(12)
+                        .width(50),
+                    entry_button(
+                        format!("{}: {}", entry.title(), entry.episode_name),
+                        entry.series_id,
+                        true
+                    ),
+                ]
+                .align_y(iced::Alignment::Center),
+            );
+        }
+
+        if !scheduled_series.is_empty() {
+            let scheduled_series = scheduled_series
+                .iter()
+                .map(|series_info| entry_button(series_info.name.clone(), series_info.id, false))
+                .collect();
+
+            content = content
+                .push(text("Also airing").size(12))
+                .push(Wrap::with_elements(scheduled_series).spacing(10.0));
+        }
+
+        container(content)
+            .padding(10)
+            .width(Length::Fill)
+            .style(styles::container_styles::first_class_container_rounded_theme)
+            .into()
+    }
+
+    fn get_tracked_entries(&self, date: NaiveDate) -> &[CalendarEntry] {
+        self.tracked_entries
+            .get(&date)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The series of the schedule aired on the date, empty when the schedule is not shown
+    fn get_scheduled_series(&self, date: NaiveDate) -> &[SeriesMainInformation] {
+        if !self.show_schedule {
+            return &[];
+        }
+
+        self.schedule
+            .get(&date)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The dates displayed by the current view mode
+    ///
+    /// The month grid is made of the six weeks starting from the week of the first day of the month.
+    fn visible_dates(&self) -> Vec<NaiveDate> {
+        let (first_date, days) = match self.view_mode {
+            ViewMode::Month => (week_start(first_of_month(self.focused_date)), 42),
+            ViewMode::Week => (week_start(self.focused_date), 7),
+        };
+
+        (0..days)
+            .map(|day| first_date + Duration::days(day))
+            .collect()
+    }
+
+    /// Loads the schedule of the visible dates that have not been loaded yet
+    ///
+    /// The dates are loaded one after the other to be gentle with the rate limit of the api.
+    fn load_visible_schedule(&mut self) -> Task<Message> {
+        if !self.show_schedule {
+            return Task::none();
+        }
+
+        let dates: Vec<NaiveDate> = self
+            .visible_dates()
+            .into_iter()
+            .filter(|date| {
+                !self.schedule.contains_key(date) && !self.loading_schedule_dates.contains(date)
+            })
+            .collect();
+
+        self.loading_schedule_dates.extend(dates.iter().copied());
+
+        dates
+            .into_iter()
+            .map(|date| {
+                Task::perform(load_schedule(date), move |series_infos| {
+                    Message::ScheduleLoaded(date, series_infos)
+                })
+            })
+            .reduce(Task::chain)
+            .unwrap_or_else(Task::none)
+    }
+}
+
+impl Tab for CalendarTab {
+    type Message = Message;
+
+    fn title() -> &'static str {
+        "Calendar"
+    }
+
+    fn icon_bytes() -> &'static [u8] {
+        CALENDAR3
+    }
+
+    fn get_scrollable_offset(&self) -> scrollable::RelativeOffset {
+        self.scrollable_offset
+    }
+}
+
+/// A button opening the series page, entries of tracked series being highlighted
+fn entry_button(label: String, series_id: u32, is_tracked: bool) -> Element<'static, Message> {
+    let mut label = text(label).size(11);
+    if is_tracked {
+        label = label.style(styles::text_styles::accent_color_theme);
+    }
+
+    button(label)
+        .on_press(Message::SeriesPressed(series_id))
+        .padding(0)
+        .style(styles::button_styles::transparent_button_theme)
+        .into()
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1)
+        .expect("the first day should exist in every month")
+}
+
+/// The monday of the week of the date
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Loads all the episodes of the tracked series that have an air date
+async fn load_tracked_episodes() -> (Vec<SeriesMainInformation>, Vec<CalendarEntry>) {
+    let series_infos = series_list::SeriesList::new()
+        .get_tracked_series_information()
+        .await
+        .unwrap_or_else(|err| {
+            tracing::error!("failed to get tracked series for the calendar: {}", err);
+            vec![]
+        });
+
+    let handles: Vec<_> = series_infos
+        .iter()
+        .map(|series_info| FETCH_LIMITER.spawn(EpisodeList::new(series_info.id)))
+        .collect();
+
+    let mut entries = vec![];
+    for (handle, series_info) in handles.into_iter().zip(series_infos.iter()) {
+        match handle.await.expect("failed to join episode list handle") {
+            Ok(episode_list) => entries.extend(
+                episode_list
+                    .get_all_episodes()
+                    .iter()
+                    .filter_map(|episode| CalendarEntry::new(series_info, episode)),
+            ),
+            Err(err) => tracing::error!(
+                "failed to get the episode list of \"{}\" for the calendar: {}",
+                series_info.name,
+                err
+            ),
+        }
+    }
+
+    (series_infos, entries)
+}
+
+async fn load_schedule(date: NaiveDate) -> Vec<SeriesMainInformation> {
+    tv_schedule::get_series_with_date(Some(&date.format("%Y-%m-%d").to_string()))
+        .await
+        .unwrap_or_else(|err| {
+            tracing::error!("failed to get the schedule of {}: {}", date, err);
+            vec![]
+        })
+}
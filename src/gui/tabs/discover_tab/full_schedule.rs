@@ -463,7 +463,7 @@ where
         let mut posters = Vec::with_capacity(series_infos.len());
         let mut posters_commands = Vec::with_capacity(series_infos.len());
 
-        for (index, series_info) in range.clone().zip(series_infos.into_iter()) {
+        for (index, series_info) in range.clone().zip(series_infos) {
             let (poster, command) = SeriesPoster::new(
                 index,
                 std::borrow::Cow::Borrowed(series_info),
@@ -475,7 +475,7 @@ where
         (posters, posters_commands)
     }
 
-    fn get_section(&self, section_id: &T) -> &[SeriesPoster] {
+    fn get_section(&self, section_id: &T) -> &[SeriesPoster<'_>] {
         let range = self
             .index
             .get(section_id)
@@ -488,7 +488,7 @@ where
         &self,
         section_id: &T,
         message: fn(IndexedMessage<usize, SeriesPosterMessage>) -> Message,
-    ) -> Element<Message>
+    ) -> Element<'_, Message>
     where
         T: std::fmt::Display,
     {
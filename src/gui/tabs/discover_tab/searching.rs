@@ -7,6 +7,7 @@ use search_result::{IndexedMessage, Message as SearchResultMessage, SearchResult
 
 use crate::core::api::tv_maze::series_information::SeriesMainInformation;
 use crate::core::api::tv_maze::series_searching;
+use crate::core::offline::OFFLINE;
 use crate::gui::styles;
 
 #[derive(Default, Eq, PartialEq)]
@@ -133,12 +134,22 @@ impl Search {
     }
 
     pub fn view(&self) -> (Element<'_, Message>, Option<Element<'_, Message>>) {
+        let is_offline = OFFLINE.is_offline();
         let search_bar = column!(
             Space::with_height(10),
-            text_input("Search", &self.search_term)
-                .width(300)
-                .on_input_maybe((!self.load_state.is_loading()).then_some(Message::TermChanged))
-                .on_submit(Message::TermSearched)
+            text_input(
+                if is_offline {
+                    "Search is unavailable offline"
+                } else {
+                    "Search"
+                },
+                &self.search_term
+            )
+            .width(300)
+            .on_input_maybe(
+                (!self.load_state.is_loading() && !is_offline).then_some(Message::TermChanged)
+            )
+            .on_submit(Message::TermSearched)
         )
         .width(Length::Fill)
         .align_x(iced::Alignment::Center);
@@ -200,6 +211,7 @@ mod search_result {
 
     use crate::core::api::tv_maze::series_information::SeriesMainInformation;
     use crate::core::api::tv_maze::Rating;
+    use crate::core::date_time_display::DateTimeDisplay;
     use crate::core::{api::tv_maze::series_searching, caching};
     use crate::gui::assets::icons::STAR_FILL;
     use crate::gui::helpers::empty_image;
@@ -238,7 +250,7 @@ mod search_result {
                         Task::perform(
                             caching::load_image(
                                 url.medium_image_url,
-                                caching::ImageResolution::Medium,
+                                caching::ImageResolution::Medium(caching::ImageKind::PosterThumb),
                             ),
                             Message::ImageLoaded,
                         )
@@ -286,7 +298,13 @@ mod search_result {
             ];
 
             if let Some(premier) = &self.search_result.show.premiered {
-                column = column.push(text(format!("Premiered: {}", premier)).size(9));
+                column = column.push(
+                    text(format!(
+                        "Premiered: {}",
+                        DateTimeDisplay::from_settings().date_str(premier)
+                    ))
+                    .size(9),
+                );
             }
 
             column = column.push(Self::rating_widget(&self.search_result.show.rating));
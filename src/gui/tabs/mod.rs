@@ -1,4 +1,5 @@
 use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+use calendar_tab::{CalendarTab, Message as CalendarMessage};
 use discover_tab::{DiscoverTab, Message as DiscoverMessage};
 use my_shows_tab::{Message as MyShowsMessage, MyShowsTab};
 use settings_tab::{Message as SettingsMessage, SettingsTab};
@@ -9,6 +10,7 @@ use iced::widget::scrollable::{self, Id, RelativeOffset};
 use iced::{Element, Task};
 use std::sync::mpsc;
 
+pub mod calendar_tab;
 pub mod discover_tab;
 pub mod my_shows_tab;
 pub mod settings_tab;
@@ -46,6 +48,7 @@ pub enum TabId {
     Discover,
     Watchlist,
     MyShows,
+    Calendar,
     Statistics,
     Settings,
 }
@@ -56,8 +59,9 @@ impl From<usize> for TabId {
             0 => Self::Discover,
             1 => Self::Watchlist,
             2 => Self::MyShows,
-            3 => Self::Statistics,
-            4 => Self::Settings,
+            3 => Self::Calendar,
+            4 => Self::Statistics,
+            5 => Self::Settings,
             _ => unreachable!("no more tabs"),
         }
     }
@@ -69,8 +73,9 @@ impl From<TabId> for usize {
             TabId::Discover => 0,
             TabId::Watchlist => 1,
             TabId::MyShows => 2,
-            TabId::Statistics => 3,
-            TabId::Settings => 4,
+            TabId::Calendar => 3,
+            TabId::Statistics => 4,
+            TabId::Settings => 5,
         }
     }
 }
@@ -81,6 +86,7 @@ impl std::fmt::Display for TabId {
             TabId::Discover => "Discover",
             TabId::Watchlist => "Watchlist",
             TabId::MyShows => "MyShows",
+            TabId::Calendar => "Calendar",
             TabId::Statistics => "Statistics",
             TabId::Settings => "Settings",
         };
@@ -105,14 +111,16 @@ pub enum Message {
     Discover(DiscoverMessage),
     Watchlist(WatchlistMessage),
     MyShows(MyShowsMessage),
+    Calendar(CalendarMessage),
     Statistics(StatisticsMessage),
     Settings(SettingsMessage),
 }
 
 enum ReloadableTab<'a> {
     Watchlist(WatchlistTab<'a>),
-    MyShows(MyShowsTab<'a>),
-    Statistics(StatisticsTab<'a>),
+    MyShows(Box<MyShowsTab<'a>>),
+    Calendar(CalendarTab),
+    Statistics(Box<StatisticsTab<'a>>),
 }
 
 pub struct TabsController<'a> {
@@ -120,7 +128,7 @@ pub struct TabsController<'a> {
     discover_tab: DiscoverTab<'a>,
     settings_tab: SettingsTab,
     reloadable_tab: Option<ReloadableTab<'a>>,
-    tabs_scrollable_offsets: [RelativeOffset; 5],
+    tabs_scrollable_offsets: [RelativeOffset; 6],
     series_page_sender: mpsc::Sender<SeriesMainInformation>,
 }
 
@@ -135,7 +143,7 @@ impl TabsController<'_> {
                 discover_tab,
                 reloadable_tab: None,
                 settings_tab,
-                tabs_scrollable_offsets: [RelativeOffset::START; 5],
+                tabs_scrollable_offsets: [RelativeOffset::START; 6],
                 series_page_sender,
             },
             Task::batch([
@@ -166,6 +174,8 @@ impl TabsController<'_> {
                             .record_scrollable_offset(index, watchlist_tab.get_scrollable_offset()),
                         ReloadableTab::MyShows(my_shows_tab) => self
                             .record_scrollable_offset(index, my_shows_tab.get_scrollable_offset()),
+                        ReloadableTab::Calendar(calendar_tab) => self
+                            .record_scrollable_offset(index, calendar_tab.get_scrollable_offset()),
                         ReloadableTab::Statistics(statistics_tab) => self.record_scrollable_offset(
                             index,
                             statistics_tab.get_scrollable_offset(),
@@ -208,6 +218,10 @@ impl TabsController<'_> {
                         MyShowsTab::set_scrollable_offset(self.tabs_scrollable_offsets[index])
                             .map(Message::MyShows)
                     }
+                    ReloadableTab::Calendar(_) => {
+                        CalendarTab::set_scrollable_offset(self.tabs_scrollable_offsets[index])
+                            .map(Message::Calendar)
+                    }
                     ReloadableTab::Statistics(_) => {
                         StatisticsTab::set_scrollable_offset(self.tabs_scrollable_offsets[index])
                             .map(Message::Statistics)
@@ -238,15 +252,23 @@ impl TabsController<'_> {
                     self.series_page_sender.clone(),
                     Some(self.tabs_scrollable_offsets[index]),
                 );
-                self.reloadable_tab = Some(ReloadableTab::MyShows(my_shows_tab));
+                self.reloadable_tab = Some(ReloadableTab::MyShows(Box::new(my_shows_tab)));
                 my_shows_command.map(Message::MyShows)
             }
+            TabId::Calendar => {
+                let (calendar_tab, calendar_command) = CalendarTab::new(
+                    self.series_page_sender.clone(),
+                    Some(self.tabs_scrollable_offsets[index]),
+                );
+                self.reloadable_tab = Some(ReloadableTab::Calendar(calendar_tab));
+                calendar_command.map(Message::Calendar)
+            }
             TabId::Statistics => {
                 let (statistics_tab, statistics_command) = StatisticsTab::new(
                     self.series_page_sender.clone(),
                     Some(self.tabs_scrollable_offsets[index]),
                 );
-                self.reloadable_tab = Some(ReloadableTab::Statistics(statistics_tab));
+                self.reloadable_tab = Some(ReloadableTab::Statistics(Box::new(statistics_tab)));
                 statistics_command.map(Message::Statistics)
             }
             TabId::Settings => Task::none(),
@@ -294,6 +316,13 @@ impl TabsController<'_> {
                     Task::none()
                 }
             }
+            Message::Calendar(message) => {
+                if let Some(ReloadableTab::Calendar(ref mut calendar)) = self.reloadable_tab {
+                    calendar.update(message).map(Message::Calendar)
+                } else {
+                    Task::none()
+                }
+            }
             Message::Statistics(message) => {
                 if let Some(ReloadableTab::Statistics(ref mut statistics)) = self.reloadable_tab {
                     statistics.update(message).map(Message::Statistics)
@@ -305,11 +334,12 @@ impl TabsController<'_> {
         }
     }
 
-    pub fn get_labels(&self) -> [TabLabel; 5] {
+    pub fn get_labels(&self) -> [TabLabel; 6] {
         [
             DiscoverTab::tab_label(),
             WatchlistTab::tab_label(),
             MyShowsTab::tab_label(),
+            CalendarTab::tab_label(),
             StatisticsTab::tab_label(),
             SettingsTab::tab_label(),
         ]
@@ -324,6 +354,7 @@ impl TabsController<'_> {
                 match reloadable_tab {
                     ReloadableTab::Watchlist(watchlist) => watchlist.view().map(Message::Watchlist),
                     ReloadableTab::MyShows(my_shows) => my_shows.view().map(Message::MyShows),
+                    ReloadableTab::Calendar(calendar) => calendar.view().map(Message::Calendar),
                     ReloadableTab::Statistics(statistics) => {
                         statistics.view().map(Message::Statistics)
                     }
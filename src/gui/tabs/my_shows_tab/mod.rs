@@ -5,16 +5,18 @@ use crate::gui::assets::icons::FILM;
 use crate::gui::styles;
 
 use iced::widget::scrollable::{RelativeOffset, Viewport};
-use iced::widget::{column, scrollable, text};
+use iced::widget::{button, column, horizontal_space, row, scrollable, text, Space};
 use iced::{Element, Length, Task};
 
 use my_shows_widget::{Message as MyShowsMessage, MyShows};
+use notification_history_widget::{Message as NotificationHistoryMessage, NotificationHistory};
 use upcoming_releases_widget::{Message as UpcomingReleasesMessage, UpcomingReleases};
 
 use super::tab_searching::{Message as SearcherMessage, Searchable, Searcher};
 use super::Tab;
 
 mod my_shows_widget;
+mod notification_history_widget;
 mod upcoming_releases_widget;
 
 #[derive(Debug, Clone)]
@@ -23,6 +25,11 @@ pub enum Message {
     Waiting(MyShowsMessage),
     Upcoming(UpcomingReleasesMessage),
     Untracked(MyShowsMessage),
+    NotificationHistory(NotificationHistoryMessage),
+    ExportCalendarPressed,
+    /// `None` when no file was chosen to export to
+    CalendarExported(Option<Result<usize, String>>),
+    CalendarExportStatusTimeout,
     PageScrolled(Viewport),
     Searcher(SearcherMessage),
 }
@@ -32,6 +39,9 @@ pub struct MyShowsTab<'a> {
     upcoming_releases: UpcomingReleases<'a>,
     ended_releases: MyShows<'a>,
     untracked_releases: MyShows<'a>,
+    notification_history: NotificationHistory,
+    /// The number of exported episodes of the last calendar export or why it failed
+    calendar_export_status: Option<Result<usize, String>>,
     scrollable_offset: RelativeOffset,
     searcher: Searcher,
 }
@@ -48,7 +58,9 @@ impl MyShowsTab<'_> {
         let (upcoming_releases, upcoming_releases_commands) =
             UpcomingReleases::new(series_page_sender.clone());
         let (waiting_releases, waiting_releases_commands) =
-            MyShows::new_as_waiting_release_series(series_page_sender);
+            MyShows::new_as_waiting_release_series(series_page_sender.clone());
+        let (notification_history, notification_history_commands) =
+            NotificationHistory::new(series_page_sender);
 
         (
             Self {
@@ -56,6 +68,8 @@ impl MyShowsTab<'_> {
                 untracked_releases,
                 waiting_releases,
                 upcoming_releases,
+                notification_history,
+                calendar_export_status: None,
                 scrollable_offset: scrollable_offset.unwrap_or(RelativeOffset::START),
                 searcher: Searcher::new("Search My Shows".to_owned()),
             },
@@ -64,12 +78,18 @@ impl MyShowsTab<'_> {
                 ended_releases_commands.map(Message::Ended),
                 waiting_releases_commands.map(Message::Waiting),
                 upcoming_releases_commands.map(Message::Upcoming),
+                notification_history_commands.map(Message::NotificationHistory),
             ]),
         )
     }
 
     pub fn subscription(&self) -> iced::Subscription<Message> {
-        self.upcoming_releases.subscription().map(Message::Upcoming)
+        iced::Subscription::batch([
+            self.upcoming_releases.subscription().map(Message::Upcoming),
+            self.notification_history
+                .subscription()
+                .map(Message::NotificationHistory),
+        ])
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
@@ -86,6 +106,27 @@ impl MyShowsTab<'_> {
                 .untracked_releases
                 .update(message)
                 .map(Message::Untracked),
+            Message::NotificationHistory(message) => self
+                .notification_history
+                .update(message)
+                .map(Message::NotificationHistory),
+            Message::ExportCalendarPressed => {
+                Task::perform(calendar_export::export(), Message::CalendarExported)
+            }
+            Message::CalendarExported(export_result) => {
+                if export_result.is_none() {
+                    return Task::none();
+                }
+                self.calendar_export_status = export_result;
+                Task::perform(
+                    tokio::time::sleep(std::time::Duration::from_secs(3)),
+                    |_| Message::CalendarExportStatusTimeout,
+                )
+            }
+            Message::CalendarExportStatusTimeout => {
+                self.calendar_export_status = None;
+                Task::none()
+            }
             Message::PageScrolled(view_port) => {
                 self.scrollable_offset = view_port.relative_offset();
                 Task::none()
@@ -104,8 +145,19 @@ impl MyShowsTab<'_> {
         }
     }
 
-    pub fn view(&self) -> Element<Message> {
-        let upcoming_releases = self.upcoming_releases.view().map(Message::Upcoming);
+    pub fn view(&self) -> Element<'_, Message> {
+        let upcoming_releases: Element<'_, Message> = column![
+            row![
+                horizontal_space(),
+                self.calendar_export_status_widget(),
+                button("Export calendar (.ics)").on_press(Message::ExportCalendarPressed)
+            ]
+            .spacing(10)
+            .align_y(iced::Alignment::Center),
+            self.upcoming_releases.view().map(Message::Upcoming)
+        ]
+        .spacing(5)
+        .into();
 
         let waiting_releases: Element<'_, Message> = column![
             text("Waiting for release date")
@@ -132,6 +184,15 @@ impl MyShowsTab<'_> {
         .spacing(5)
         .into();
 
+        let notification_history: Element<'_, Message> = column![
+            text("Notification History").size(21),
+            self.notification_history
+                .view()
+                .map(Message::NotificationHistory)
+        ]
+        .spacing(5)
+        .into();
+
         let searcher = self.searcher.view().map(Message::Searcher);
 
         let content = scrollable(
@@ -140,6 +201,7 @@ impl MyShowsTab<'_> {
                 waiting_releases,
                 ended_releases,
                 untracked_releases,
+                notification_history,
             ]
             .padding(10)
             .spacing(50)
@@ -152,6 +214,47 @@ impl MyShowsTab<'_> {
 
         column![searcher, content].spacing(10).padding(5).into()
     }
+
+    fn calendar_export_status_widget(&self) -> Element<'_, Message> {
+        match &self.calendar_export_status {
+            Some(Ok(exported_episodes)) => {
+                text(format!("{} upcoming episodes exported!", exported_episodes))
+                    .style(styles::text_styles::green_text_theme)
+                    .into()
+            }
+            Some(Err(err)) => text(err).style(styles::text_styles::red_text_theme).into(),
+            None => Space::new(0, 0).into(),
+        }
+    }
+}
+
+mod calendar_export {
+    use directories::UserDirs;
+    use rfd::AsyncFileDialog;
+
+    use crate::core::calendar;
+
+    /// Exports the upcoming episodes to the file chosen by the user
+    ///
+    /// Returns `None` when no file was chosen.
+    pub async fn export() -> Option<Result<usize, String>> {
+        let mut file_dialog = AsyncFileDialog::new()
+            .set_file_name("series-troxide.ics")
+            .add_filter("iCalendar", &["ics"]);
+
+        if let Some(user_dirs) = UserDirs::new() {
+            file_dialog = file_dialog.set_directory(user_dirs.home_dir());
+        }
+
+        let chosen_path = file_dialog.save_file().await?.path().to_owned();
+
+        Some(
+            calendar::export_upcoming_episodes(chosen_path)
+                .await
+                .map(|events| events.len())
+                .map_err(|err| err.to_string()),
+        )
+    }
 }
 
 impl Tab for MyShowsTab<'_> {
@@ -0,0 +1,293 @@
+use std::sync::mpsc;
+
+use iced::futures::{SinkExt, Stream};
+use iced::widget::{button, column, container, horizontal_space, row, text, Column};
+use iced::{Element, Length, Subscription, Task};
+
+use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+use crate::core::caching::episode_list::EpisodeList;
+use crate::core::caching::series_information;
+use crate::core::database::{NotificationHistoryEntry, DB};
+use crate::core::date_time_display::DateTimeDisplay;
+use crate::gui::helpers::season_episode_str_gen;
+use crate::gui::styles;
+use crate::gui::tabs::tab_searching::unavailable_posters;
+
+/// The number of notifications displayed before showing all of them is requested
+const COLLAPSED_ENTRIES: usize = 5;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    HistoryChanged,
+    ShowAllToggled,
+    SeriesPressed(u32),
+    SeriesInformationLoaded(Option<Box<SeriesMainInformation>>),
+    MarkWatched(usize),
+    MarkedWatched(usize),
+    ReleasedEpisodesLoaded(Vec<NotificationHistoryEntry>, Vec<bool>),
+}
+
+pub struct NotificationHistory {
+    entries: Vec<NotificationHistoryEntry>,
+    /// Whether the episode of the entry at the same index is watched
+    watched_episodes: Vec<bool>,
+    /// Whether the episode of the entry at the same index has been released
+    released_episodes: Vec<bool>,
+    show_all: bool,
+    series_page_sender: mpsc::Sender<SeriesMainInformation>,
+}
+
+impl NotificationHistory {
+    pub fn new(series_page_sender: mpsc::Sender<SeriesMainInformation>) -> (Self, Task<Message>) {
+        let mut notification_history = Self {
+            entries: vec![],
+            watched_episodes: vec![],
+            released_episodes: vec![],
+            show_all: false,
+            series_page_sender,
+        };
+        let task = notification_history.load_entries();
+        (notification_history, task)
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::run(notification_history_watcher)
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::HistoryChanged => self.load_entries(),
+            Message::ShowAllToggled => {
+                self.show_all = !self.show_all;
+                Task::none()
+            }
+            Message::SeriesPressed(series_id) => Task::perform(
+                async move {
+                    series_information::get_series_main_info_with_id(series_id)
+                        .await
+                        .map_err(|err| {
+                            tracing::error!(
+                                "failed to load series information for notification history: {}",
+                                err
+                            )
+                        })
+                        .ok()
+                        .map(Box::new)
+                },
+                Message::SeriesInformationLoaded,
+            ),
+            Message::SeriesInformationLoaded(series_info) => {
+                if let Some(series_info) = series_info {
+                    self.series_page_sender
+                        .send(*series_info)
+                        .expect("failed to send series page info");
+                }
+                Task::none()
+            }
+            Message::MarkWatched(index) => {
+                let entry = &self.entries[index];
+                let (Some(series_id), Some(season), Some(episode_number)) =
+                    (entry.series_id, entry.season, entry.episode_number)
+                else {
+                    return Task::none();
+                };
+                let series_name = entry.series_name.clone().unwrap_or_default();
+
+                Task::perform(
+                    async move {
+                        let mut series = DB.get_series(series_id).unwrap_or_else(|| {
+                            crate::core::database::Series::new(series_name, series_id)
+                        });
+                        series.add_episode(season, episode_number).await;
+                    },
+                    move |_| Message::MarkedWatched(index),
+                )
+            }
+            Message::MarkedWatched(index) => {
+                if let Some(entry) = self.entries.get(index) {
+                    self.watched_episodes[index] = is_episode_watched(entry);
+                }
+                Task::none()
+            }
+            Message::ReleasedEpisodesLoaded(entries, released_episodes) => {
+                // The history may have changed while the episodes were loading
+                if entries == self.entries {
+                    self.released_episodes = released_episodes;
+                }
+                Task::none()
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        if self.entries.is_empty() {
+            return unavailable_posters("No notifications sent yet")
+                .style(styles::container_styles::first_class_container_square_theme)
+                .height(100)
+                .width(Length::Fill)
+                .into();
+        }
+
+        let entries_to_show = if self.show_all {
+            self.entries.len()
+        } else {
+            COLLAPSED_ENTRIES
+        };
+
+        let entries = self
+            .entries
+            .iter()
+            .zip(self.watched_episodes.iter())
+            .zip(self.released_episodes.iter())
+            .enumerate()
+            .take(entries_to_show)
+            .map(|(index, ((entry, is_watched), is_released))| {
+                entry_widget(index, entry, *is_watched, *is_released)
+            });
+
+        let mut content = Column::with_children(entries)
+            .spacing(5)
+            .width(Length::Fill)
+            .align_x(iced::Alignment::Center);
+
+        if self.entries.len() > COLLAPSED_ENTRIES {
+            content = content.push(
+                button(if self.show_all {
+                    "Show less"
+                } else {
+                    "Show all"
+                })
+                .on_press(Message::ShowAllToggled)
+                .style(styles::button_styles::transparent_button_theme),
+            );
+        }
+
+        content.into()
+    }
+
+    fn load_entries(&mut self) -> Task<Message> {
+        self.entries = DB.get_notification_history();
+        self.watched_episodes = self.entries.iter().map(is_episode_watched).collect();
+        // Treated as unreleased until their release times are known
+        self.released_episodes = vec![false; self.entries.len()];
+
+        let entries = self.entries.clone();
+        Task::perform(
+            async move {
+                let mut released_episodes = Vec::with_capacity(entries.len());
+                for entry in &entries {
+                    released_episodes.push(is_episode_released(entry).await);
+                }
+                (entries, released_episodes)
+            },
+            |(entries, released_episodes)| {
+                Message::ReleasedEpisodesLoaded(entries, released_episodes)
+            },
+        )
+    }
+}
+
+fn entry_widget(
+    index: usize,
+    entry: &NotificationHistoryEntry,
+    is_watched: bool,
+    is_released: bool,
+) -> Element<'_, Message> {
+    let sent_at = entry
+        .sent_at()
+        .map(|sent_at| DateTimeDisplay::from_settings().date_time(&sent_at))
+        .unwrap_or_default();
+
+    let backends = if entry.backends.is_empty() {
+        "not delivered".to_owned()
+    } else {
+        format!("via {}", entry.backends.join(", "))
+    };
+
+    let mut metadata = column![
+        text(&entry.summary).style(styles::text_styles::accent_color_theme),
+        text(&entry.body).size(12),
+        text(format!("{} {}", sent_at, backends)).size(11),
+    ]
+    .spacing(3);
+
+    if let (Some(season), Some(episode_number)) = (entry.season, entry.episode_number) {
+        metadata = metadata.push(text(season_episode_str_gen(season, episode_number)).size(11));
+    }
+
+    let mut actions = column![].spacing(5).align_x(iced::Alignment::End);
+
+    if let Some(series_id) = entry.series_id {
+        actions = actions.push(button("Open series").on_press(Message::SeriesPressed(series_id)));
+    }
+
+    if entry.episode_number.is_some() {
+        actions = actions.push(if is_watched {
+            button("Watched")
+        } else if is_released {
+            button("Mark watched").on_press(Message::MarkWatched(index))
+        } else {
+            // Unreleased episodes can't be watched yet
+            button("Not released")
+        });
+    }
+
+    container(row![metadata, horizontal_space(), actions].spacing(10))
+        .padding(10)
+        .style(styles::container_styles::first_class_container_rounded_theme)
+        .width(1000)
+        .into()
+}
+
+fn is_episode_watched(entry: &NotificationHistoryEntry) -> bool {
+    let (Some(series_id), Some(season), Some(episode_number)) =
+        (entry.series_id, entry.season, entry.episode_number)
+    else {
+        return false;
+    };
+
+    DB.get_series(series_id)
+        .and_then(|series| {
+            series
+                .get_season(season)
+                .map(|season| season.is_episode_watched(episode_number))
+        })
+        .unwrap_or(false)
+}
+
+/// Whether the release time of the episode of the entry has passed
+async fn is_episode_released(entry: &NotificationHistoryEntry) -> bool {
+    let (Some(series_id), Some(season), Some(episode_number)) =
+        (entry.series_id, entry.season, entry.episode_number)
+    else {
+        return false;
+    };
+
+    let episode_list = match EpisodeList::new(series_id).await {
+        Ok(episode_list) => episode_list,
+        Err(err) => {
+            tracing::error!(
+                "failed to load episode list for notification history: {}",
+                err
+            );
+            return false;
+        }
+    };
+
+    episode_list
+        .get_episode(season, episode_number)
+        .map(|episode| matches!(episode.is_future_release(), Ok(false)))
+        .unwrap_or(false)
+}
+
+fn notification_history_watcher() -> impl Stream<Item = Message> {
+    iced::stream::channel(100, |mut output| async move {
+        let mut subscriber = DB.watch_notification_history();
+
+        while (&mut subscriber).await.is_some() {
+            if output.send(Message::HistoryChanged).await.is_err() {
+                break;
+            }
+        }
+    })
+}
@@ -257,7 +257,7 @@ mod upcoming_poster {
             }
         }
 
-        pub fn view(&self) -> Element<IndexedMessage<usize, Message>> {
+        pub fn view(&self) -> Element<'_, IndexedMessage<usize, Message>> {
             let mut content = row!().padding(2).spacing(7);
             if let Some(image_bytes) = self.poster.get_image() {
                 let image_handle = image::Handle::from_bytes(image_bytes.clone());
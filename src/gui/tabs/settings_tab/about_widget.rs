@@ -1,4 +1,5 @@
 use crate::core::api::crates::{get_program_info, CrateInformation};
+use crate::core::offline::OFFLINE;
 use crate::core::settings_config::SETTINGS;
 use crate::gui::assets::icons::{ARROW_REPEAT, CUP_HOT_FILL, GITHUB_ICON, SERIES_TROXIDE_ICON};
 use crate::gui::styles;
@@ -77,21 +78,21 @@ impl About {
                                 // For some reasons, async version of notify-rust = "4.9.0" does not work on macos
                                 // and windows so we use the sync version here and async for the linux
                                 #[cfg(not(target_os = "linux"))]
-                                {
-                                    platform_notify::not_linux::notify(
-                                        notification_summary,
-                                        &notification_body,
-                                    )
-                                    .await
-                                }
+                                let result = platform_notify::not_linux::notify(
+                                    notification_summary,
+                                    &notification_body,
+                                )
+                                .await;
 
                                 #[cfg(target_os = "linux")]
-                                {
-                                    platform_notify::linux::notify(
-                                        notification_summary,
-                                        &notification_body,
-                                    )
-                                    .await
+                                let result = platform_notify::linux::notify(
+                                    notification_summary,
+                                    &notification_body,
+                                )
+                                .await;
+
+                                if let Err(err) = result {
+                                    error!("failed to show update notification: {}", err);
                                 }
                             },
                             |_| Message::NotificationSent,
@@ -148,7 +149,7 @@ impl About {
     }
 }
 
-fn update_widget(about: &About) -> Element<Message> {
+fn update_widget(about: &About) -> Element<'_, Message> {
     let series_troxide_icon_handle = svg::Handle::from_memory(SERIES_TROXIDE_ICON);
     let icon = svg(series_troxide_icon_handle).width(40);
     let program_info = column![
@@ -219,7 +220,7 @@ fn update_widget(about: &About) -> Element<Message> {
 
     let refresh_button = button(refresh_icon)
         .style(styles::button_styles::transparent_button_theme)
-        .on_press(Message::RecheckUpdate);
+        .on_press_maybe((!OFFLINE.is_offline()).then_some(Message::RecheckUpdate));
 
     let version_information = container(row![
         column![
@@ -0,0 +1,98 @@
+use iced::widget::{column, container, row, text, toggler};
+use iced::{Element, Length};
+use iced_aw::NumberInput;
+
+use crate::core::calendar::feed;
+use crate::core::settings_config::SETTINGS;
+use crate::gui::styles;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Toggled(bool),
+    PortChanged(u16),
+}
+
+#[derive(Default)]
+pub struct CalendarFeed;
+
+impl CalendarFeed {
+    pub fn update(&mut self, message: Message) {
+        match message {
+            Message::Toggled(enabled) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .calendar_feed
+                    .enabled = enabled;
+            }
+            Message::PortChanged(port) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .calendar_feed
+                    .port = port;
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let calendar_feed_settings = SETTINGS
+            .read()
+            .unwrap()
+            .get_current_settings()
+            .calendar_feed
+            .clone();
+
+        let calendar_feed_info = column![
+            text("Calendar feed"),
+            text("Serves the upcoming releases of your tracked series as a calendar that calendar apps can subscribe to, takes effect after a restart")
+                .size(11)
+        ];
+
+        let enabled_toggler = toggler(calendar_feed_settings.enabled)
+            .label("Serve calendar feed")
+            .on_toggle(Message::Toggled)
+            .spacing(10)
+            .width(Length::Shrink);
+
+        let mut content = column![
+            text("Calendar")
+                .style(styles::text_styles::accent_color_theme)
+                .size(21),
+            calendar_feed_info,
+            enabled_toggler,
+        ]
+        .spacing(10);
+
+        if calendar_feed_settings.enabled {
+            let port_input = row![
+                text("Port").size(11),
+                NumberInput::new(
+                    calendar_feed_settings.port,
+                    1..=u16::MAX,
+                    Message::PortChanged
+                )
+                .width(Length::Fixed(200.0)),
+            ]
+            .spacing(5)
+            .align_y(iced::Alignment::Center);
+
+            content = content.push(port_input);
+            content = content.push(
+                text(format!(
+                    "Subscribe to {}",
+                    feed::feed_url(calendar_feed_settings.port)
+                ))
+                .size(11),
+            );
+        }
+
+        container(content)
+            .style(styles::container_styles::first_class_container_rounded_theme)
+            .padding(5)
+            .width(1000)
+            .into()
+    }
+}
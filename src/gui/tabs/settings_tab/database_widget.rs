@@ -1,10 +1,17 @@
-use iced::widget::{button, column, container, horizontal_space, progress_bar, row, text, Space};
-use iced::{Element, Task};
-
+use iced::widget::{
+    button, column, container, horizontal_space, progress_bar, row, text, toggler, Space,
+};
+use iced::{Element, Length, Task};
+use iced_aw::NumberInput;
+
+use crate::core::caching::image_cache::{self, ImageCacheStats};
+use crate::core::caching::offline_availability::{self, OfflineAvailability};
 use crate::core::database::database_transfer::TransferData;
 use crate::core::database::DB;
+use crate::core::offline::OFFLINE;
+use crate::core::settings_config::{self, SETTINGS};
 
-use crate::gui::styles;
+use crate::gui::{helpers, styles};
 
 mod trakt_integration;
 
@@ -18,6 +25,13 @@ pub enum Message {
     ExportTimeoutComplete,
     ImportCachingEvent(full_caching::Event),
     TraktIntegration(trakt_integration::Message),
+    MaxConcurrentFetchesChanged(usize),
+    MaxImageCacheSizeChanged(u64),
+    OfflineModeToggled(bool),
+    RefreshImageCacheStats,
+    ImageCacheStatsLoaded(Result<ImageCacheStats, String>),
+    MakeTrackedSeriesAvailableOffline,
+    MadeTrackedSeriesAvailableOffline(Result<OfflineAvailability, String>),
 }
 
 pub struct Database {
@@ -28,19 +42,28 @@ pub struct Database {
     transfer_data: Option<TransferData>,
     sender: Option<iced::futures::channel::mpsc::Sender<full_caching::Input>>,
     trakt_widget: trakt_integration::TraktIntegration,
+    image_cache_stats: Option<Result<ImageCacheStats, String>>,
+    making_available_offline: bool,
+    offline_availability: Option<Result<OfflineAvailability, String>>,
 }
 
 impl Database {
-    pub fn new() -> Self {
-        Self {
-            import_status: None,
-            export_status: None,
-            import_progress: (0, 0),
-            importing: false,
-            transfer_data: None,
-            sender: None,
-            trakt_widget: trakt_integration::TraktIntegration::new(),
-        }
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                import_status: None,
+                export_status: None,
+                import_progress: (0, 0),
+                importing: false,
+                transfer_data: None,
+                sender: None,
+                trakt_widget: trakt_integration::TraktIntegration::new(),
+                image_cache_stats: None,
+                making_available_offline: false,
+                offline_availability: None,
+            },
+            load_image_cache_stats(),
+        )
     }
     pub fn subscription(&self) -> iced::Subscription<Message> {
         iced::Subscription::batch([
@@ -132,6 +155,60 @@ impl Database {
                 .trakt_widget
                 .update(message)
                 .map(Message::TraktIntegration),
+            Message::MaxConcurrentFetchesChanged(max_concurrent_fetches) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .caching
+                    .max_concurrent_fetches = max_concurrent_fetches;
+                Task::none()
+            }
+            Message::MaxImageCacheSizeChanged(max_image_cache_size) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .caching
+                    .max_image_cache_size = max_image_cache_size;
+                Task::none()
+            }
+            Message::OfflineModeToggled(offline_mode) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .caching
+                    .offline_mode = offline_mode;
+                OFFLINE.refresh_status();
+                Task::none()
+            }
+            Message::RefreshImageCacheStats => {
+                self.image_cache_stats = None;
+                load_image_cache_stats()
+            }
+            Message::ImageCacheStatsLoaded(image_cache_stats) => {
+                self.image_cache_stats = Some(image_cache_stats);
+                Task::none()
+            }
+            Message::MakeTrackedSeriesAvailableOffline => {
+                self.making_available_offline = true;
+                self.offline_availability = None;
+                Task::perform(
+                    offline_availability::make_tracked_series_available_offline(),
+                    |result| {
+                        Message::MadeTrackedSeriesAvailableOffline(
+                            result.map_err(|err| err.to_string()),
+                        )
+                    },
+                )
+            }
+            Message::MadeTrackedSeriesAvailableOffline(offline_availability) => {
+                self.making_available_offline = false;
+                self.offline_availability = Some(offline_availability);
+                // The new images change the images cache usage
+                load_image_cache_stats()
+            }
         }
     }
 
@@ -164,7 +241,8 @@ impl Database {
                 get_status_text(self.import_status.as_ref()),
                 {
                     let mut button = button("Import");
-                    if !self.importing {
+                    // Imported series are cached right away, which needs the network
+                    if !self.importing && !OFFLINE.is_offline() {
                         button = button.on_press(Message::ImportDatabasePressed);
                     }
                     button
@@ -204,7 +282,12 @@ impl Database {
                 .size(21)
                 .style(styles::text_styles::accent_color_theme),
             series_troxide_data,
-            trakt_data
+            trakt_data,
+            caching_widget(self.image_cache_stats.as_ref()),
+            offline_availability_widget(
+                self.making_available_offline,
+                self.offline_availability.as_ref()
+            ),
         ]
         .padding(5);
 
@@ -215,6 +298,170 @@ impl Database {
     }
 }
 
+fn caching_widget(
+    image_cache_stats: Option<&Result<ImageCacheStats, String>>,
+) -> Element<'_, Message> {
+    let caching_settings = SETTINGS
+        .read()
+        .unwrap()
+        .get_current_settings()
+        .caching
+        .clone();
+
+    let max_concurrent_fetches = caching_settings.max_concurrent_fetches;
+    let max_image_cache_size = caching_settings.max_image_cache_size;
+
+    let offline_mode = column![
+        toggler(caching_settings.offline_mode)
+            .label("Offline mode")
+            .on_toggle(Message::OfflineModeToggled)
+            .spacing(10)
+            .width(Length::Shrink),
+        text(
+            "Only use cached data without going online, actions that need the network are disabled"
+        )
+        .size(11)
+    ]
+    .spacing(5);
+
+    let concurrent_fetches_info = column![
+        text("Concurrent fetches"),
+        text(format!(
+            "At most {} series will be fetched at the same time, takes effect after a restart",
+            max_concurrent_fetches
+        ))
+        .size(11)
+    ];
+
+    let concurrent_fetches_input = NumberInput::new(
+        max_concurrent_fetches,
+        1..101,
+        Message::MaxConcurrentFetchesChanged,
+    )
+    .width(Length::Fixed(200.0));
+
+    let image_cache_info = column![
+        text("Images cache size"),
+        text(format!(
+            "Least recently used images will be removed when images take more than {} MB, images of tracked series and of series made available offline are never removed",
+            max_image_cache_size
+        ))
+        .size(11)
+    ];
+
+    let image_cache_size_input = NumberInput::new(
+        max_image_cache_size,
+        1..settings_config::MAX_IMAGE_CACHE_SIZE_LIMIT + 1,
+        Message::MaxImageCacheSizeChanged,
+    )
+    .width(Length::Fixed(200.0));
+
+    let image_cache_stats: Element<'_, Message> = match image_cache_stats {
+        Some(Ok(stats)) => text(format!(
+            "Using {} of {} by {} images, {} by {} pinned images",
+            helpers::readable_size(stats.total_size),
+            helpers::readable_size(stats.max_size),
+            stats.total_images,
+            helpers::readable_size(stats.pinned_size),
+            stats.pinned_images,
+        ))
+        .size(11)
+        .into(),
+        Some(Err(err)) => text(err)
+            .size(11)
+            .style(styles::text_styles::red_text_theme)
+            .into(),
+        None => text("computing images cache usage...").size(11).into(),
+    };
+
+    column![
+        text("Caching").size(18),
+        offline_mode,
+        concurrent_fetches_info,
+        concurrent_fetches_input,
+        image_cache_info,
+        image_cache_size_input,
+        row![
+            image_cache_stats,
+            horizontal_space(),
+            button("Refresh").on_press(Message::RefreshImageCacheStats)
+        ]
+        .spacing(5),
+    ]
+    .spacing(5)
+    .into()
+}
+
+fn offline_availability_widget(
+    making_available_offline: bool,
+    offline_availability: Option<&Result<OfflineAvailability, String>>,
+) -> Element<'_, Message> {
+    let offline_availability_status: Element<'_, Message> = if making_available_offline {
+        text("making tracked series available offline...")
+            .size(11)
+            .into()
+    } else {
+        match offline_availability {
+            Some(Ok(offline_availability)) => {
+                let mut status = format!(
+                    "{} series available offline using {}",
+                    offline_availability.available_series,
+                    helpers::readable_size(offline_availability.disk_size)
+                );
+                if offline_availability.failed_series != 0 {
+                    status.push_str(&format!(
+                        ", {} series failed",
+                        offline_availability.failed_series
+                    ));
+                }
+                if offline_availability.failed_images != 0 {
+                    status.push_str(&format!(
+                        ", {} images failed",
+                        offline_availability.failed_images
+                    ));
+                }
+                text(status)
+                    .size(11)
+                    .style(styles::text_styles::green_text_theme)
+                    .into()
+            }
+            Some(Err(err)) => text(err)
+                .size(11)
+                .style(styles::text_styles::red_text_theme)
+                .into(),
+            None => Space::new(0, 0).into(),
+        }
+    };
+
+    let make_available_offline_button = button("Make available offline").on_press_maybe(
+        (!making_available_offline && !OFFLINE.is_offline())
+            .then_some(Message::MakeTrackedSeriesAvailableOffline),
+    );
+
+    column![
+        text("Offline Availability").size(18),
+        row![
+            column![
+                text("Cache all the data and images of the tracked series for offline use")
+                    .size(11),
+                offline_availability_status,
+            ]
+            .spacing(5),
+            horizontal_space(),
+            make_available_offline_button,
+        ]
+        .spacing(5),
+    ]
+    .spacing(5)
+    .into()
+}
+
+fn load_image_cache_stats() -> Task<Message> {
+    Task::perform(image_cache::get_image_cache_stats(), |result| {
+        Message::ImageCacheStatsLoaded(result.map_err(|err| err.to_string()))
+    })
+}
+
 fn get_status_text(status: Option<&Result<(), String>>) -> Element<'_, Message> {
     if let Some(res) = status {
         if let Err(err) = res {
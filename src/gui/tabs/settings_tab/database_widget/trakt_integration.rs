@@ -9,6 +9,7 @@ use crate::core::api::trakt::authentication::{self, CodeResponse, TokenResponse}
 use crate::core::api::trakt::trakt_data::TraktShow;
 use crate::core::api::trakt::user_credentials::{self, Client, Credentials, CredentialsError};
 use crate::core::api::trakt::user_settings::{self, UserSettings};
+use crate::core::offline::OFFLINE;
 use crate::gui::assets::icons::TRAKT_ICON_RED;
 use crate::gui::styles;
 
@@ -180,12 +181,16 @@ impl TraktIntegration {
                 .padding(10)
                 .into()
         } else {
+            // Everything about trakt integration needs the network
+            let is_online = !OFFLINE.is_offline();
             row![
                 text("Import series data from your Trakt account"),
                 horizontal_space(),
                 row![
-                    button("Sync Trakt Data").on_press(Message::SyncTraktData),
-                    button("Configure Trakt integration").on_press(Message::LoadCredentials),
+                    button("Sync Trakt Data")
+                        .on_press_maybe(is_online.then_some(Message::SyncTraktData)),
+                    button("Configure Trakt integration")
+                        .on_press_maybe(is_online.then_some(Message::LoadCredentials)),
                 ]
                 .spacing(5),
             ]
@@ -0,0 +1,138 @@
+use iced::widget::{column, combo_box, container, radio, row, text, Column};
+use iced::Element;
+
+use crate::core::date_time_display::DateTimeDisplay;
+use crate::core::settings_config::{
+    ClockFormat, DateFormat, ALL_CLOCK_FORMATS, ALL_DATE_FORMATS, SETTINGS,
+};
+use crate::gui::styles;
+
+/// The time zone option for using the time zone of the system
+const SYSTEM_TIME_ZONE: &str = "System";
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    TimeZone(String),
+    ClockFormat(ClockFormat),
+    DateFormat(DateFormat),
+}
+
+pub struct DateAndTime {
+    time_zone_combo_box_state: combo_box::State<String>,
+}
+
+impl DateAndTime {
+    pub fn new() -> Self {
+        let time_zone_list = std::iter::once(SYSTEM_TIME_ZONE.to_owned())
+            .chain(
+                chrono_tz::TZ_VARIANTS
+                    .iter()
+                    .map(|time_zone| time_zone.name().to_owned()),
+            )
+            .collect();
+
+        Self {
+            time_zone_combo_box_state: combo_box::State::new(time_zone_list),
+        }
+    }
+
+    pub fn update(&mut self, message: Message) {
+        let mut settings = SETTINGS.write().unwrap();
+        let locale_settings = &mut settings.change_settings().locale;
+
+        match message {
+            Message::TimeZone(time_zone) => {
+                locale_settings.time_zone = time_zone.parse().ok();
+            }
+            Message::ClockFormat(clock_format) => {
+                locale_settings.clock_format = clock_format;
+            }
+            Message::DateFormat(date_format) => {
+                locale_settings.date_format = date_format;
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let locale_settings = SETTINGS
+            .read()
+            .unwrap()
+            .get_current_settings()
+            .locale
+            .clone();
+
+        let selected_time_zone = locale_settings
+            .time_zone
+            .map(|time_zone| time_zone.name().to_owned())
+            .unwrap_or_else(|| SYSTEM_TIME_ZONE.to_owned());
+
+        let time_zone_widget = column![
+            text("Time zone").size(18),
+            text("The time zone release times are displayed in, useful when travelling or when the system is set to UTC").size(11),
+            combo_box(
+                &self.time_zone_combo_box_state,
+                "select a time zone",
+                Some(&selected_time_zone),
+                Message::TimeZone,
+            )
+            .width(500),
+        ]
+        .spacing(5);
+
+        let clock_format_list = Column::with_children(ALL_CLOCK_FORMATS.iter().map(|format| {
+            radio(
+                format.to_string(),
+                *format,
+                Some(locale_settings.clock_format),
+                Message::ClockFormat,
+            )
+            .into()
+        }))
+        .spacing(5);
+
+        let date_format_list = Column::with_children(ALL_DATE_FORMATS.iter().map(|format| {
+            radio(
+                format.to_string(),
+                *format,
+                Some(locale_settings.date_format),
+                Message::DateFormat,
+            )
+            .into()
+        }))
+        .spacing(5);
+
+        let formats_widget = row![
+            column![text("Clock").size(18), clock_format_list].spacing(5),
+            column![text("Date").size(18), date_format_list].spacing(5),
+        ]
+        .spacing(100);
+
+        let preview = text(format!(
+            "Now: {}",
+            DateTimeDisplay::new(&locale_settings).date_time(&chrono::Local::now())
+        ))
+        .size(11);
+
+        let content = column![
+            text("Date and Time")
+                .size(21)
+                .style(styles::text_styles::accent_color_theme),
+            time_zone_widget,
+            formats_widget,
+            preview,
+        ]
+        .padding(5)
+        .spacing(10);
+
+        container(content)
+            .style(styles::container_styles::first_class_container_rounded_theme)
+            .width(1000)
+            .into()
+    }
+}
+
+impl Default for DateAndTime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
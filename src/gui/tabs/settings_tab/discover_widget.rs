@@ -104,6 +104,7 @@ mod hidden_series {
     use iced::widget::{button, column, container, row, scrollable, text, Column, Space};
     use iced::{Element, Task};
 
+    use crate::core::date_time_display::DateTimeDisplay;
     use crate::{core::posters_hiding::HIDDEN_SERIES, gui::styles};
 
     #[derive(Clone, Debug)]
@@ -178,9 +179,12 @@ mod hidden_series {
                 .style(styles::button_styles::transparent_button_with_rounded_border_theme)
                 .on_press(Message::UnhideSeries(series_id));
             let premier_date: Element<'_, Message> = if let Some(premier_date) = premier_date {
-                text(format!("({})", premier_date))
-                    .style(styles::text_styles::accent_color_theme)
-                    .into()
+                text(format!(
+                    "({})",
+                    DateTimeDisplay::from_settings().date_str(&premier_date)
+                ))
+                .style(styles::text_styles::accent_color_theme)
+                .into()
             } else {
                 Space::new(0, 0).into()
             };
@@ -6,7 +6,9 @@ use crate::gui::assets::icons::GEAR_WIDE_CONNECTED;
 use crate::gui::styles;
 use about_widget::{About, Message as AboutMessage};
 use appearance_widget::{Appearance, Message as AppearanceMessage};
+use calendar_feed_widget::{CalendarFeed, Message as CalendarFeedMessage};
 use database_widget::{Database, Message as DatabaseMessage};
+use date_and_time_widget::{DateAndTime, Message as DateAndTimeMessage};
 use discover_widget::{Discover, Message as DiscoverMessage};
 use notifications_widget::{Message as NotificationsMessage, Notifications};
 use settings_controls_widget::{Message as SettingsControlsMessage, SettingsControls};
@@ -15,7 +17,9 @@ use super::Tab;
 
 mod about_widget;
 mod appearance_widget;
+mod calendar_feed_widget;
 mod database_widget;
+mod date_and_time_widget;
 mod discover_widget;
 mod notifications_widget;
 mod settings_controls_widget;
@@ -23,8 +27,10 @@ mod settings_controls_widget;
 #[derive(Debug, Clone)]
 pub enum Message {
     Appearance(AppearanceMessage),
+    DateAndTime(DateAndTimeMessage),
     Database(DatabaseMessage),
     Notifications(NotificationsMessage),
+    CalendarFeed(CalendarFeedMessage),
     Discover(DiscoverMessage),
     About(AboutMessage),
     Controls(SettingsControlsMessage),
@@ -33,8 +39,10 @@ pub enum Message {
 
 pub struct SettingsTab {
     appearance_settings: Appearance,
+    date_and_time_settings: DateAndTime,
     database_settings: Database,
     notifications_settings: Notifications,
+    calendar_feed_settings: CalendarFeed,
     discover_settings: Discover,
     about: About,
     controls_settings: SettingsControls,
@@ -44,17 +52,23 @@ pub struct SettingsTab {
 impl SettingsTab {
     pub fn new() -> (Self, Task<Message>) {
         let (about_widget, about_command) = About::new();
+        let (database_widget, database_command) = Database::new();
         (
             Self {
                 appearance_settings: Appearance,
-                database_settings: Database::new(),
+                date_and_time_settings: DateAndTime::default(),
+                database_settings: database_widget,
                 notifications_settings: Notifications,
+                calendar_feed_settings: CalendarFeed,
                 discover_settings: Discover::default(),
                 about: about_widget,
                 scrollable_offset: RelativeOffset::START,
                 controls_settings: SettingsControls,
             },
-            about_command.map(Message::About),
+            Task::batch([
+                about_command.map(Message::About),
+                database_command.map(Message::Database),
+            ]),
         )
     }
 
@@ -78,22 +92,33 @@ impl SettingsTab {
             }
             Message::About(message) => return self.about.update(message).map(Message::About),
             Message::Notifications(message) => self.notifications_settings.update(message),
+            Message::CalendarFeed(message) => self.calendar_feed_settings.update(message),
             Message::Appearance(message) => self.appearance_settings.update(message),
-            Message::Controls(message) => self.controls_settings.update(message),
+            Message::DateAndTime(message) => self.date_and_time_settings.update(message),
+            Message::Controls(message) => {
+                return self
+                    .controls_settings
+                    .update(message)
+                    .map(Message::Controls)
+            }
             Message::PageScrolled(view_port) => {
                 self.scrollable_offset = view_port.relative_offset()
             }
         }
         Task::none()
     }
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
         let settings_body = scrollable(
             column![
                 self.appearance_settings.view().map(Message::Appearance),
+                self.date_and_time_settings.view().map(Message::DateAndTime),
                 self.database_settings.view().map(Message::Database),
                 self.notifications_settings
                     .view()
                     .map(Message::Notifications),
+                self.calendar_feed_settings
+                    .view()
+                    .map(Message::CalendarFeed),
                 self.discover_settings.view().map(Message::Discover),
                 self.about.view().map(Message::About),
             ]
@@ -1,14 +1,43 @@
-use iced::widget::{column, container, text, toggler};
+use chrono::Timelike;
+use iced::widget::{button, column, container, row, text, toggler, Column};
 use iced::{Element, Length};
 use iced_aw::NumberInput;
 
-use crate::core::settings_config::SETTINGS;
+use crate::core::settings_config::{Config, DailyDigestSettings, QuietHoursSettings, SETTINGS};
 use crate::gui::styles;
 
+/// The times of the day set in the notification settings
+#[derive(Debug, Clone, Copy)]
+pub enum TimeOfDay {
+    DailyDigest,
+    QuietHoursStart,
+    QuietHoursEnd,
+}
+
+impl TimeOfDay {
+    fn get_mut(self, config: &mut Config) -> &mut chrono::NaiveTime {
+        let notification_settings = &mut config.notifications;
+        match self {
+            TimeOfDay::DailyDigest => &mut notification_settings.daily_digest.time,
+            TimeOfDay::QuietHoursStart => &mut notification_settings.quiet_hours.start,
+            TimeOfDay::QuietHoursEnd => &mut notification_settings.quiet_hours.end,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
-    TimeChanged(u32),
+    TimeChanged(usize, u32),
+    TimeAdded,
+    TimeRemoved(usize),
     NotifyWhenOutdated(bool),
+    NotifySeriesChanges(bool),
+    DesktopNotifications(bool),
+    DailyDigestToggled(bool),
+    DailyDigestReplacesRemindersToggled(bool),
+    QuietHoursToggled(bool),
+    HourChanged(TimeOfDay, u32),
+    MinuteChanged(TimeOfDay, u32),
 }
 
 #[derive(Default)]
@@ -17,13 +46,31 @@ pub struct Notifications;
 impl Notifications {
     pub fn update(&mut self, message: Message) {
         match message {
-            Message::TimeChanged(new_time) => {
+            Message::TimeChanged(index, new_time) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .notifications
+                    .times_to_notify[index] = new_time;
+            }
+            Message::TimeAdded => {
                 SETTINGS
                     .write()
                     .unwrap()
                     .change_settings()
                     .notifications
-                    .time_to_notify = new_time;
+                    .times_to_notify
+                    .push(60);
+            }
+            Message::TimeRemoved(index) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .notifications
+                    .times_to_notify
+                    .remove(index);
             }
             Message::NotifyWhenOutdated(notify_when_outdate) => {
                 SETTINGS
@@ -33,6 +80,63 @@ impl Notifications {
                     .notifications
                     .notify_when_outdated = notify_when_outdate;
             }
+            Message::NotifySeriesChanges(notify_series_changes) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .notifications
+                    .notify_series_changes = notify_series_changes;
+            }
+            Message::DesktopNotifications(desktop_notifications) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .notifications
+                    .desktop_notifications = desktop_notifications;
+            }
+            Message::DailyDigestToggled(enabled) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .notifications
+                    .daily_digest
+                    .enabled = enabled;
+            }
+            Message::DailyDigestReplacesRemindersToggled(replaces_reminders) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .notifications
+                    .daily_digest
+                    .replaces_reminders = replaces_reminders;
+            }
+            Message::QuietHoursToggled(enabled) => {
+                SETTINGS
+                    .write()
+                    .unwrap()
+                    .change_settings()
+                    .notifications
+                    .quiet_hours
+                    .enabled = enabled;
+            }
+            Message::HourChanged(time_of_day, hour) => {
+                let mut settings = SETTINGS.write().unwrap();
+                let time = time_of_day.get_mut(settings.change_settings());
+                if let Some(new_time) = time.with_hour(hour) {
+                    *time = new_time;
+                }
+            }
+            Message::MinuteChanged(time_of_day, minute) => {
+                let mut settings = SETTINGS.write().unwrap();
+                let time = time_of_day.get_mut(settings.change_settings());
+                if let Some(new_time) = time.with_minute(minute) {
+                    *time = new_time;
+                }
+            }
         }
     }
     pub fn view(&self) -> Element<'_, Message> {
@@ -43,26 +147,38 @@ impl Notifications {
             .notifications
             .clone();
 
-        let current_time_to_notify = notification_settings.time_to_notify;
         let notify_when_outdated = notification_settings.notify_when_outdated;
 
         let notifications_info = column![
             text("When to notify"),
-            text(format!(
-                "System notification will be sent {} minutes before an episode release",
-                current_time_to_notify
-            ))
-            .size(11)
+            text("A system notification will be sent for each of these times before an episode release")
+                .size(11)
         ];
 
-        let time_to_notify = NumberInput::new(
-            current_time_to_notify,
-            u32::MIN..u32::MAX,
-            Message::TimeChanged,
-        )
-        .width(Length::Fixed(200.0));
+        let times_to_notify = notification_settings
+            .times_to_notify
+            .into_iter()
+            .enumerate()
+            .map(|(index, time_to_notify)| {
+                row![
+                    NumberInput::new(time_to_notify, u32::MIN..u32::MAX, move |new_time| {
+                        Message::TimeChanged(index, new_time)
+                    })
+                    .width(Length::Fixed(200.0)),
+                    text("minutes before release").size(11),
+                    button("Remove").on_press(Message::TimeRemoved(index)),
+                ]
+                .spacing(5)
+                .align_y(iced::Alignment::Center)
+                .into()
+            });
 
-        let when_to_notify = column![notifications_info, time_to_notify,].spacing(5);
+        let when_to_notify = column![
+            notifications_info,
+            Column::with_children(times_to_notify).spacing(5),
+            button("Add reminder").on_press(Message::TimeAdded),
+        ]
+        .spacing(5);
 
         let notify_when_outdated = toggler(notify_when_outdated)
             .label("Notify when outdated")
@@ -70,12 +186,44 @@ impl Notifications {
             .spacing(10)
             .width(Length::Shrink);
 
+        let notify_series_changes = column![
+            toggler(notification_settings.notify_series_changes)
+                .label("Notify series changes")
+                .on_toggle(Message::NotifySeriesChanges)
+                .spacing(10)
+                .width(Length::Shrink),
+            text(
+                "Tracked series ending, getting a new season or a premiere date, series that are not tracked are never checked"
+            )
+            .size(11),
+        ];
+
+        let desktop_notifications = column![
+            toggler(notification_settings.desktop_notifications)
+                .label("Desktop notifications")
+                .on_toggle(Message::DesktopNotifications)
+                .spacing(10)
+                .width(Length::Shrink),
+            text(format!(
+                "Notifications are also sent to {} backend(s) set in the config file",
+                notification_settings.backends.len()
+            ))
+            .size(11),
+        ];
+
+        let daily_digest = Self::daily_digest_widget(&notification_settings.daily_digest);
+        let quiet_hours = Self::quiet_hours_widget(&notification_settings.quiet_hours);
+
         let content = column![
             text("Notifications")
                 .style(styles::text_styles::accent_color_theme)
                 .size(21),
             when_to_notify,
             notify_when_outdated,
+            notify_series_changes,
+            desktop_notifications,
+            daily_digest,
+            quiet_hours,
         ]
         .spacing(10);
 
@@ -85,4 +233,91 @@ impl Notifications {
             .width(1000)
             .into()
     }
+
+    fn daily_digest_widget(
+        daily_digest_settings: &DailyDigestSettings,
+    ) -> Element<'static, Message> {
+        let daily_digest_info = column![
+            text("Daily digest"),
+            text("A single notification sent every day listing the releases of the day and the number of unwatched episodes")
+                .size(11)
+        ];
+
+        let enabled_toggler = toggler(daily_digest_settings.enabled)
+            .label("Send daily digest")
+            .on_toggle(Message::DailyDigestToggled)
+            .spacing(10)
+            .width(Length::Shrink);
+
+        let mut content = column![daily_digest_info, enabled_toggler].spacing(5);
+
+        if daily_digest_settings.enabled {
+            let digest_time = row![
+                text("Send at").size(11),
+                time_of_day_input(daily_digest_settings.time, TimeOfDay::DailyDigest),
+            ]
+            .spacing(5)
+            .align_y(iced::Alignment::Center);
+
+            let replaces_reminders_toggler = toggler(daily_digest_settings.replaces_reminders)
+                .label("Send instead of the episode release reminders")
+                .on_toggle(Message::DailyDigestReplacesRemindersToggled)
+                .spacing(10)
+                .width(Length::Shrink);
+
+            content = content.push(digest_time).push(replaces_reminders_toggler);
+        }
+
+        content.into()
+    }
+
+    fn quiet_hours_widget(quiet_hours_settings: &QuietHoursSettings) -> Element<'static, Message> {
+        let quiet_hours_info = column![
+            text("Quiet hours"),
+            text("Notifications during quiet hours are held back and sent together once they are over")
+                .size(11)
+        ];
+
+        let enabled_toggler = toggler(quiet_hours_settings.enabled)
+            .label("Enable quiet hours")
+            .on_toggle(Message::QuietHoursToggled)
+            .spacing(10)
+            .width(Length::Shrink);
+
+        let mut content = column![quiet_hours_info, enabled_toggler].spacing(5);
+
+        if quiet_hours_settings.enabled {
+            let quiet_hours = row![
+                text("From").size(11),
+                time_of_day_input(quiet_hours_settings.start, TimeOfDay::QuietHoursStart),
+                text("to").size(11),
+                time_of_day_input(quiet_hours_settings.end, TimeOfDay::QuietHoursEnd),
+            ]
+            .spacing(5)
+            .align_y(iced::Alignment::Center);
+
+            content = content.push(quiet_hours);
+        }
+
+        content.into()
+    }
+}
+
+fn time_of_day_input(time: chrono::NaiveTime, time_of_day: TimeOfDay) -> Element<'static, Message> {
+    row![
+        NumberInput::new(time.hour(), 0..=23, move |hour| Message::HourChanged(
+            time_of_day,
+            hour
+        ))
+        .width(Length::Fixed(80.0)),
+        text(":"),
+        NumberInput::new(time.minute(), 0..=59, move |minute| Message::MinuteChanged(
+            time_of_day,
+            minute
+        ))
+        .width(Length::Fixed(80.0)),
+    ]
+    .spacing(5)
+    .align_y(iced::Alignment::Center)
+    .into()
 }
@@ -1,6 +1,8 @@
 use iced::widget::{button, horizontal_space, row};
-use iced::Element;
+use iced::{Element, Task};
 
+use crate::core::caching::image_cache;
+use crate::core::offline::OFFLINE;
 use crate::core::settings_config::SETTINGS;
 
 #[derive(Clone, Debug)]
@@ -8,18 +10,35 @@ pub enum Message {
     Save,
     Reset,
     RestoreDefaults,
+    ImageCacheSizeLimited,
 }
 
 #[derive(Default)]
 pub struct SettingsControls;
 
 impl SettingsControls {
-    pub fn update(&mut self, message: Message) {
-        match message {
-            Message::Save => SETTINGS.write().unwrap().save_settings(),
-            Message::Reset => SETTINGS.write().unwrap().reset_settings(),
-            Message::RestoreDefaults => SETTINGS.write().unwrap().set_default_settings(),
-        }
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        let command = match message {
+            Message::Save => {
+                SETTINGS.write().unwrap().save_settings();
+                // The maximum images cache size might have been lowered
+                Task::perform(image_cache::limit_image_cache_size(), |_| {
+                    Message::ImageCacheSizeLimited
+                })
+            }
+            Message::Reset => {
+                SETTINGS.write().unwrap().reset_settings();
+                Task::none()
+            }
+            Message::RestoreDefaults => {
+                SETTINGS.write().unwrap().set_default_settings();
+                Task::none()
+            }
+            Message::ImageCacheSizeLimited => return Task::none(),
+        };
+        // Offline mode might have been changed by resetting the settings
+        OFFLINE.refresh_status();
+        command
     }
     pub fn view(&self) -> Element<'_, Message> {
         let mut save_settings_button = button("Save");
@@ -0,0 +1,301 @@
+//! Canvas charts for the statistics, drawn with the colors of the current theme
+
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke, Text};
+use iced::{mouse, Color, Pixels, Point, Rectangle, Renderer, Size, Theme};
+
+use crate::gui::styles::colors::accent_color;
+
+const LABEL_SIZE: f32 = 11.0;
+/// The space kept for the labels under the bars and the line
+const BOTTOM_LABELS_HEIGHT: f32 = 16.0;
+/// The space kept for the value labels above the bars and the line
+const TOP_LABELS_HEIGHT: f32 = 14.0;
+/// The most labels displayed under a chart, the rest are skipped to avoid overlapping
+const MAX_BOTTOM_LABELS: usize = 8;
+/// The height of every bar of a horizontal bar chart together with it's spacing
+pub const HORIZONTAL_BAR_ROW_HEIGHT: f32 = 18.0;
+
+pub struct ChartEntry {
+    pub label: String,
+    pub value: f32,
+    /// How the value is displayed
+    pub value_label: String,
+}
+
+impl ChartEntry {
+    pub fn new(label: impl Into<String>, value: f32, value_label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            value_label: value_label.into(),
+        }
+    }
+}
+
+/// A bar chart with the bars laid out either from left to right or from top to bottom
+pub struct BarChart {
+    entries: Vec<ChartEntry>,
+    horizontal_bars: bool,
+}
+
+impl BarChart {
+    /// Vertical bars from left to right, with labels under them
+    pub fn vertical(entries: Vec<ChartEntry>) -> Self {
+        Self {
+            entries,
+            horizontal_bars: false,
+        }
+    }
+
+    /// Horizontal bars from top to bottom, each taking `HORIZONTAL_BAR_ROW_HEIGHT`
+    pub fn horizontal(entries: Vec<ChartEntry>) -> Self {
+        Self {
+            entries,
+            horizontal_bars: true,
+        }
+    }
+
+    fn draw_vertical_bars(&self, frame: &mut Frame, text_color: Color) {
+        let size = frame.size();
+        let max_value = max_value(&self.entries);
+        let slot_width = size.width / self.entries.len() as f32;
+        let bar_width = (slot_width * 0.7).max(1.0);
+        let chart_height = size.height - BOTTOM_LABELS_HEIGHT - TOP_LABELS_HEIGHT;
+        let label_step = label_step(self.entries.len());
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let slot_center = slot_width * (index as f32 + 0.5);
+            let bar_height = chart_height * entry.value / max_value;
+            let bar_top = TOP_LABELS_HEIGHT + chart_height - bar_height;
+
+            frame.fill_rectangle(
+                Point::new(slot_center - bar_width / 2.0, bar_top),
+                Size::new(bar_width, bar_height),
+                accent_color(),
+            );
+
+            if entry.value > 0.0 && slot_width >= LABEL_SIZE * 2.0 {
+                frame.fill_text(label(
+                    &entry.value_label,
+                    Point::new(slot_center, bar_top),
+                    text_color,
+                    Horizontal::Center,
+                    Vertical::Bottom,
+                ));
+            }
+
+            if index % label_step == 0 {
+                frame.fill_text(label(
+                    &entry.label,
+                    Point::new(slot_center, size.height),
+                    text_color,
+                    Horizontal::Center,
+                    Vertical::Bottom,
+                ));
+            }
+        }
+
+        draw_base_line(frame, size.height - BOTTOM_LABELS_HEIGHT, text_color);
+    }
+
+    fn draw_horizontal_bars(&self, frame: &mut Frame, text_color: Color) {
+        let size = frame.size();
+        let max_value = max_value(&self.entries);
+        let labels_width = size.width * 0.25;
+        let value_labels_width = size.width * 0.2;
+        let chart_width = size.width - labels_width - value_labels_width;
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let row_top = HORIZONTAL_BAR_ROW_HEIGHT * index as f32;
+            let row_center = row_top + HORIZONTAL_BAR_ROW_HEIGHT / 2.0;
+            let bar_width = chart_width * entry.value / max_value;
+
+            frame.fill_text(label(
+                &entry.label,
+                Point::new(labels_width - 5.0, row_center),
+                text_color,
+                Horizontal::Right,
+                Vertical::Center,
+            ));
+
+            frame.fill_rectangle(
+                Point::new(labels_width, row_top + HORIZONTAL_BAR_ROW_HEIGHT * 0.15),
+                Size::new(bar_width, HORIZONTAL_BAR_ROW_HEIGHT * 0.7),
+                accent_color(),
+            );
+
+            frame.fill_text(label(
+                &entry.value_label,
+                Point::new(labels_width + bar_width + 5.0, row_center),
+                text_color,
+                Horizontal::Left,
+                Vertical::Center,
+            ));
+        }
+    }
+}
+
+impl<Message> canvas::Program<Message> for BarChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if !self.entries.is_empty() {
+            let text_color = theme.palette().text;
+            if self.horizontal_bars {
+                self.draw_horizontal_bars(&mut frame, text_color);
+            } else {
+                self.draw_vertical_bars(&mut frame, text_color);
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// A line chart with the area under the line filled
+pub struct LineChart {
+    entries: Vec<ChartEntry>,
+}
+
+impl LineChart {
+    pub fn new(entries: Vec<ChartEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+impl<Message> canvas::Program<Message> for LineChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        if self.entries.is_empty() {
+            return vec![frame.into_geometry()];
+        }
+
+        let text_color = theme.palette().text;
+        let size = frame.size();
+        let max_value = max_value(&self.entries);
+        let slot_width = size.width / self.entries.len() as f32;
+        let chart_bottom = size.height - BOTTOM_LABELS_HEIGHT;
+        let chart_height = chart_bottom - TOP_LABELS_HEIGHT;
+
+        let points: Vec<Point> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                Point::new(
+                    slot_width * (index as f32 + 0.5),
+                    chart_bottom - chart_height * entry.value / max_value,
+                )
+            })
+            .collect();
+
+        let area = Path::new(|builder| {
+            builder.move_to(Point::new(points[0].x, chart_bottom));
+            points.iter().for_each(|point| builder.line_to(*point));
+            builder.line_to(Point::new(points[points.len() - 1].x, chart_bottom));
+            builder.close();
+        });
+        frame.fill(&area, accent_color().scale_alpha(0.3));
+
+        let line = Path::new(|builder| {
+            builder.move_to(points[0]);
+            points
+                .iter()
+                .skip(1)
+                .for_each(|point| builder.line_to(*point));
+        });
+        frame.stroke(
+            &line,
+            Stroke::default().with_color(accent_color()).with_width(2.0),
+        );
+
+        let label_step = label_step(self.entries.len());
+        for (index, (entry, point)) in self.entries.iter().zip(points.iter()).enumerate() {
+            if index % label_step == 0 {
+                frame.fill_text(label(
+                    &entry.label,
+                    Point::new(point.x, size.height),
+                    text_color,
+                    Horizontal::Center,
+                    Vertical::Bottom,
+                ));
+            }
+        }
+
+        // Only the latest value is labelled as the line only ever goes up
+        if let (Some(entry), Some(point)) = (self.entries.last(), points.last()) {
+            frame.fill_text(label(
+                &entry.value_label,
+                *point,
+                text_color,
+                Horizontal::Right,
+                Vertical::Bottom,
+            ));
+        }
+
+        draw_base_line(&mut frame, chart_bottom, text_color);
+
+        vec![frame.into_geometry()]
+    }
+}
+
+fn max_value(entries: &[ChartEntry]) -> f32 {
+    entries
+        .iter()
+        .map(|entry| entry.value)
+        .fold(0.0, f32::max)
+        .max(1.0)
+}
+
+/// Every how many entries a label is displayed under a chart
+fn label_step(total_entries: usize) -> usize {
+    total_entries.div_ceil(MAX_BOTTOM_LABELS).max(1)
+}
+
+fn draw_base_line(frame: &mut Frame, y: f32, color: Color) {
+    let width = frame.width();
+    frame.stroke(
+        &Path::line(Point::new(0.0, y), Point::new(width, y)),
+        Stroke::default()
+            .with_color(color.scale_alpha(0.3))
+            .with_width(1.0),
+    );
+}
+
+fn label(
+    content: &str,
+    position: Point,
+    color: Color,
+    horizontal_alignment: Horizontal,
+    vertical_alignment: Vertical,
+) -> Text {
+    Text {
+        content: content.to_owned(),
+        position,
+        color,
+        size: Pixels(LABEL_SIZE),
+        horizontal_alignment,
+        vertical_alignment,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,222 @@
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, text, text_input, Column, Space,
+};
+use iced::{Alignment, Element, Length, Task};
+
+use crate::core::database;
+use crate::core::date_time_display::DateTimeDisplay;
+use crate::core::goals::{
+    self, Goal, GoalKind, GoalsOverview, ALL_GOAL_KINDS, MAX_EPISODES_PER_WEEK,
+};
+use crate::core::statistics::WatchedSeries;
+use crate::gui::styles;
+use crate::gui::troxide_widget;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    OverviewReceived(GoalsOverview),
+    KindSelected(GoalKind),
+    EpisodesInput(String),
+    SeriesSelected(SeriesChoice),
+    DeadlineInput(String),
+    AddGoal,
+    RemoveGoal(u64),
+}
+
+/// A series to choose for a `FinishSeries` goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeriesChoice {
+    id: u32,
+    name: String,
+}
+
+impl std::fmt::Display for SeriesChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+pub struct Goals {
+    overview: GoalsOverview,
+    kind: GoalKind,
+    episodes: String,
+    series: Option<SeriesChoice>,
+    deadline: String,
+}
+
+impl Goals {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                overview: GoalsOverview::default(),
+                kind: GoalKind::EpisodesPerWeek,
+                episodes: String::new(),
+                series: None,
+                deadline: String::new(),
+            },
+            load_overview(),
+        )
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<Message> {
+        match message {
+            Message::OverviewReceived(overview) => {
+                self.overview = overview;
+                Task::none()
+            }
+            Message::KindSelected(kind) => {
+                self.kind = kind;
+                Task::none()
+            }
+            Message::EpisodesInput(episodes) => {
+                if episodes.chars().all(|char| char.is_ascii_digit()) {
+                    self.episodes = episodes;
+                }
+                Task::none()
+            }
+            Message::SeriesSelected(series) => {
+                self.series = Some(series);
+                Task::none()
+            }
+            Message::DeadlineInput(deadline) => {
+                self.deadline = deadline;
+                Task::none()
+            }
+            Message::AddGoal => {
+                let Some(goal) = self.new_goal() else {
+                    return Task::none();
+                };
+                database::DB.add_goal(&goal);
+
+                self.episodes.clear();
+                self.series = None;
+                self.deadline.clear();
+                load_overview()
+            }
+            Message::RemoveGoal(id) => {
+                database::DB.remove_goal(id);
+                load_overview()
+            }
+        }
+    }
+
+    pub fn view(&self, watched_series: &[WatchedSeries]) -> Element<'_, Message> {
+        let date_time_display = DateTimeDisplay::from_settings();
+
+        let goals: Element<'_, Message> = if self.overview.goals.is_empty() {
+            text("Set a goal to keep up with your series").into()
+        } else {
+            Column::with_children(self.overview.goals.iter().map(|progress| {
+                row![
+                    container(troxide_widget::goals::goal_progress(
+                        progress,
+                        &date_time_display
+                    ))
+                    .width(Length::Fill),
+                    button(text("Remove").size(11))
+                        .on_press(Message::RemoveGoal(progress.id))
+                        .style(styles::button_styles::transparent_button_with_rounded_border_theme),
+                ]
+                .spacing(10)
+                .align_y(Alignment::Center)
+                .into()
+            }))
+            .spacing(10)
+            .into()
+        };
+
+        let content = column![
+            row![
+                text("Goals"),
+                Space::with_width(Length::Fill),
+                troxide_widget::goals::streaks(&self.overview.streaks),
+            ]
+            .align_y(Alignment::Center),
+            scrollable(container(goals).padding(5).width(Length::Fill))
+                .direction(styles::scrollable_styles::vertical_direction())
+                .height(Length::Fill),
+            self.new_goal_widget(watched_series),
+        ]
+        .spacing(10);
+
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(10)
+            .style(styles::container_styles::first_class_container_rounded_theme)
+            .into()
+    }
+
+    fn new_goal_widget(&self, watched_series: &[WatchedSeries]) -> Element<'_, Message> {
+        let kind = pick_list(ALL_GOAL_KINDS, Some(self.kind), Message::KindSelected).text_size(11);
+
+        let goal_inputs: Element<'_, Message> = match self.kind {
+            GoalKind::EpisodesPerWeek => text_input("episodes", &self.episodes)
+                .on_input(Message::EpisodesInput)
+                .on_submit(Message::AddGoal)
+                .size(11)
+                .width(100)
+                .into(),
+            GoalKind::FinishSeries => {
+                let mut series_choices: Vec<SeriesChoice> = watched_series
+                    .iter()
+                    .map(|series| SeriesChoice {
+                        id: series.series_info.id,
+                        name: series.series_info.name.clone(),
+                    })
+                    .collect();
+                series_choices.sort_by(|a, b| a.name.cmp(&b.name));
+
+                row![
+                    pick_list(series_choices, self.series.clone(), Message::SeriesSelected)
+                        .placeholder("series")
+                        .text_size(11),
+                    text_input("deadline (YYYY-MM-DD)", &self.deadline)
+                        .on_input(Message::DeadlineInput)
+                        .on_submit(Message::AddGoal)
+                        .size(11)
+                        .width(150),
+                ]
+                .spacing(5)
+                .into()
+            }
+        };
+
+        row![
+            kind,
+            goal_inputs,
+            button(text("Add goal").size(11))
+                .on_press_maybe(self.new_goal().map(|_| Message::AddGoal)),
+        ]
+        .spacing(5)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    /// The goal from the inputs, `None` when they don't make up one
+    fn new_goal(&self) -> Option<Goal> {
+        match self.kind {
+            GoalKind::EpisodesPerWeek => self
+                .episodes
+                .parse()
+                .ok()
+                .filter(|episodes| (1..=MAX_EPISODES_PER_WEEK).contains(episodes))
+                .map(Goal::EpisodesPerWeek),
+            GoalKind::FinishSeries => {
+                let series = self.series.as_ref()?;
+                let deadline =
+                    chrono::NaiveDate::parse_from_str(self.deadline.trim(), "%Y-%m-%d").ok()?;
+
+                Some(Goal::FinishSeries {
+                    series_id: series.id,
+                    series_name: series.name.clone(),
+                    deadline,
+                })
+            }
+        }
+    }
+}
+
+fn load_overview() -> Task<Message> {
+    Task::perform(goals::get_goals_overview(), Message::OverviewReceived)
+}
@@ -1,11 +1,19 @@
-use iced::widget::{center, column, container, row, scrollable, text, Row, Space};
+use iced::widget::{
+    canvas, center, column, container, pick_list, progress_bar, row, scrollable, text, Column, Row,
+    Space,
+};
 use iced::{Alignment, Element, Length};
 use iced_aw::{Grid, GridRow};
 
-use crate::core::api::tv_maze::series_information::SeriesMainInformation;
 use crate::core::database;
+use crate::core::statistics::{
+    self, BreakdownEntry, BreakdownKind, BreakdownSorting, ChartPeriod, PeriodWatches,
+    ProgressSorting, SeriesProgress, WatchedSeries, ALL_BREAKDOWN_KINDS, ALL_BREAKDOWN_SORTINGS,
+    ALL_CHART_PERIODS, ALL_PROGRESS_SORTINGS,
+};
 use crate::gui::{helpers, styles};
 
+use super::charts::{BarChart, ChartEntry, LineChart, HORIZONTAL_BAR_ROW_HEIGHT};
 use super::Message;
 
 pub fn watch_count() -> Element<'static, Message> {
@@ -55,23 +63,21 @@ pub fn watch_count() -> Element<'static, Message> {
         .into()
 }
 
-pub fn time_count(
-    series_infos_and_time: &[(SeriesMainInformation, Option<u32>)],
-) -> Element<'_, Message> {
-    let total_average_minutes: u32 = series_infos_and_time
+pub fn time_count(watched_series: &[WatchedSeries]) -> Element<'_, Message> {
+    let total_minutes: u32 = watched_series
         .iter()
-        .map(|(_, average_runtime)| average_runtime.unwrap_or(0))
+        .map(|series| series.watch_time.unwrap_or(0))
         .sum();
 
     let total_minutes_count = column![
-        text(total_average_minutes)
+        text(total_minutes)
             .style(styles::text_styles::accent_color_theme)
             .size(31),
         text("Minutes").size(11)
     ]
     .align_x(Alignment::Center);
 
-    let times = helpers::time::NaiveTime::new(total_average_minutes).as_parts();
+    let times = helpers::time::NaiveTime::new(total_minutes).as_parts();
 
     let complete_time_count: Element<'_, Message> = if times.is_empty() {
         Space::new(0, 0).into()
@@ -101,7 +107,7 @@ pub fn time_count(
     };
 
     let content = column![
-        text("Total average time spent watching Series"),
+        text("Total time spent watching Series"),
         total_minutes_count,
         complete_time_count,
     ]
@@ -114,44 +120,189 @@ pub fn time_count(
         .into()
 }
 
-pub fn genre_stats(series_infos: Vec<&SeriesMainInformation>) -> Element<'_, Message> {
-    use crate::core::api::tv_maze::series_information::Genre;
-    use std::collections::HashMap;
-
-    if series_infos.is_empty() {
+pub fn rating_stats(average_rating: Option<f32>) -> Element<'static, Message> {
+    let Some(average_rating) = average_rating else {
         return Space::new(0, 0).into();
-    }
-
-    let mut genre_count: HashMap<Genre, usize> = HashMap::new();
+    };
 
-    series_infos.iter().for_each(|series_info| {
-        series_info.get_genres().into_iter().for_each(|genre| {
-            genre_count
-                .entry(genre)
-                .and_modify(|count| *count += 1)
-                .or_insert(1);
-        })
-    });
+    let content = column![
+        text("Average rating of what you watch"),
+        text(format!("{:.1}", average_rating))
+            .size(31)
+            .style(styles::text_styles::accent_color_theme),
+        text("out of 10").size(11),
+    ]
+    .align_x(Alignment::Center)
+    .spacing(5);
 
-    let mut genre_count: Vec<(Genre, usize)> = genre_count.into_iter().collect();
+    center(content)
+        .padding(10)
+        .style(styles::container_styles::first_class_container_rounded_theme)
+        .into()
+}
 
-    genre_count.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+pub fn breakdown_stats(
+    entries: &[BreakdownEntry],
+    kind: BreakdownKind,
+    sorting: BreakdownSorting,
+) -> Element<'static, Message> {
+    let controls = row![
+        pick_list(
+            ALL_BREAKDOWN_KINDS,
+            Some(kind),
+            Message::BreakdownKindSelected
+        )
+        .text_size(11),
+        pick_list(
+            ALL_BREAKDOWN_SORTINGS,
+            Some(sorting),
+            Message::BreakdownSortingSelected
+        )
+        .text_size(11),
+    ]
+    .spacing(5);
 
     let mut content = Grid::new();
 
-    for (genre, count) in genre_count.into_iter() {
+    for entry in entries {
         content = content.push(
             GridRow::new()
-                .push(text(format!("{}    ", genre)).style(styles::text_styles::accent_color_theme))
-                .push(text(format!("{} series", count))),
+                .push(
+                    text(format!("{}    ", entry.name))
+                        .style(styles::text_styles::accent_color_theme),
+                )
+                .push(text(format!("{} series    ", entry.series)))
+                .push(text(format!("{} episodes    ", entry.watched_episodes)))
+                .push(text(watch_time_str(entry.watch_time))),
         );
     }
 
-    let content = column![text("Genre Stats"), content]
-        .align_x(Alignment::Center)
-        .spacing(10)
+    stats_container(text("Breakdown"), controls, content)
+}
+
+pub fn progress_stats(
+    series_progress: &[SeriesProgress],
+    sorting: ProgressSorting,
+) -> Element<'static, Message> {
+    let controls = pick_list(
+        ALL_PROGRESS_SORTINGS,
+        Some(sorting),
+        Message::ProgressSortingSelected,
+    )
+    .text_size(11);
+
+    let content = Column::with_children(series_progress.iter().map(|progress| {
+        column![
+            row![
+                text(progress.name.clone()).size(13),
+                Space::with_width(Length::Fill),
+                text(format!(
+                    "{}/{} ({:.0}%)",
+                    progress.watched_episodes,
+                    progress.watchable_episodes,
+                    progress.completion() * 100.0
+                ))
+                .size(11),
+            ],
+            progress_bar(0.0..=1.0, progress.completion()).height(5),
+        ]
+        .spacing(2)
+        .into()
+    }))
+    .spacing(5);
+
+    stats_container(text("Completion"), controls, content)
+}
+
+pub fn furthest_behind_stats(series_progress: &[SeriesProgress]) -> Element<'static, Message> {
+    let content: Element<'_, Message> = if series_progress.is_empty() {
+        text("You're all caught up!").into()
+    } else {
+        let mut content = Grid::new();
+        for progress in series_progress {
+            content = content.push(
+                GridRow::new()
+                    .push(text(format!("{}    ", progress.name)))
+                    .push(
+                        text(format!("{} behind", progress.unwatched_episodes()))
+                            .style(styles::text_styles::accent_color_theme),
+                    ),
+            );
+        }
+        content.into()
+    };
+
+    stats_container(text("Furthest behind"), Space::new(0, 0), content)
+}
+
+/// A card with a title and controls on top of a scrollable content
+fn stats_container<'a>(
+    title: impl Into<Element<'a, Message>>,
+    controls: impl Into<Element<'a, Message>>,
+    content: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    let content = column![
+        row![
+            title.into(),
+            Space::with_width(Length::Fill),
+            controls.into()
+        ]
+        .align_y(Alignment::Center),
+        scrollable(container(content).padding(5).width(Length::Fill))
+            .direction(styles::scrollable_styles::vertical_direction())
+            .height(Length::Fill),
+    ]
+    .spacing(10);
+
+    container(content)
         .width(Length::Fill)
-        .padding(10);
+        .height(Length::Fill)
+        .padding(10)
+        .style(styles::container_styles::first_class_container_rounded_theme)
+        .into()
+}
+
+/// The largest part of the watch time e.g. `3 Days`
+fn watch_time_str(minutes: u32) -> String {
+    helpers::time::NaiveTime::new(minutes)
+        .largest_part()
+        .map(|(time_value, time_text)| format!("{} {}", time_value, time_text))
+        .unwrap_or_else(|| "0 Minutes".to_owned())
+}
+
+pub fn genre_stats(watched_series: &[WatchedSeries]) -> Element<'_, Message> {
+    if watched_series.is_empty() {
+        return Space::new(0, 0).into();
+    }
+
+    let genre_share = statistics::genre_share(watched_series);
+    let chart_height = HORIZONTAL_BAR_ROW_HEIGHT * genre_share.len() as f32;
+
+    let entries = genre_share
+        .into_iter()
+        .map(|(genre, count)| {
+            ChartEntry::new(
+                genre.to_string(),
+                count as f32,
+                format!(
+                    "{} ({:.0}%)",
+                    count,
+                    count as f32 * 100.0 / watched_series.len() as f32
+                ),
+            )
+        })
+        .collect();
+
+    let content = column![
+        text("Genre Stats"),
+        canvas(BarChart::horizontal(entries))
+            .width(Length::Fill)
+            .height(chart_height)
+    ]
+    .align_x(Alignment::Center)
+    .spacing(10)
+    .width(Length::Fill)
+    .padding(10);
 
     let content = scrollable(content)
         .direction(styles::scrollable_styles::vertical_direction())
@@ -165,6 +316,74 @@ pub fn genre_stats(series_infos: Vec<&SeriesMainInformation>) -> Element<'_, Mes
         .into()
 }
 
+pub fn watch_history_stats(
+    watch_history: &[PeriodWatches],
+    chart_period: ChartPeriod,
+) -> Element<'static, Message> {
+    let label_format = match chart_period {
+        ChartPeriod::Week => "%b %-d",
+        ChartPeriod::Month => "%b %Y",
+    };
+
+    let mut episode_entries = Vec::with_capacity(watch_history.len());
+    let mut hour_entries = Vec::with_capacity(watch_history.len());
+    for period_watches in watch_history {
+        let label = period_watches.start.format(label_format).to_string();
+        let hours = period_watches.cumulative_minutes as f32 / 60.0;
+
+        episode_entries.push(ChartEntry::new(
+            label.clone(),
+            period_watches.episodes as f32,
+            period_watches.episodes.to_string(),
+        ));
+        hour_entries.push(ChartEntry::new(label, hours, format!("{:.0} hours", hours)));
+    }
+
+    let controls = pick_list(
+        ALL_CHART_PERIODS,
+        Some(chart_period),
+        Message::ChartPeriodSelected,
+    )
+    .text_size(11);
+
+    let charts = row![
+        column![
+            text("Episodes watched").size(13),
+            canvas(BarChart::vertical(episode_entries))
+                .width(Length::Fill)
+                .height(Length::Fill)
+        ]
+        .spacing(5),
+        column![
+            text("Cumulative hours").size(13),
+            canvas(LineChart::new(hour_entries))
+                .width(Length::Fill)
+                .height(Length::Fill)
+        ]
+        .spacing(5),
+    ]
+    .spacing(20);
+
+    let content = column![
+        row![
+            text("Watch history"),
+            Space::with_width(Length::Fill),
+            controls
+        ]
+        .align_y(Alignment::Center),
+        charts,
+        text("Episodes marked watched before the watch times started being recorded count on their air date").size(11),
+    ]
+    .spacing(10);
+
+    container(content)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10)
+        .style(styles::container_styles::first_class_container_rounded_theme)
+        .into()
+}
+
 pub mod series_banner {
     use std::sync::mpsc;
 
@@ -1,13 +1,22 @@
 use std::sync::mpsc;
 
 use iced::widget::scrollable::{RelativeOffset, Viewport};
-use iced::widget::{column, container, row, scrollable};
+use iced::widget::{
+    button, column, container, horizontal_space, pick_list, row, scrollable, text, Space,
+};
 use iced::{Element, Length, Task};
 use iced_aw::Wrap;
 
-use crate::core::{api::tv_maze::series_information::SeriesMainInformation, database};
+use crate::core::api::tv_maze::series_information::SeriesMainInformation;
+use crate::core::date_time_display::DateTimeDisplay;
+use crate::core::statistics::{
+    self, BreakdownEntry, BreakdownKind, BreakdownSorting, ChartPeriod, PeriodWatches,
+    ProgressSorting, SeriesProgress, WatchedSeries,
+};
+use crate::core::year_in_review;
 use crate::gui::assets::icons::GRAPH_UP_ARROW;
 use crate::gui::styles;
+use goals_widget::{Goals, Message as GoalsMessage};
 use series_banner::{IndexedMessage, Message as SeriesBannerMessage, SeriesBanner};
 
 use mini_widgets::*;
@@ -15,18 +24,45 @@ use mini_widgets::*;
 use super::tab_searching::{unavailable_posters, Message as SearcherMessage, Searchable, Searcher};
 use super::Tab;
 
+mod charts;
+mod goals_widget;
 mod mini_widgets;
 
 #[derive(Clone, Debug)]
 pub enum Message {
-    SeriesInfosAndTimeReceived(Vec<(SeriesMainInformation, Option<u32>)>),
+    WatchedSeriesReceived(Vec<WatchedSeries>),
+    BreakdownKindSelected(BreakdownKind),
+    BreakdownSortingSelected(BreakdownSorting),
+    ProgressSortingSelected(ProgressSorting),
+    ChartPeriodSelected(ChartPeriod),
+    Goals(GoalsMessage),
+    YearInReviewYearSelected(i32),
+    ExportYearInReviewPressed,
+    /// `None` when no directory was chosen to export to
+    YearInReviewExported(Option<Result<String, String>>),
+    YearInReviewExportStatusTimeout,
     SeriesBanner(IndexedMessage<usize, SeriesBannerMessage>),
     PageScrolled(Viewport),
     Searcher(SearcherMessage),
 }
 
 pub struct StatisticsTab<'a> {
-    series_infos_and_time: Vec<(SeriesMainInformation, Option<u32>)>,
+    watched_series: Vec<WatchedSeries>,
+    breakdown_kind: BreakdownKind,
+    breakdown_sorting: BreakdownSorting,
+    progress_sorting: ProgressSorting,
+    chart_period: ChartPeriod,
+    // The statistics are worked out whenever the watched series or their controls change rather
+    // than every time they are viewed
+    breakdown: Vec<BreakdownEntry>,
+    series_progress: Vec<SeriesProgress>,
+    furthest_behind: Vec<SeriesProgress>,
+    watch_history: Vec<PeriodWatches>,
+    goals: Goals,
+    /// The year chosen for the year in review, the most recent year with watches when not chosen
+    year_in_review_year: Option<i32>,
+    /// The directory of the last year in review export or why it failed
+    year_in_review_export_status: Option<Result<String, String>>,
     series_banners: Vec<SeriesBanner<'a>>,
     series_page_sender: mpsc::Sender<SeriesMainInformation>,
     scrollable_offset: RelativeOffset,
@@ -40,39 +76,55 @@ impl StatisticsTab<'_> {
         series_page_sender: mpsc::Sender<SeriesMainInformation>,
         scrollable_offset: Option<RelativeOffset>,
     ) -> (Self, Task<Message>) {
+        let (goals, goals_command) = Goals::new();
         (
             Self {
-                series_infos_and_time: vec![],
+                watched_series: vec![],
+                breakdown_kind: BreakdownKind::Network,
+                breakdown_sorting: BreakdownSorting::Series,
+                progress_sorting: ProgressSorting::LeastCompleted,
+                chart_period: ChartPeriod::Week,
+                breakdown: vec![],
+                series_progress: vec![],
+                furthest_behind: vec![],
+                watch_history: vec![],
+                goals,
+                year_in_review_year: None,
+                year_in_review_export_status: None,
                 series_banners: vec![],
                 series_page_sender,
                 scrollable_offset: scrollable_offset.unwrap_or(RelativeOffset::START),
                 matched_id_collection: None,
                 searcher: Searcher::new("Search Statistics".to_owned()),
             },
-            Task::perform(
-                get_series_with_runtime(),
-                Message::SeriesInfosAndTimeReceived,
-            ),
+            Task::batch([
+                Task::perform(
+                    statistics::get_watched_series(),
+                    Message::WatchedSeriesReceived,
+                ),
+                goals_command.map(Message::Goals),
+            ]),
         )
     }
 
     pub fn update(&mut self, message: Message) -> Task<Message> {
         match message {
-            Message::SeriesInfosAndTimeReceived(mut series_infos_and_time) => {
-                self.series_infos_and_time
-                    .clone_from(&series_infos_and_time);
+            Message::WatchedSeriesReceived(mut watched_series) => {
+                self.watched_series.clone_from(&watched_series);
+                self.refresh_breakdown();
+                self.refresh_series_progress();
+                self.furthest_behind = statistics::furthest_behind(&self.watched_series);
+                self.refresh_watch_history();
 
-                series_infos_and_time.sort_by(|(_, average_minutes_a), (_, average_minutes_b)| {
-                    average_minutes_b.cmp(average_minutes_a)
-                });
+                watched_series.sort_by_key(|series| std::cmp::Reverse(series.watch_time));
 
-                let mut banners = Vec::with_capacity(series_infos_and_time.len());
-                let mut banners_commands = Vec::with_capacity(series_infos_and_time.len());
-                for (index, series_info_and_time) in series_infos_and_time.into_iter().enumerate() {
+                let mut banners = Vec::with_capacity(watched_series.len());
+                let mut banners_commands = Vec::with_capacity(watched_series.len());
+                for (index, series) in watched_series.into_iter().enumerate() {
                     let (banner, banner_command) = SeriesBanner::new(
                         index,
-                        std::borrow::Cow::Owned(series_info_and_time.0),
-                        series_info_and_time.1,
+                        std::borrow::Cow::Owned(series.series_info),
+                        series.watch_time,
                         self.series_page_sender.clone(),
                     );
                     banners.push(banner);
@@ -81,6 +133,52 @@ impl StatisticsTab<'_> {
                 self.series_banners = banners;
                 Task::batch(banners_commands).map(Message::SeriesBanner)
             }
+            Message::BreakdownKindSelected(kind) => {
+                self.breakdown_kind = kind;
+                self.refresh_breakdown();
+                Task::none()
+            }
+            Message::BreakdownSortingSelected(sorting) => {
+                self.breakdown_sorting = sorting;
+                self.refresh_breakdown();
+                Task::none()
+            }
+            Message::ProgressSortingSelected(sorting) => {
+                self.progress_sorting = sorting;
+                self.refresh_series_progress();
+                Task::none()
+            }
+            Message::ChartPeriodSelected(period) => {
+                self.chart_period = period;
+                self.refresh_watch_history();
+                Task::none()
+            }
+            Message::Goals(message) => self.goals.update(message).map(Message::Goals),
+            Message::YearInReviewYearSelected(year) => {
+                self.year_in_review_year = Some(year);
+                Task::none()
+            }
+            Message::ExportYearInReviewPressed => match self.year_in_review_year() {
+                Some(year) => Task::perform(
+                    year_in_review_export::export(self.watched_series.clone(), year),
+                    Message::YearInReviewExported,
+                ),
+                None => Task::none(),
+            },
+            Message::YearInReviewExported(export_result) => {
+                if export_result.is_none() {
+                    return Task::none();
+                }
+                self.year_in_review_export_status = export_result;
+                Task::perform(
+                    tokio::time::sleep(std::time::Duration::from_secs(3)),
+                    |_| Message::YearInReviewExportStatusTimeout,
+                )
+            }
+            Message::YearInReviewExportStatusTimeout => {
+                self.year_in_review_export_status = None;
+                Task::none()
+            }
             Message::SeriesBanner(message) => {
                 self.series_banners[message.index()].update(message);
                 Task::none()
@@ -97,7 +195,7 @@ impl StatisticsTab<'_> {
             }
         }
     }
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
         let series_list: Element<'_, Message> = if self.series_banners.is_empty() {
             Self::empty_statistics_posters()
         } else {
@@ -121,71 +219,147 @@ impl StatisticsTab<'_> {
                     .spacing(5.0)
                     .line_spacing(5.0);
 
-                scrollable(container(series_list).padding(10).center_x(Length::Fill))
-                    .id(Self::scrollable_id())
-                    .on_scroll(Message::PageScrolled)
-                    .direction(styles::scrollable_styles::vertical_direction())
+                container(series_list)
+                    .padding(10)
+                    .center_x(Length::Fill)
                     .into()
             }
         };
 
-        let series_infos: Vec<&SeriesMainInformation> = self
-            .series_infos_and_time
-            .iter()
-            .map(|(series_info, _)| series_info)
-            .collect();
-
         let searcher = self.searcher.view().map(Message::Searcher);
 
-        column![
+        let content = column![
+            self.year_in_review_widget(),
             row![
                 watch_count(),
-                genre_stats(series_infos),
-                time_count(&self.series_infos_and_time)
+                genre_stats(&self.watched_series),
+                time_count(&self.watched_series),
+                rating_stats(statistics::average_rating(&self.watched_series)),
             ]
             .height(200)
             .spacing(10),
-            searcher,
+            row![
+                breakdown_stats(&self.breakdown, self.breakdown_kind, self.breakdown_sorting),
+                progress_stats(&self.series_progress, self.progress_sorting),
+                furthest_behind_stats(&self.furthest_behind),
+            ]
+            .height(300)
+            .spacing(10),
+            container(watch_history_stats(&self.watch_history, self.chart_period)).height(250),
+            container(self.goals.view(&self.watched_series).map(Message::Goals)).height(250),
             series_list
         ]
+        .spacing(10);
+
+        let content = scrollable(content)
+            .id(Self::scrollable_id())
+            .on_scroll(Message::PageScrolled)
+            .direction(styles::scrollable_styles::vertical_direction());
+
+        column![searcher, content].spacing(10).padding(10).into()
+    }
+
+    fn refresh_breakdown(&mut self) {
+        self.breakdown = statistics::breakdown(
+            &self.watched_series,
+            self.breakdown_kind,
+            self.breakdown_sorting,
+        );
+    }
+
+    fn refresh_series_progress(&mut self) {
+        self.series_progress =
+            statistics::series_progress(&self.watched_series, self.progress_sorting);
+    }
+
+    fn refresh_watch_history(&mut self) {
+        self.watch_history = statistics::watch_history(
+            &self.watched_series,
+            self.chart_period,
+            DateTimeDisplay::from_settings().today(),
+        );
+    }
+
+    fn year_in_review_year(&self) -> Option<i32> {
+        self.year_in_review_year.or_else(|| {
+            year_in_review::get_years(&self.watched_series)
+                .first()
+                .copied()
+        })
+    }
+
+    fn year_in_review_widget(&self) -> Element<'_, Message> {
+        let export_status: Element<'_, Message> = match &self.year_in_review_export_status {
+            Some(Ok(directory)) => text(format!("Year in review exported to {}", directory))
+                .style(styles::text_styles::green_text_theme)
+                .into(),
+            Some(Err(err)) => text(err).style(styles::text_styles::red_text_theme).into(),
+            None => Space::new(0, 0).into(),
+        };
+
+        let year = self.year_in_review_year();
+
+        row![
+            horizontal_space(),
+            export_status,
+            pick_list(
+                year_in_review::get_years(&self.watched_series),
+                year,
+                Message::YearInReviewYearSelected
+            )
+            .placeholder("no watched episodes"),
+            button("Export year in review")
+                .on_press_maybe(year.map(|_| Message::ExportYearInReviewPressed)),
+        ]
         .spacing(10)
-        .padding(10)
+        .align_y(iced::Alignment::Center)
         .into()
     }
 
     fn empty_statistics_posters() -> Element<'static, Message> {
         unavailable_posters("Your watched series will appear here")
             .width(Length::Fill)
-            .height(Length::Fill)
+            .height(200)
             .into()
     }
 
     fn no_search_matches() -> Element<'static, Message> {
         unavailable_posters("No matches found!")
             .width(Length::Fill)
-            .height(Length::Fill)
+            .height(200)
             .into()
     }
 }
 
-/// Get the collection of all series with their associated total
-/// average runtime
-async fn get_series_with_runtime() -> Vec<(SeriesMainInformation, Option<u32>)> {
-    let series_ids_handles: Vec<_> = database::DB
-        .get_series_collection()
-        .into_iter()
-        .map(|series| tokio::spawn(async move { series.get_total_average_watchtime().await }))
-        .collect();
-
-    let mut infos_and_time = Vec::with_capacity(series_ids_handles.len());
-    for handle in series_ids_handles {
-        infos_and_time.push(
-            handle
+mod year_in_review_export {
+    use directories::UserDirs;
+    use rfd::AsyncFileDialog;
+
+    use crate::core::statistics::WatchedSeries;
+    use crate::core::year_in_review;
+
+    /// Exports the year in review of the given year to the directory chosen by the user
+    ///
+    /// Returns `None` when no directory was chosen.
+    pub async fn export(
+        watched_series: Vec<WatchedSeries>,
+        year: i32,
+    ) -> Option<Result<String, String>> {
+        let mut folder_dialog = AsyncFileDialog::new();
+
+        if let Some(user_dirs) = UserDirs::new() {
+            folder_dialog = folder_dialog.set_directory(user_dirs.home_dir());
+        }
+
+        let chosen_directory = folder_dialog.pick_folder().await?.path().to_owned();
+
+        Some(
+            year_in_review::export(watched_series, year, &chosen_directory)
                 .await
-                .expect("failed to await all series_infos and their average runtime"),
-        );
+                .map(|_| chosen_directory.display().to_string())
+                .map_err(|err| err.to_string()),
+        )
     }
-    infos_and_time
 }
 
 impl Tab for StatisticsTab<'_> {
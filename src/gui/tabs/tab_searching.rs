@@ -23,7 +23,7 @@ pub trait Searchable {
 
     /// Checks if a particular `SeriesMainInformation` id is among the matched ids after a search
     fn is_matched_id(&self, matched_ids: &[u32], id: u32) -> bool {
-        matched_ids.iter().any(|matched_id| *matched_id == id)
+        matched_ids.contains(&id)
     }
 
     /// Updates the matched series ids of the tab based on the search term
@@ -110,7 +110,7 @@ impl Searcher {
 
 /// A helper function that produces an `Element` that indicates absence of series posters based
 /// on the supplied absence reason
-pub fn unavailable_posters<Message: 'static>(absence_reason: &str) -> Container<Message> {
+pub fn unavailable_posters<Message: 'static>(absence_reason: &str) -> Container<'_, Message> {
     container(text(absence_reason).align_x(iced::alignment::Horizontal::Center))
         .center_x(Length::Shrink)
         .center_y(Length::Shrink)
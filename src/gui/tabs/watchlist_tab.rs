@@ -9,7 +9,9 @@ use super::tab_searching::{unavailable_posters, Message as SearcherMessage, Sear
 use super::Tab;
 use crate::core::api::tv_maze::series_information::SeriesMainInformation;
 use crate::core::caching::episode_list::EpisodeList;
+use crate::core::caching::fetch_limiter::FETCH_LIMITER;
 use crate::core::caching::series_list;
+use crate::core::goals::{self, GoalsOverview};
 use crate::core::{caching, database};
 use crate::gui::assets::icons::CARD_CHECKLIST;
 use crate::gui::message::IndexedMessage;
@@ -20,6 +22,7 @@ use watchlist_summary::WatchlistSummary;
 #[derive(Debug, Clone)]
 pub enum Message {
     SeriesInformationLoaded(Vec<(SeriesMainInformation, EpisodeList, usize)>),
+    GoalsOverviewLoaded(GoalsOverview),
     WatchlistPoster(IndexedMessage<usize, WatchlistPosterMessage>),
     PageScrolled(Viewport),
     Searcher(SearcherMessage),
@@ -36,6 +39,7 @@ pub struct WatchlistTab<'a> {
     load_state: LoadState,
     watchlist_posters: Vec<WatchlistPoster<'a>>,
     watchlist_summary: Option<WatchlistSummary>,
+    goals_overview: GoalsOverview,
     series_page_sender: mpsc::Sender<SeriesMainInformation>,
     scrollable_offset: RelativeOffset,
     /// A collection of matched series id after a fuzzy search
@@ -52,16 +56,20 @@ impl WatchlistTab<'_> {
             Self {
                 watchlist_posters: vec![],
                 watchlist_summary: None,
+                goals_overview: GoalsOverview::default(),
                 load_state: LoadState::Loading,
                 series_page_sender,
                 scrollable_offset: scrollable_offset.unwrap_or(RelativeOffset::START),
                 matched_id_collection: None,
                 searcher: Searcher::new("Search Watchlist".to_owned()),
             },
-            Task::perform(
-                get_series_information_and_watched_episodes(),
-                Message::SeriesInformationLoaded,
-            ),
+            Task::batch([
+                Task::perform(
+                    get_series_information_and_watched_episodes(),
+                    Message::SeriesInformationLoaded,
+                ),
+                Task::perform(goals::get_goals_overview(), Message::GoalsOverviewLoaded),
+            ]),
         )
     }
 
@@ -109,9 +117,30 @@ impl WatchlistTab<'_> {
 
                 Task::batch(commands).map(Message::WatchlistPoster)
             }
-            Message::WatchlistPoster(message) => self.watchlist_posters[message.index()]
-                .update(message)
-                .map(Message::WatchlistPoster),
+            Message::GoalsOverviewLoaded(goals_overview) => {
+                self.goals_overview = goals_overview;
+                Task::none()
+            }
+            Message::WatchlistPoster(message) => {
+                // Marking episodes watched moves the goals and streaks along
+                let is_episode_message = matches!(
+                    message.clone().message(),
+                    WatchlistPosterMessage::EpisodePoster(_)
+                );
+
+                let poster_command = self.watchlist_posters[message.index()]
+                    .update(message)
+                    .map(Message::WatchlistPoster);
+
+                if is_episode_message {
+                    Task::batch([
+                        poster_command,
+                        Task::perform(goals::get_goals_overview(), Message::GoalsOverviewLoaded),
+                    ])
+                } else {
+                    poster_command
+                }
+            }
             Message::PageScrolled(view_port) => {
                 self.scrollable_offset = view_port.relative_offset();
                 Task::none()
@@ -124,7 +153,7 @@ impl WatchlistTab<'_> {
             }
         }
     }
-    pub fn view(&self) -> Element<Message> {
+    pub fn view(&self) -> Element<'_, Message> {
         match self.load_state {
             LoadState::Loading => center(Spinner::new()).into(),
             LoadState::Loaded => {
@@ -134,7 +163,7 @@ impl WatchlistTab<'_> {
                     let watchlist_summary = self
                         .watchlist_summary
                         .as_ref()
-                        .map(|watchlist_summary| watchlist_summary.view())
+                        .map(|watchlist_summary| watchlist_summary.view(&self.goals_overview))
                         .unwrap_or(Space::new(0, 0).into());
 
                     let watchlist_items: Vec<Element<'_, Message>> = self
@@ -212,7 +241,9 @@ async fn get_series_information_and_watched_episodes(
 
     let episode_lists_handles: Vec<_> = tracked_series_information
         .iter()
-        .map(|series_info| tokio::spawn(caching::episode_list::EpisodeList::new(series_info.id)))
+        .map(|series_info| {
+            FETCH_LIMITER.spawn(caching::episode_list::EpisodeList::new(series_info.id))
+        })
         .collect();
 
     let mut episodes_lists = Vec::with_capacity(episode_lists_handles.len());
@@ -227,7 +258,7 @@ async fn get_series_information_and_watched_episodes(
 
     tracked_series_information
         .into_iter()
-        .zip(episodes_lists.into_iter())
+        .zip(episodes_lists)
         .filter(|(series_info, episode_list)| {
             let series = database::DB.get_series(series_info.id).unwrap();
             has_pending_episodes(&series, episode_list)
@@ -516,12 +547,16 @@ mod watchlist_poster {
 
 mod watchlist_summary {
     use crate::core::database;
+    use crate::core::date_time_display::DateTimeDisplay;
+    use crate::core::goals::GoalsOverview;
     use crate::gui::helpers::time::NaiveTime;
     use crate::gui::styles;
+    use crate::gui::troxide_widget;
 
     use super::Message;
-    use iced::widget::{column, container, progress_bar, row, text};
+    use iced::widget::{column, container, progress_bar, row, text, Space};
     use iced::{Alignment, Element};
+    use iced_aw::Wrap;
 
     pub struct WatchlistSummary {
         /// Vec<(series id, total_episodes, series runtime)>
@@ -537,7 +572,7 @@ mod watchlist_summary {
             }
         }
 
-        pub fn view(&self) -> Element<'static, Message> {
+        pub fn view(&self, goals_overview: &GoalsOverview) -> Element<'static, Message> {
             let total_shows_to_watch =
                 Self::summary_item("Total Series to Watch", self.series_ids.len().to_string());
 
@@ -610,6 +645,7 @@ mod watchlist_summary {
                 ]
                 .spacing(20),
                 progress,
+                Self::goals(goals_overview),
             ]
             .spacing(20)
             .align_x(Alignment::Center);
@@ -620,6 +656,38 @@ mod watchlist_summary {
                 .into()
         }
 
+        fn goals(goals_overview: &GoalsOverview) -> Element<'static, Message> {
+            if goals_overview.goals.is_empty() && goals_overview.streaks.longest_days == 0 {
+                return Space::new(0, 0).into();
+            }
+
+            let date_time_display = DateTimeDisplay::from_settings();
+            let goals = Wrap::with_elements(
+                goals_overview
+                    .goals
+                    .iter()
+                    .map(|progress| {
+                        container(troxide_widget::goals::goal_progress(
+                            progress,
+                            &date_time_display,
+                        ))
+                        .width(250)
+                        .into()
+                    })
+                    .collect(),
+            )
+            .spacing(20.0)
+            .line_spacing(10.0);
+
+            column![
+                troxide_widget::goals::streaks(&goals_overview.streaks),
+                goals
+            ]
+            .spacing(10)
+            .align_x(Alignment::Center)
+            .into()
+        }
+
         fn summary_item(title: &'static str, info: String) -> Element<'static, Message> {
             column![
                 text(title),
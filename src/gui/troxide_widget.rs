@@ -54,8 +54,9 @@ pub mod episode_widget {
             };
 
             let command = if let Some(image) = episode_image {
+                let (image_url, image_resolution) = caching::episode_still_source(&image);
                 Task::perform(
-                    caching::load_image(image.medium_image_url, caching::ImageResolution::Medium),
+                    caching::load_image(image_url, image_resolution),
                     Message::ImageLoaded,
                 )
                 .map(move |message| IndexedMessage::new(index, message))
@@ -95,6 +96,12 @@ pub mod episode_widget {
                                 let mut series = database::Series::new(series_name, series_id);
                                 series.add_episode_unchecked(season_number, episode_number)
                             }
+                            database::DB.set_episode_watch_time(
+                                series_id,
+                                season_number,
+                                episode_number,
+                                chrono::Local::now(),
+                            );
 
                             Task::none()
                         }
@@ -253,6 +260,7 @@ pub mod series_poster {
     use crate::core::api::tv_maze::series_information::{Rating, SeriesMainInformation};
     use crate::core::api::tv_maze::Image;
     use crate::core::caching;
+    use crate::core::date_time_display::DateTimeDisplay;
     use crate::core::posters_hiding::HIDDEN_SERIES;
     use crate::gui::assets::icons::{EYE_SLASH_FILL, STAR_FILL};
     use crate::gui::helpers;
@@ -318,7 +326,7 @@ pub mod series_poster {
                     async move {
                         caching::load_image(
                             image.medium_image_url,
-                            caching::ImageResolution::Medium,
+                            caching::ImageResolution::Medium(caching::ImageKind::PosterThumb),
                         )
                         .await
                     },
@@ -494,7 +502,12 @@ pub mod series_poster {
 
         fn premier_widget(premier_date: Option<&str>) -> Element<'_, Message> {
             if let Some(premier_date) = premier_date {
-                text(format!("Premiered: {}", premier_date)).size(11).into()
+                text(format!(
+                    "Premiered: {}",
+                    DateTimeDisplay::from_settings().date_str(premier_date)
+                ))
+                .size(11)
+                .into()
             } else {
                 Space::new(0, 0).into()
             }
@@ -525,12 +538,99 @@ pub mod series_poster {
     }
 }
 
+pub mod goals {
+    //! Widgets showing the progress of the watching goals and the streaks
+
+    use iced::widget::{column, progress_bar, row, text, Space};
+    use iced::{Alignment, Element, Length};
+
+    use crate::core::date_time_display::DateTimeDisplay;
+    use crate::core::goals::{Goal, GoalProgress, GoalStatus, Streaks};
+    use crate::gui::styles;
+
+    pub fn goal_progress<'a, Message: 'a>(
+        progress: &GoalProgress,
+        date_time_display: &DateTimeDisplay,
+    ) -> Element<'a, Message> {
+        let status = text(progress.status.to_string()).size(11);
+        let status = match progress.status {
+            GoalStatus::Achieved => status.style(styles::text_styles::green_text_theme),
+            GoalStatus::OnTrack => status.style(styles::text_styles::accent_color_theme),
+            GoalStatus::Behind | GoalStatus::Missed => {
+                status.style(styles::text_styles::red_text_theme)
+            }
+        };
+
+        let episodes = match progress.goal {
+            Goal::EpisodesPerWeek(_) => {
+                format!("{}/{} episodes this week", progress.done, progress.target)
+            }
+            Goal::FinishSeries { .. } => {
+                format!("{}/{} episodes", progress.done, progress.target)
+            }
+        };
+        let details = match progress.status {
+            GoalStatus::OnTrack | GoalStatus::Behind => format!(
+                "{}, {} more in {} days",
+                episodes,
+                progress.remaining(),
+                progress.days_left
+            ),
+            GoalStatus::Achieved | GoalStatus::Missed => episodes,
+        };
+
+        column![
+            row![
+                text(progress.goal.description(date_time_display)).size(13),
+                Space::with_width(Length::Fill),
+                status,
+            ]
+            .align_y(Alignment::Center),
+            progress_bar(0.0..=1.0, progress.completion()).height(5),
+            text(details).size(11),
+        ]
+        .spacing(2)
+        .into()
+    }
+
+    pub fn streaks<'a, Message: 'a>(streaks: &Streaks) -> Element<'a, Message> {
+        row![
+            streak_count(streaks.current_days, "Day streak"),
+            streak_count(streaks.current_weeks, "Week streak"),
+            text(format!(
+                "Longest: {} days, {} weeks",
+                streaks.longest_days, streaks.longest_weeks
+            ))
+            .size(11),
+        ]
+        .spacing(20)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn streak_count<'a, Message: 'a>(count: usize, title: &'static str) -> Element<'a, Message> {
+        column![
+            text(count)
+                .size(24)
+                .style(styles::text_styles::accent_color_theme),
+            text(title).size(11),
+        ]
+        .align_x(Alignment::Center)
+        .into()
+    }
+}
+
 pub mod title_bar {
+    use iced::futures::{SinkExt, Stream};
     use iced::widget::{
-        button, container, horizontal_space, mouse_area, row, svg, text, Row, Space,
+        button, container, horizontal_space, mouse_area, progress_bar, row, svg, text, Row, Space,
     };
-    use iced::{Element, Length};
+    use iced::{Alignment, Element, Length};
 
+    use crate::core::caching::cache_updating::get_last_update_time;
+    use crate::core::caching::fetch_limiter::{FetchProgress, FETCH_LIMITER};
+    use crate::core::date_time_display::DateTimeDisplay;
+    use crate::core::offline::{ConnectionStatus, OFFLINE};
     use crate::gui::assets::icons::CARET_LEFT_FILL;
     use crate::gui::styles;
     use crate::gui::tabs::TabLabel;
@@ -545,6 +645,13 @@ pub mod title_bar {
         active_tab: usize,
     }
 
+    /// The connection status together with when the cached data was last updated
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct ConnectionState {
+        pub status: ConnectionStatus,
+        pub data_from: Option<chrono::DateTime<chrono::Local>>,
+    }
+
     impl TitleBar {
         pub fn new() -> Self {
             Self {
@@ -562,6 +669,8 @@ pub mod title_bar {
             &self,
             tab_labels: &[TabLabel],
             show_back_button: bool,
+            fetch_progress: FetchProgress,
+            connection_state: ConnectionState,
         ) -> iced::Element<'_, Message> {
             let tab_views = tab_labels.iter().enumerate().map(|(index, tab_label)| {
                 let svg_handle = svg::Handle::from_memory(tab_label.icon);
@@ -601,10 +710,97 @@ pub mod title_bar {
                 back_button,
                 horizontal_space(),
                 tab_views,
-                horizontal_space()
+                horizontal_space(),
+                fetch_progress_widget(fetch_progress),
+                offline_indicator(connection_state),
             ])
             .style(styles::container_styles::first_class_container_square_theme)
             .into()
         }
     }
+
+    fn fetch_progress_widget(fetch_progress: FetchProgress) -> Element<'static, Message> {
+        if !fetch_progress.is_active() {
+            return Space::new(0, 0).into();
+        }
+
+        row![
+            text("fetching series").size(11),
+            progress_bar(
+                0.0..=fetch_progress.total as f32,
+                fetch_progress.completed as f32
+            )
+            .width(100)
+            .height(8),
+            text(format!(
+                "{} / {}",
+                fetch_progress.completed, fetch_progress.total
+            ))
+            .size(11),
+        ]
+        .spacing(5)
+        .padding(5)
+        .align_y(Alignment::Center)
+        .into()
+    }
+
+    fn offline_indicator(connection_state: ConnectionState) -> Element<'static, Message> {
+        let offline_reason = match connection_state.status {
+            ConnectionStatus::Online => return Space::new(0, 0).into(),
+            ConnectionStatus::OfflineMode => "offline",
+            ConnectionStatus::Disconnected => "offline (no connection)",
+        };
+
+        let indicator_text = match connection_state.data_from {
+            Some(data_from) => format!(
+                "{}, data from {}",
+                offline_reason,
+                DateTimeDisplay::from_settings().date_time(&data_from)
+            ),
+            None => format!("{}, cached data", offline_reason),
+        };
+
+        container(
+            text(indicator_text)
+                .size(11)
+                .style(styles::text_styles::red_text_theme),
+        )
+        .padding(5)
+        .into()
+    }
+
+    /// Watches the connection status, see the `offline` module
+    pub fn connection_state_watcher() -> impl Stream<Item = ConnectionState> {
+        iced::stream::channel(100, |mut output| async move {
+            let mut status_receiver = OFFLINE.subscribe();
+
+            loop {
+                let status = *status_receiver.borrow_and_update();
+                let connection_state = ConnectionState {
+                    status,
+                    data_from: get_last_update_time().await,
+                };
+
+                if output.send(connection_state).await.is_err()
+                    || status_receiver.changed().await.is_err()
+                {
+                    break;
+                }
+            }
+        })
+    }
+
+    /// Watches the progress of the fetches spawned through the `FETCH_LIMITER`
+    pub fn fetch_progress_watcher() -> impl Stream<Item = FetchProgress> {
+        iced::stream::channel(100, |mut output| async move {
+            let mut progress_receiver = FETCH_LIMITER.subscribe();
+
+            while progress_receiver.changed().await.is_ok() {
+                let fetch_progress = *progress_receiver.borrow_and_update();
+                if output.send(fetch_progress).await.is_err() {
+                    break;
+                }
+            }
+        })
+    }
 }
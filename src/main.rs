@@ -14,7 +14,10 @@ fn main() -> anyhow::Result<()> {
     std::thread::spawn(|| {
         if let Err(err) = tokio::runtime::Runtime::new()
             .expect("failed to create tokio runtime")
-            .block_on(core::caching::cache_updating::update_cache())
+            .block_on(async {
+                core::caching::image_cache::limit_image_cache_size().await;
+                core::caching::cache_updating::update_cache().await
+            })
         {
             tracing::error!("failed to update cache: {}", err)
         };
@@ -22,6 +25,15 @@ fn main() -> anyhow::Result<()> {
 
     std::thread::spawn(|| core::notifications::TroxideNotify::new()?.run());
 
+    std::thread::spawn(|| {
+        if let Err(err) = tokio::runtime::Runtime::new()
+            .expect("failed to create tokio runtime")
+            .block_on(core::calendar::feed::serve_if_enabled())
+        {
+            tracing::error!("failed to serve calendar feed: {}", err)
+        };
+    });
+
     // TODO: set the window icon
     let _icon = window::icon::from_file_data(gui::assets::logos::IMG_LOGO, None).ok();
 